@@ -0,0 +1,157 @@
+use rand::{Rng, rng};
+use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+use std::time::{Duration, Instant};
+
+const INTRO_LENGTH: Duration = Duration::from_millis(350);
+const LINE_FLASH_LENGTH: Duration = Duration::from_millis(1500);
+const CONFETTI_LENGTH: Duration = Duration::from_millis(2500);
+const CONFETTI_PARTICLES: usize = 80;
+const CONFETTI_SYMBOLS: [&str; 6] = ["*", "+", "o", "~", "•", "°"];
+const CONFETTI_COLORS: [Color; 6] = [
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+];
+// In area heights per second², pulls the confetti back down after the burst
+const GRAVITY: f32 = 1.6;
+
+#[derive(Debug)]
+struct Particle {
+    // start position and velocity, relative to the area (0..1)
+    x: f32,
+    y: f32,
+    dx: f32,
+    dy: f32,
+    symbol: &'static str,
+    color: Color,
+}
+
+#[derive(Debug)]
+enum Kind {
+    Intro,
+    LineFlash,
+    Confetti(Vec<Particle>),
+}
+
+#[derive(Debug)]
+struct Animation {
+    kind: Kind,
+    started: Instant,
+    length: Duration,
+}
+
+impl Animation {
+    fn progress(&self) -> f32 {
+        (self.started.elapsed().as_secs_f32() / self.length.as_secs_f32()).min(1.)
+    }
+}
+
+/// Time based animations, advanced by the tick loop and drawn on top of the regular widgets.
+/// Everything is derived from the elapsed time so rendering never needs mutable access.
+#[derive(Debug)]
+pub struct Animations {
+    enabled: bool,
+    active: Vec<Animation>,
+}
+
+impl Animations {
+    pub fn new(enabled: bool) -> Self {
+        Animations {
+            enabled,
+            active: Vec::new(),
+        }
+    }
+
+    pub fn intro(&mut self) {
+        self.active.retain(|a| !matches!(a.kind, Kind::Intro));
+        self.start(Kind::Intro, INTRO_LENGTH);
+    }
+
+    pub fn line_flash(&mut self) {
+        self.active.retain(|a| !matches!(a.kind, Kind::LineFlash));
+        self.start(Kind::LineFlash, LINE_FLASH_LENGTH);
+    }
+
+    pub fn confetti(&mut self) {
+        let mut rng = rng();
+        let particles = (0..CONFETTI_PARTICLES)
+            .map(|_| Particle {
+                x: rng.random_range(0.3..0.7),
+                y: 1.,
+                dx: rng.random_range(-0.4..0.4),
+                dy: rng.random_range(-1.8..-0.9),
+                symbol: CONFETTI_SYMBOLS[rng.random_range(0..CONFETTI_SYMBOLS.len())],
+                color: CONFETTI_COLORS[rng.random_range(0..CONFETTI_COLORS.len())],
+            })
+            .collect();
+
+        self.start(Kind::Confetti(particles), CONFETTI_LENGTH);
+    }
+
+    fn start(&mut self, kind: Kind, length: Duration) {
+        if self.enabled {
+            self.active.push(Animation {
+                kind,
+                started: Instant::now(),
+                length,
+            });
+        }
+    }
+
+    /// Drops the animations that are over, called once per tick. Returns whether any was
+    /// running, the screen then needs a redraw.
+    pub fn tick(&mut self) -> bool {
+        let running = self.is_running();
+        self.active.retain(|a| a.progress() < 1.);
+        running
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.active.is_empty()
+    }
+
+    /// How much of the quote the intro has revealed so far, from 0 to 1.
+    pub fn intro_progress(&self) -> f32 {
+        self.active
+            .iter()
+            .find(|a| matches!(a.kind, Kind::Intro))
+            .map(|a| a.progress())
+            .unwrap_or(1.)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// How far along the flash of the last completed line is, if it is still showing.
+    pub fn line_flash_progress(&self) -> Option<f32> {
+        self.active
+            .iter()
+            .find(|a| matches!(a.kind, Kind::LineFlash))
+            .map(|a| a.progress())
+    }
+
+    pub fn render_confetti(&self, area: Rect, buf: &mut Buffer) {
+        for animation in &self.active {
+            let Kind::Confetti(particles) = &animation.kind else {
+                continue;
+            };
+            let t = animation.started.elapsed().as_secs_f32();
+
+            for p in particles {
+                let x = p.x + p.dx * t;
+                let y = p.y + p.dy * t + GRAVITY * t * t / 2.;
+                if !(0. ..1.).contains(&x) || !(0. ..1.).contains(&y) {
+                    continue;
+                }
+
+                let cx = area.x + (x * area.width as f32) as u16;
+                let cy = area.y + (y * area.height as f32) as u16;
+                buf[(cx, cy)].set_symbol(p.symbol).set_fg(p.color);
+            }
+        }
+    }
+}
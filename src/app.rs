@@ -0,0 +1,1336 @@
+use crossterm::{clipboard::CopyToClipboard, event::KeyCode, execute};
+use ratatui::{
+    Terminal,
+    backend::{Backend, CrosstermBackend, TestBackend},
+    layout::Rect,
+};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, BufReader},
+    time::{Duration, Instant},
+};
+use unicode_segmentation::UnicodeSegmentation;
+use wpm_core::{
+    achievements::{Achievement, Unlocked},
+    card,
+    challenge::Challenge,
+    content::{self, Book, Casing, Drill, Flow, Library, Practice, Quote, Registry},
+    course::{self, LESSONS, Progress},
+    custom, diff, endurance,
+    engine::{Milestone, Rules, TypingTest},
+    history,
+    keyboard::KeyFilter,
+    latency::Histogram,
+    leaderboard::{Cache, Standing},
+    letters::Letters,
+    mistakes,
+    race::{self, Bot, Player, Race},
+    rating::{Ratings, Tag},
+    replay::{self, Playback},
+    review,
+    rhythm::Cadence,
+    sim::Simulation,
+    sink::StatsSink,
+    stats::{self, Metric},
+    storage::{Job, Writer},
+    telemetry::{Frame, Telemetry},
+};
+
+use crate::{
+    animation::Animations,
+    config::Config,
+    gate::Gate,
+    homework::Homework,
+    i18n::{Locale, Strings},
+    input::InputSource,
+    session::{self, Session},
+    sound::{Cue, Sounds},
+    ui,
+};
+
+pub(crate) const MAX_LENGTH_PER_LINE: usize = 50;
+// Lines typed in one test when going through a book
+const BOOK_PAGE_LINES: usize = 6;
+// Keystrokes arriving faster than this can only come from pasted text
+const BURST_KEYS: usize = 12;
+const BURST_WINDOW: Duration = Duration::from_millis(60);
+// Longest text an input method commits at once, anything longer is a paste
+const MAX_COMMIT_LENGTH: usize = 16;
+// Redraw rate while the timer or an animation is running
+const TICK_RATE: Duration = Duration::from_millis(50);
+// Shortest time between two frames, bursts of input are drawn together
+const FRAME_INTERVAL: Duration = Duration::from_millis(1000 / 60);
+// How long to wait for input when nothing on screen moves
+const IDLE_TICK: Duration = Duration::from_secs(1);
+
+/// A terminal text can be copied through, so copying also works over SSH.
+pub trait Clipboard {
+    fn copy(&mut self, text: &str) -> io::Result<()>;
+}
+
+impl<W: io::Write> Clipboard for CrosstermBackend<W> {
+    /// With an escape sequence the terminal understands (OSC 52)
+    fn copy(&mut self, text: &str) -> io::Result<()> {
+        execute!(self, CopyToClipboard::to_clipboard_from(text))
+    }
+}
+
+impl Clipboard for TestBackend {
+    fn copy(&mut self, _text: &str) -> io::Result<()> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
+
+/// Everything that can happen to the app. Terminal events are turned into these by the input
+/// module, and [`App::update`] applies them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Msg {
+    Quit,
+    ToggleInfo,
+    ToggleLeaderboard,
+    ToggleAchievements,
+    ToggleHistory,
+    /// Moves the selection of the history browser
+    PreviousRow,
+    NextRow,
+    /// Opens the text of the selected test, or goes back to the list
+    OpenRow,
+    /// Selects a test of the history browser and opens it, from a click
+    SelectRow(usize),
+    /// Shows the intervals between keys in place of the speed over time
+    ToggleLatency,
+    /// Copies a summary of the result to the clipboard
+    CopyResult,
+    PreviousGroup,
+    NextGroup,
+    SelectGroup(usize),
+    Type(char),
+    /// Text sent in one go, by a paste or an input method
+    Paste(String),
+    Backspace,
+    DeleteWord,
+    Restart,
+    NewQuote,
+    /// Moves on to the next length group, with a new text of it
+    ChangeLength,
+    /// Shows what the text of the finished test was over its results
+    ToggleDetails,
+    /// Shows the result as a card to share, with a QR code of its challenge
+    ToggleShare,
+    /// Gives the quote just typed a tag, or takes it away
+    Rate(Tag),
+    /// Moves the selection of the results menu
+    PreviousAction,
+    NextAction,
+    /// Does what is selected in the results menu
+    Act,
+    Resize,
+    /// Time passed, moves the timer, animations and replays along
+    Tick,
+    TogglePause,
+    Faster,
+    Slower,
+}
+
+/// What can be done from the results, in the order of their menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Retry,
+    NewQuote,
+    ChangeLength,
+    Details,
+    Share,
+    Quit,
+}
+
+impl Action {
+    pub const ALL: [Action; 6] = [
+        Action::Retry,
+        Action::NewQuote,
+        Action::ChangeLength,
+        Action::Details,
+        Action::Share,
+        Action::Quit,
+    ];
+
+    /// The message doing it.
+    pub fn msg(self) -> Msg {
+        match self {
+            Action::Retry => Msg::Restart,
+            Action::NewQuote => Msg::NewQuote,
+            Action::ChangeLength => Msg::ChangeLength,
+            Action::Details => Msg::ToggleDetails,
+            Action::Share => Msg::ToggleShare,
+            Action::Quit => Msg::Quit,
+        }
+    }
+}
+
+/// The numbers shown while typing, as of some moment of the test.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct LiveStats {
+    pub(crate) elapsed: Duration,
+    pub(crate) speed: f32,
+    pub(crate) accuracy: f32,
+    pub(crate) correct: u32,
+    pub(crate) incorrect: u32,
+    pub(crate) fixed: u32,
+}
+
+#[derive(Debug)]
+pub struct App {
+    pub(crate) test: TypingTest,
+
+    // quotes looked up by id, for replays
+    pub(crate) library: &'static Library,
+    pub(crate) selected_group: usize,
+
+    // where the texts typed come from, the embedded quotes unless told otherwise
+    pub(crate) content: Registry,
+    pub(crate) sentence_source: String,
+    pub(crate) sentence_id: u32,
+    pub(crate) sentence_length: u32,
+    pub(crate) difficulty: f32,
+
+    pub(crate) history: Vec<history::Record>,
+    pub(crate) previous_best: Option<f32>,
+    // how quotes felt to type, rated on their results
+    pub(crate) ratings: Ratings,
+    // the result card was copied since the test finished
+    pub(crate) copied: bool,
+    // text for `run` to copy through the terminal
+    pub(crate) to_copy: Option<String>,
+    // standings as of the last `wpm sync`
+    pub(crate) leaderboard: Vec<Standing>,
+    // where results are sent, the history first by default
+    pub(crate) sinks: Vec<Box<dyn StatsSink>>,
+    // writes what is kept between runs besides results, none in tests
+    pub(crate) storage: Option<Writer>,
+
+    pub(crate) animations: Animations,
+    pub(crate) config: Config,
+    // size of the last drawn frame, used to resolve mouse clicks
+    pub(crate) area: Rect,
+    pub(crate) show_info: bool,
+    // the results show the intervals between keys rather than the speed
+    pub(crate) show_latency: bool,
+    // intervals between the keys of every test
+    pub(crate) latency: Histogram,
+    pub(crate) show_leaderboard: bool,
+    pub(crate) show_achievements: bool,
+    pub(crate) achievements: Unlocked,
+    pub(crate) show_history: bool,
+    // row selected in the history browser, the latest test first
+    pub(crate) history_row: usize,
+    // text of the test opened from the history with its mistakes marked, empty without a replay
+    pub(crate) history_detail: Option<Vec<Vec<diff::Cell>>>,
+    // unlocked by the test just finished, shown over its results
+    pub(crate) unlocked: Vec<Achievement>,
+    // selected in the menu of the results
+    pub(crate) action: Action,
+    // the text of the finished test is shown over its results
+    pub(crate) show_details: bool,
+    // and so is the card to share it
+    pub(crate) show_share: bool,
+    // whether Shift was held on the last key
+    pub(crate) shift: bool,
+    // last key pressed and not released yet, only known once the terminal reports releases
+    pub(crate) held: Option<KeyCode>,
+    pub(crate) reports_releases: bool,
+    // when the last few characters were typed, to catch pastes the terminal sends as keys
+    pub(crate) recent_keys: VecDeque<Instant>,
+    // set when watching a replay instead of typing
+    pub(crate) playback: Option<Playback>,
+    // where keys of the replay came after a long pause, marked in the text
+    pub(crate) pauses: Vec<(usize, usize)>,
+    // set when racing other players, the host picks the quotes
+    pub(crate) race: Option<Race>,
+    // opponents racing offline
+    pub(crate) bots: Vec<Bot>,
+    // result to beat, from a friend's challenge code
+    pub(crate) challenge: Option<Challenge>,
+    // speed to reach before quitting, for `wpm gate`
+    pub(crate) gate: Option<Gate>,
+    // cadence bar while typing and a rhythm score at the end, for `wpm rhythm`
+    pub(crate) rhythm: bool,
+    // mistakes allowed before the test fails, for `wpm accuracy`
+    pub(crate) error_budget: Option<u32>,
+    // minutes of an endurance run, for `wpm endurance`, and the runs before it
+    pub(crate) endurance: Option<u32>,
+    pub(crate) endurance_runs: Vec<endurance::Run>,
+    // assignment being typed, for `wpm assignment do`
+    pub(crate) homework: Option<Homework>,
+    // practice plan being worked through, for `wpm session`
+    pub(crate) session: Option<Session>,
+    // lesson of the course being typed, for `wpm course`
+    pub(crate) lesson: Option<usize>,
+    pub(crate) course: Progress,
+    // whether the last attempt passed the lesson
+    pub(crate) lesson_passed: bool,
+    // stream of the test to overlays, when turned on
+    pub(crate) telemetry: Option<Telemetry>,
+
+    // clicks and chimes, silent unless turned on
+    pub(crate) sounds: Sounds,
+    // numbers as of the end of the last line, kept still with reduced motion
+    pub(crate) line_stats: Option<LiveStats>,
+    // drawn without colors, checked once at start
+    pub(crate) monochrome: bool,
+    // texts of the interface, in the language of the config
+    pub(crate) strings: &'static Strings,
+    // how numbers and dates are written
+    pub(crate) locale: Locale,
+    // something on screen changed since the last frame
+    pub(crate) dirty: bool,
+    pub(crate) exit: bool,
+}
+
+impl App {
+    pub fn new(config: Config) -> Self {
+        App {
+            test: TypingTest::new(Vec::new(), config.rules(), Metric::Wpm),
+
+            library: Library::embedded(),
+            selected_group: 0,
+
+            content: Registry::new(Library::embedded()),
+            sentence_source: "loading quote...".to_string(),
+            sentence_id: 0,
+            sentence_length: 0,
+            difficulty: 0.,
+
+            history: history::load(),
+            previous_best: None,
+            ratings: Ratings::load(),
+            copied: false,
+            to_copy: None,
+            leaderboard: Cache::load().standings,
+            sinks: config.sinks(),
+            storage: Some(Writer::spawn()),
+
+            animations: Animations::new(config.animations && !config.reduced_motion),
+            area: Rect::default(),
+            show_info: config.info_panel,
+            show_latency: false,
+            latency: Histogram::load(),
+            show_leaderboard: false,
+            show_achievements: false,
+            show_history: false,
+            history_row: 0,
+            history_detail: None,
+            achievements: Unlocked::load(),
+            unlocked: Vec::new(),
+            action: Action::NewQuote,
+            show_details: false,
+            show_share: false,
+            sounds: if config.sound {
+                Sounds::new(
+                    &config.sound_pack,
+                    config.sound_volume,
+                    config.metronome_beat(),
+                )
+            } else {
+                Sounds::default()
+            },
+            line_stats: None,
+            monochrome: config.monochrome(),
+            strings: config.ui_language.strings(),
+            locale: Locale::new(&config.locale),
+            config,
+            shift: false,
+            held: None,
+            reports_releases: false,
+            recent_keys: VecDeque::with_capacity(BURST_KEYS),
+            playback: None,
+            pauses: Vec::new(),
+            race: None,
+            bots: Vec::new(),
+            challenge: None,
+            gate: None,
+            rhythm: false,
+            error_budget: None,
+            endurance: None,
+            endurance_runs: Vec::new(),
+            homework: None,
+            session: None,
+            lesson: None,
+            course: Progress::default(),
+            lesson_passed: false,
+            telemetry: None,
+
+            dirty: true,
+            exit: false,
+        }
+    }
+
+    /// Draws to any ratatui backend and takes input from any source, until told to quit.
+    pub fn run<B: Backend + Clipboard>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        input: &mut impl InputSource,
+    ) -> io::Result<()> {
+        let mut last_frame: Option<Instant> = None;
+
+        while !self.exit {
+            let next_frame = last_frame.map_or(Duration::ZERO, |last| {
+                FRAME_INTERVAL.saturating_sub(last.elapsed())
+            });
+            if self.dirty && next_frame.is_zero() {
+                terminal.draw(|frame| {
+                    self.area = frame.area();
+                    ui::view(self, frame);
+                })?;
+                self.dirty = false;
+                last_frame = Some(Instant::now());
+            }
+
+            // Wake up at least every tick so the timer and animations keep moving
+            let timeout = if self.dirty {
+                next_frame
+            } else if self.is_moving() || self.race.is_some() {
+                // Other players move on their own during a race
+                TICK_RATE
+            } else {
+                IDLE_TICK
+            };
+            if let Some(msg) = input.next_msg(self, timeout)? {
+                self.update(msg);
+            }
+            self.update(Msg::Tick);
+            if let Some(text) = self.to_copy.take() {
+                self.copied = terminal.backend_mut().copy(&text).is_ok();
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies a message to the state. Anything but a tick changes what is on screen.
+    pub fn update(&mut self, msg: Msg) {
+        if msg != Msg::Tick {
+            self.dirty = true;
+        }
+
+        match msg {
+            Msg::Quit => self.exit = true,
+            Msg::ToggleInfo => self.show_info = !self.show_info,
+            Msg::ToggleLeaderboard => self.show_leaderboard = !self.show_leaderboard,
+            Msg::ToggleAchievements => self.show_achievements = !self.show_achievements,
+            Msg::ToggleHistory => {
+                self.show_history = !self.show_history;
+                self.history_row = 0;
+                self.history_detail = None;
+            }
+            Msg::PreviousRow => self.history_row = self.history_row.saturating_sub(1),
+            Msg::NextRow => {
+                let last = self.history.len().saturating_sub(1);
+                self.history_row = (self.history_row + 1).min(last);
+            }
+            Msg::OpenRow => self.open_history_row(),
+            Msg::SelectRow(row) => {
+                self.history_row = row.min(self.history.len().saturating_sub(1));
+                self.history_detail = None;
+                self.open_history_row();
+            }
+            Msg::ToggleLatency => self.show_latency = !self.show_latency,
+            Msg::CopyResult => self.copy_result(),
+            Msg::PreviousGroup => self.previous_group(),
+            Msg::NextGroup => self.next_group(),
+            Msg::SelectGroup(gid) => self.select_group(gid),
+            // Nothing to type on before the race starts, or behind another screen
+            Msg::Type(_) | Msg::Paste(_) | Msg::Backspace | Msg::DeleteWord
+                if self.waiting_for_race()
+                    || self.show_leaderboard
+                    || self.show_achievements
+                    || self.show_history => {}
+            Msg::Type(char) => {
+                if self.test.is_done() {
+                    return;
+                }
+
+                self.check_burst();
+                let mistakes = self.mistakes();
+                let was_running = self.test.is_running();
+                self.test.hold_shift(self.shift);
+                let milestone = self.test.type_key(char);
+                if !was_running && self.test.is_running() {
+                    self.sounds.start_metronome();
+                }
+                self.sounds.play(match milestone {
+                    Some(Milestone::Finished) => Cue::Done,
+                    _ if self.mistakes() > mistakes => Cue::Error,
+                    _ if char == ' ' => Cue::Space,
+                    _ => Cue::Key,
+                });
+                self.on_milestone(milestone);
+                if self.over_budget() && self.test.is_running() {
+                    self.test.invalidate("over the error budget");
+                    let milestone = self.test.stop();
+                    self.on_milestone(milestone);
+                }
+            }
+            Msg::Paste(text) => self.paste(&text),
+            Msg::Backspace => self.test.backspace(),
+            Msg::DeleteWord => self.test.delete_word(),
+            Msg::Restart => self.restart(),
+            Msg::NewQuote => self.new_quote(),
+            Msg::ChangeLength => self.change_length(),
+            Msg::ToggleDetails => self.show_details = !self.show_details,
+            Msg::ToggleShare => self.show_share = !self.show_share,
+            Msg::Rate(tag) => self.rate(tag),
+            Msg::PreviousAction | Msg::NextAction => {
+                let count = Action::ALL.len();
+                let step = if msg == Msg::NextAction { 1 } else { count - 1 };
+                self.action = Action::ALL[(self.action as usize + step) % count];
+            }
+            Msg::Act => self.update(self.action.msg()),
+            Msg::Resize => {}
+            Msg::Tick => self.tick(),
+            Msg::TogglePause | Msg::Faster | Msg::Slower => {
+                let Some(playback) = &mut self.playback else {
+                    return;
+                };
+                match msg {
+                    Msg::TogglePause => playback.toggle_pause(),
+                    Msg::Faster => playback.faster(),
+                    _ => playback.slower(),
+                }
+            }
+        }
+    }
+
+    fn tick(&mut self) {
+        if let Some(playback) = &mut self.playback {
+            playback.advance();
+        }
+        while let Some(key) = self.playback.as_mut().and_then(Playback::next_key) {
+            let milestone = self.test.play_key(key);
+            self.on_milestone(milestone);
+        }
+        if let Some(limit) = self.time_limit()
+            && self.test.is_running()
+            && self.test.elapsed() >= limit
+        {
+            let milestone = self.test.stop();
+            self.on_milestone(milestone);
+        }
+
+        if let Some(race) = &mut self.race
+            && self.test.is_running()
+        {
+            race.report(
+                self.test.progress(),
+                self.test.speed(self.test.elapsed()),
+                false,
+            );
+        }
+        if let Some(start) = self.race.as_ref().and_then(Race::take_start) {
+            self.start_race(start);
+        }
+        if self.race.as_ref().is_some_and(Race::changed) {
+            self.dirty = true;
+        }
+        if let Some(telemetry) = &mut self.telemetry {
+            telemetry.update(Frame::of(&self.test, self.sentence_id));
+        }
+
+        if self.is_moving() {
+            self.dirty = true;
+        }
+        // Also redraws once after the last animation ends
+        if self.animations.tick() {
+            self.dirty = true;
+        }
+    }
+
+    /// When the test stops if the text isn't over, for gates, endurance runs and timed tests.
+    fn time_limit(&self) -> Option<Duration> {
+        let timed = match self.session.as_ref().and_then(Session::current) {
+            Some(session::Test::Timed(time)) => Some(time),
+            _ => None,
+        };
+        self.gate
+            .map(|gate| gate.time)
+            .or(self
+                .endurance
+                .map(|minutes| Duration::from_secs(minutes as u64 * 60)))
+            .or(timed)
+    }
+
+    /// Whether the screen changes on its own, from the timer or an animation. With reduced
+    /// motion the timer only shows at the end of lines, which keys already redraw.
+    fn is_moving(&self) -> bool {
+        (self.test.is_running() && !self.config.reduced_motion)
+            || self.animations.is_running()
+            || self.playback.as_ref().is_some_and(|p| !p.is_paused())
+    }
+
+    /// Types IME commits when composed input is on. Any other paste is ignored, and flags
+    /// the test if it is running.
+    fn paste(&mut self, text: &str) {
+        if self.test.is_done() {
+            return;
+        }
+
+        if !self.config.composed_input {
+            if !self.test.is_untouched() {
+                self.test.invalidate("text was pasted");
+            }
+            return;
+        }
+
+        if text.graphemes(true).count() > MAX_COMMIT_LENGTH {
+            self.test.invalidate("text was pasted");
+        }
+        let milestone = self.test.type_text(text);
+        self.on_milestone(milestone);
+    }
+
+    /// Mistakes made so far, fixed or not.
+    pub(crate) fn mistakes(&self) -> u32 {
+        self.test.live_counts().1 + self.test.corrected()
+    }
+
+    /// Whether the test made more mistakes than the error budget allows.
+    pub(crate) fn over_budget(&self) -> bool {
+        self.error_budget
+            .is_some_and(|budget| self.mistakes() > budget)
+    }
+
+    /// How evenly the keys of the test came so far, against the metronome if it ticks.
+    pub(crate) fn cadence(&self) -> Option<Cadence> {
+        Cadence::of(self.test.keys(), self.config.metronome_beat())
+    }
+
+    /// Flags the test when keys come in faster than anyone can type.
+    fn check_burst(&mut self) {
+        if self.recent_keys.len() == BURST_KEYS {
+            self.recent_keys.pop_front();
+        }
+        self.recent_keys.push_back(Instant::now());
+
+        if self.recent_keys.len() == BURST_KEYS
+            && self.recent_keys[BURST_KEYS - 1] - self.recent_keys[0] < BURST_WINDOW
+        {
+            self.test.invalidate("input was impossibly fast");
+        }
+    }
+
+    /// Plays the animations of a completed line, and saves the result once the test is over.
+    fn on_milestone(&mut self, milestone: Option<Milestone>) {
+        match milestone {
+            Some(Milestone::LineCompleted) => {
+                self.animations.line_flash();
+                if self.line_stats.is_some() {
+                    self.line_stats = Some(self.current_stats());
+                }
+            }
+            Some(Milestone::Finished) => self.finish(),
+            None => {}
+        }
+    }
+
+    fn finish(&mut self) {
+        self.sounds.stop_metronome();
+        self.unlocked.clear();
+        self.action = Action::NewQuote;
+        self.show_details = false;
+        self.show_share = false;
+        let record = history::Record {
+            mistakes: mistakes::classify(&self.test, self.config.typing_layout()),
+            category: self.content.active().category(),
+            difficulty: self.difficulty,
+            ..self.test.record(self.sentence_id, self.selected_group)
+        };
+        if let Some(race) = &mut self.race {
+            race.report(1., record.wpm, true);
+        }
+
+        self.previous_best = history::personal_best_in(&self.history, self.test.metric());
+        if let Some(gate) = &mut self.gate {
+            gate.attempt(record.wpm, self.test.invalid().is_none());
+        }
+        if let Some(homework) = &mut self.homework {
+            let replay = replay::Replay {
+                player: self.config.player_name(),
+                ..self.test.replay(self.sentence_id)
+            };
+            homework.attempt(replay, record.accuracy, self.test.invalid());
+        }
+        if let Some(session) = &mut self.session {
+            session.finish(session::Outcome {
+                wpm: record.wpm,
+                accuracy: record.accuracy,
+                seconds: record.seconds,
+                counted: self.test.invalid().is_none(),
+            });
+        }
+        self.lesson_passed = false;
+        if self.test.invalid().is_some() || self.playback.is_some() {
+            return;
+        }
+        if let Some(lesson) = self.lesson {
+            self.lesson_passed = self.course.attempt(lesson, record.wpm, record.accuracy);
+            self.store(Job::Course(self.course.clone()));
+        }
+        if self.previous_best.is_some_and(|best| record.wpm > best) {
+            self.animations.confetti();
+        }
+
+        let replay = self.config.record_replays.then(|| replay::Replay {
+            player: self.config.player_name(),
+            ..self.test.replay(self.sentence_id)
+        });
+        // Drills make words up, they are no words to review
+        let session = match self.content.active().name() {
+            "drill" => review::Session::default(),
+            _ => review::Session::of(&self.test),
+        };
+        let latency = Histogram::of(self.test.keys());
+        self.latency.merge(&latency);
+        // Runs are kept apart, their speed is no sprint's
+        if let Some(minutes) = self.endurance {
+            let run = endurance::Run::of(&self.test, minutes);
+            self.store(Job::Endurance(run.clone()));
+            self.store(Job::Letters(self.test.letters().clone()));
+            self.store(Job::Latency(latency));
+            self.endurance_runs.push(run);
+            return;
+        }
+        for sink in &mut self.sinks {
+            sink.record(&record, replay.as_ref());
+        }
+        self.store(Job::Letters(self.test.letters().clone()));
+        self.store(Job::Latency(latency));
+        self.store(Job::Review(session.clone()));
+        self.content.active_mut().learn(self.test.letters());
+        self.content.active_mut().adapt(record.accuracy);
+        self.content.active_mut().words(&session);
+        let timestamp = record.timestamp;
+        self.history.push(record);
+
+        self.unlocked = self.achievements.update(&self.history, timestamp);
+        if !self.unlocked.is_empty() {
+            self.store(Job::Achievements(self.achievements.clone()));
+        }
+    }
+
+    /// Keeps app state like letter stats or ratings for the next runs, whatever the sinks.
+    fn store(&self, job: Job) {
+        if let Some(storage) = &self.storage {
+            storage.write(job);
+        }
+    }
+
+    /// Starts a fresh attempt on the given lines.
+    fn start_test(&mut self, lines: Vec<String>) {
+        self.test = TypingTest::new(lines, self.config.rules(), self.content.active().metric());
+        self.previous_best = None;
+        self.copied = false;
+        self.recent_keys.clear();
+        self.line_stats = self.config.reduced_motion.then(LiveStats::default);
+        self.sounds.stop_metronome();
+        self.start_bots();
+    }
+
+    /// The numbers to show while typing, which only move at the end of each line with reduced
+    /// motion.
+    pub(crate) fn live_stats(&self) -> LiveStats {
+        match self.line_stats {
+            Some(stats) if !self.test.is_done() => stats,
+            _ => self.current_stats(),
+        }
+    }
+
+    fn current_stats(&self) -> LiveStats {
+        let elapsed = self.test.elapsed();
+        let (correct, incorrect) = self.test.live_counts();
+        LiveStats {
+            elapsed,
+            speed: self.test.speed(elapsed),
+            accuracy: self.test.accuracy(),
+            correct,
+            incorrect,
+            fixed: self.test.corrected(),
+        }
+    }
+
+    /// Lines the bots up for the text on screen.
+    fn start_bots(&mut self) {
+        let chars = self.test.lines().iter().map(Vec::len).sum();
+        for bot in &mut self.bots {
+            bot.start(chars);
+        }
+    }
+
+    /// Everyone in the race, best placed first. `None` outside of races.
+    pub(crate) fn standings(&self) -> Option<Vec<Player>> {
+        if let Some(race) = &self.race {
+            return Some(race.standings());
+        }
+        if self.bots.is_empty() {
+            return None;
+        }
+
+        let elapsed = self.test.elapsed();
+        let mut players = vec![Player {
+            name: self.config.player_name(),
+            progress: self.test.progress(),
+            wpm: self.test.speed(elapsed),
+            place: None,
+            wins: 0,
+        }];
+        let mut finish_times = vec![self.test.is_done().then_some(elapsed)];
+        for bot in &self.bots {
+            players.push(bot.player(elapsed));
+            finish_times.push(Some(bot.finish_time()).filter(|time| *time <= elapsed));
+        }
+
+        let mut finished: Vec<(Duration, usize)> = finish_times
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, time)| Some((time?, i)))
+            .collect();
+        finished.sort();
+        for (place, (_, i)) in finished.into_iter().enumerate() {
+            players[i].place = Some(place + 1);
+        }
+
+        players.sort_by(|a, b| {
+            let place = |p: &Player| p.place.unwrap_or(usize::MAX);
+            place(a)
+                .cmp(&place(b))
+                .then(b.progress.total_cmp(&a.progress))
+        });
+        Some(players)
+    }
+
+    /// Sets up the screen to play back a replay, with the typing rules it was recorded with.
+    pub fn load_replay(&mut self, replay: replay::Replay) {
+        if let Some(quote) = self.library.get(replay.quote_id) {
+            self.sentence_source = quote.source.clone();
+            self.sentence_length = quote.length;
+            self.difficulty = stats::difficulty(&quote.text);
+        }
+
+        self.sentence_id = replay.quote_id;
+        self.set_rules(replay.rules);
+        // Played at the pace it was recorded, whatever the speed it is watched at
+        self.pauses = Simulation::from_replay(&replay).test().pauses().to_vec();
+        self.start_test(replay.lines);
+        let playback = Playback::new(replay.keys);
+        self.test.set_clock(playback.clock());
+        self.playback = Some(playback);
+    }
+
+    /// Races the ghost of a replay, someone else's or one of yours, on the text of the replay.
+    pub fn race_ghost(&mut self, ghost: Bot, replay: replay::Replay) {
+        self.bots.push(ghost);
+
+        let text = replay.lines.concat();
+        self.sentence_source = self
+            .library
+            .get(replay.quote_id)
+            .map(|quote| quote.source.clone())
+            .unwrap_or_default();
+        self.sentence_id = replay.quote_id;
+        self.sentence_length = text.chars().count() as u32;
+        self.difficulty = stats::difficulty(&text);
+        self.set_rules(replay.rules);
+        self.start_test(replay.lines);
+        self.animations.intro();
+    }
+
+    /// Works through the texts of an assignment, with the rules it asks for.
+    pub fn start_homework(&mut self, homework: Homework) {
+        self.set_rules(homework.assignment.rules);
+        self.homework = Some(homework);
+        self.next_homework_text();
+    }
+
+    /// Starts the text of the assignment left to type, the next one once the current one is
+    /// over. Quits when there are none left.
+    fn next_homework_text(&mut self) {
+        let Some(homework) = &mut self.homework else {
+            return;
+        };
+        homework.advance();
+        let Some(text) = homework.current() else {
+            self.exit = true;
+            return;
+        };
+
+        let quote = Quote {
+            text: text.to_string(),
+            source: homework.assignment.title.clone(),
+            length: text.chars().count() as u32,
+            id: homework.text as u32,
+        };
+        self.start_quote(&quote);
+    }
+
+    /// Works through the tests of a practice plan, one after the other.
+    pub fn start_session(&mut self, session: Session) {
+        self.session = Some(session);
+        self.next_session_test();
+    }
+
+    /// Starts the test of the plan left to type, the next one once the current one is
+    /// finished. Quits when there are none left.
+    fn next_session_test(&mut self) {
+        let Some(session) = &mut self.session else {
+            return;
+        };
+        session.advance();
+        let (test, group) = (session.current(), session.group);
+        self.error_budget = None;
+        match test {
+            None => {
+                self.exit = true;
+                return;
+            }
+            Some(session::Test::Accuracy(mistakes)) => {
+                self.error_budget = Some(mistakes);
+                self.selected_group = group;
+            }
+            // Long quotes, so the time runs out before the text
+            Some(session::Test::Timed(_)) => {
+                self.selected_group = self.groups().len().saturating_sub(1)
+            }
+            Some(session::Test::Quote) => self.selected_group = group,
+        }
+        if self.selected_group >= self.groups().len() {
+            self.selected_group = 0;
+        }
+        if let Some(quote) = self.next_text() {
+            self.start_quote(&quote);
+        }
+    }
+
+    /// Reviews the words of `queue` that are due, in place of the quotes.
+    pub fn start_review(&mut self, queue: review::Queue) {
+        self.content.register(content::Review::new(queue));
+        self.content.select("review");
+        self.new_quote();
+    }
+
+    /// Types a version of a drill built from the user's weaknesses, in place of the quotes.
+    pub fn start_custom(&mut self, name: &str, version: custom::Version) {
+        self.content
+            .register(content::Custom::new(name.to_string(), version));
+        self.content.select("custom");
+        self.new_quote();
+    }
+
+    /// Types texts end to end for `minutes`, with the runs before to compare to.
+    pub fn start_endurance(&mut self, minutes: u32, runs: Vec<endurance::Run>) {
+        self.endurance = Some(minutes);
+        self.endurance_runs = runs;
+        let texts = self.library.quotes.iter().map(|quote| quote.text.as_str());
+        self.content
+            .register(content::Endurance::new(texts, minutes));
+        self.content.select("endurance");
+        self.new_quote();
+    }
+
+    /// Takes the lesson of the course at `index`, with the progress so far.
+    pub fn start_lesson(&mut self, course: Progress, index: usize) {
+        self.course = course;
+        self.lesson = Some(index);
+        self.lesson_passed = false;
+        // Capitals are a lesson of their own
+        self.config.casing = Casing::Exact;
+        let texts = self.library.quotes.iter().map(|quote| quote.text.as_str());
+        self.content
+            .register(course::drill(index, self.config.typing_layout(), texts));
+        self.content.select("drill");
+        self.new_quote();
+    }
+
+    /// Moves on to the lesson after a passed one, quits once the course is over.
+    fn next_lesson(&mut self) {
+        match self.lesson {
+            Some(lesson) if lesson + 1 < LESSONS.len() => {
+                self.start_lesson(self.course.clone(), lesson + 1)
+            }
+            _ => self.exit = true,
+        }
+    }
+
+    /// Types with the rules of someone else's test, like a replay or a race.
+    fn set_rules(&mut self, rules: Rules) {
+        self.config.strict_space = rules.strict_space;
+        self.config.stop_on_error = rules.stop_on_error;
+        self.config.overtype = rules.overtype;
+        self.config.punctuation = rules.punctuation;
+        self.config.dead_keys = rules.dead_keys;
+        // Their text is typed as they had it
+        self.config.casing = Casing::Exact;
+    }
+
+    /// Starts typing the text of a race the host started.
+    fn start_race(&mut self, start: race::Start) {
+        let text = start.lines.concat();
+        self.sentence_source = start.source;
+        self.sentence_id = start.quote_id;
+        self.sentence_length = text.chars().count() as u32;
+        self.difficulty = stats::difficulty(&text);
+        self.set_rules(start.rules);
+        self.start_test(start.lines);
+
+        self.animations.intro();
+    }
+
+    /// Sends the text on screen to every player, when hosting a race.
+    fn host_race(&mut self) {
+        let Some(race) = self.race.as_mut().filter(|race| race.is_host()) else {
+            return;
+        };
+
+        race.start(race::Start {
+            quote_id: self.sentence_id,
+            source: self.sentence_source.clone(),
+            lines: self.test.lines().iter().map(|line| line.concat()).collect(),
+            rules: self.config.rules(),
+        });
+    }
+
+    /// Types the quote of a friend's challenge, exactly like they did.
+    pub fn accept_challenge(&mut self, challenge: Challenge) {
+        self.set_rules(challenge.rules);
+        self.config.trailing_space = challenge.trailing_space;
+        if let Some(quote) = self.library.get(challenge.quote_id) {
+            self.start_quote(quote);
+        }
+        self.challenge = Some(challenge);
+    }
+
+    /// A code for a friend to type the finished test and try to beat it.
+    pub(crate) fn challenge_code(&self) -> Option<String> {
+        if !self.test.is_done() || self.test.invalid().is_some() || self.playback.is_some() {
+            return None;
+        }
+        // Friends only have the built-in quotes for sure, and ids are only unique among them
+        if self.content.active().name() != "quotes" {
+            return None;
+        }
+        self.library.get(self.sentence_id)?;
+
+        let duration = self.test.elapsed();
+        let challenge = Challenge {
+            quote_id: self.sentence_id,
+            rules: self.config.rules(),
+            trailing_space: self.config.trailing_space,
+            wpm: self.test.speed(duration),
+            accuracy: self.test.accuracy(),
+            seconds: duration.as_secs_f32(),
+        };
+        Some(challenge.encode())
+    }
+
+    /// A few lines about the finished test, to paste in a chat.
+    pub(crate) fn result_card(&self) -> String {
+        let record = self.test.record(self.sentence_id, self.selected_group);
+        format!(
+            "wpm · {}\n{:.0} {} · {} accuracy · {}\n{} · {}",
+            self.locale.date(record.timestamp),
+            record.wpm,
+            self.test.unit(),
+            self.locale.percent(record.accuracy, 1),
+            self.locale.seconds(record.seconds, 1),
+            self.mode_label(),
+            self.sentence_source,
+        )
+    }
+
+    /// The result in a box with a QR code of its challenge, for a friend to scan.
+    pub(crate) fn share_card(&self) -> String {
+        let record = self.test.record(self.sentence_id, self.selected_group);
+        card::text(
+            &record,
+            &self.sentence_source,
+            self.challenge_code().as_deref(),
+        )
+    }
+
+    /// Opens the text of the test selected in the history, played back from its replay, or
+    /// goes back to the list.
+    fn open_history_row(&mut self) {
+        if self.history_detail.take().is_some() {
+            return;
+        }
+        let Some(record) = self.history.iter().rev().nth(self.history_row) else {
+            return;
+        };
+        let replay = replay::find(record);
+        self.history_detail = Some(replay.map(|replay| diff::of(&replay)).unwrap_or_default());
+    }
+
+    /// Copies the result card, or the card to share when it is shown, once back in `run`.
+    fn copy_result(&mut self) {
+        if !self.test.is_done() {
+            return;
+        }
+        let card = if self.show_share {
+            self.share_card()
+        } else {
+            self.result_card()
+        };
+        self.to_copy = Some(card);
+    }
+
+    /// What is typed, like `quote 50-100`, and how far into the session.
+    pub(crate) fn mode_label(&self) -> String {
+        let label = self.test_label();
+        match &self.session {
+            Some(session) => format!(
+                "session {}/{} {label}",
+                session.step + 1,
+                session.tests.len()
+            ),
+            None => label,
+        }
+    }
+
+    fn test_label(&self) -> String {
+        if let Some(session::Test::Timed(time)) = self.session.as_ref().and_then(Session::current) {
+            return format!("timed {} s", time.as_secs());
+        }
+        if let Some(lesson) = self.lesson {
+            return format!("lesson {}", lesson + 1);
+        }
+        if let Some(minutes) = self.endurance {
+            return format!("endurance {minutes} min");
+        }
+        if let Some(budget) = self.error_budget {
+            return format!("mistakes {}/{budget}", self.mistakes());
+        }
+        if self.rhythm {
+            return "rhythm".to_string();
+        }
+        match self.groups().get(self.selected_group) {
+            Some(group) => format!("quote {}-{}", group[0], group[1]),
+            None => "quote".to_string(),
+        }
+    }
+
+    /// Whether the quotes are picked by someone else, the host of a race.
+    fn is_guest(&self) -> bool {
+        self.race.as_ref().is_some_and(|race| !race.is_host())
+    }
+
+    /// Whether a race is about to start, or hasn't been sent yet.
+    pub(crate) fn waiting_for_race(&self) -> bool {
+        self.test.lines().is_empty()
+            || self
+                .race
+                .as_ref()
+                .is_some_and(|race| race.countdown().is_some())
+    }
+
+    /// Starts the current quote over, keeping the same text.
+    fn restart(&mut self) {
+        if self.is_guest() {
+            return;
+        }
+        if self.gate.is_some_and(|gate| gate.is_over()) {
+            self.exit = true;
+            return;
+        }
+        // Every finished attempt counts, the text only changes once it is over
+        if self.homework.is_some() {
+            return self.next_homework_text();
+        }
+        if self.session.is_some() && self.test.is_done() {
+            return self.next_session_test();
+        }
+        if self.lesson_passed && self.test.is_done() {
+            return self.next_lesson();
+        }
+
+        self.test.restart();
+        self.previous_best = None;
+        self.copied = false;
+        self.recent_keys.clear();
+        self.line_stats = self.config.reduced_motion.then(LiveStats::default);
+        self.sounds.stop_metronome();
+        self.start_bots();
+        self.animations.intro();
+        self.host_race();
+    }
+
+    fn select_group(&mut self, gid: usize) {
+        if !self.test.is_untouched() || self.test.is_done() || self.groups().is_empty() {
+            return;
+        }
+
+        self.selected_group = gid;
+        self.new_quote();
+    }
+
+    fn previous_group(&mut self) {
+        if self.selected_group == 0 {
+            self.select_group(self.groups().len().saturating_sub(1));
+        } else {
+            self.select_group(self.selected_group - 1);
+        }
+    }
+
+    fn next_group(&mut self) {
+        if self.selected_group + 1 >= self.groups().len() {
+            self.select_group(0);
+        } else {
+            self.select_group(self.selected_group + 1);
+        }
+    }
+
+    /// A new text from the group after the current one, back to the first after the last.
+    fn change_length(&mut self) {
+        if !self.groups().is_empty() {
+            self.selected_group = (self.selected_group + 1) % self.groups().len();
+        }
+        self.new_quote();
+    }
+
+    /// Whether the text typed is a quote that can be rated, drills and books are not.
+    pub(crate) fn rateable(&self) -> bool {
+        !self.groups().is_empty() && self.homework.is_none()
+    }
+
+    /// Tags the quote just typed, in place of its opposite, or takes the tag away.
+    fn rate(&mut self, tag: Tag) {
+        if !self.rateable() {
+            return;
+        }
+        let source = self.content.active().name();
+        self.ratings.toggle(source, self.sentence_id, tag);
+        self.store(Job::Ratings(self.ratings.clone()));
+    }
+
+    /// Tags of the quote typed, rated in this run or an earlier one.
+    pub(crate) fn tags(&self) -> Vec<Tag> {
+        let source = self.content.active().name();
+        self.ratings.tags(source, self.sentence_id)
+    }
+
+    /// Length groups of the content typed, empty when it has none.
+    pub(crate) fn groups(&self) -> &[[u32; 2]] {
+        self.content.active().groups()
+    }
+
+    /// A text file cut in pages that fit on screen, the first one read already.
+    pub fn book(name: String, file: File) -> io::Result<Book<BufReader<File>>> {
+        Book::open(
+            name,
+            BufReader::new(file),
+            MAX_LENGTH_PER_LINE,
+            BOOK_PAGE_LINES,
+        )
+    }
+
+    /// Types the book from the start, one page per test, in place of the quotes.
+    pub fn open_book(&mut self, book: Book<BufReader<File>>) {
+        self.content.register(book);
+        self.content.select("book");
+        self.new_quote();
+    }
+
+    /// Drills on the keys the filter allows on the keyboard of the config, in place of the
+    /// quotes.
+    pub fn start_drill(&mut self, filter: &KeyFilter) {
+        let letters = filter.letters(self.config.typing_layout(), &self.config.fingering);
+        let texts = self.library.quotes.iter().map(|quote| quote.text.as_str());
+        let drill = Drill::new(filter.to_string(), letters, texts);
+        self.content.register(match self.flow("drill") {
+            Some(flow) => drill.adaptive(flow),
+            None => drill,
+        });
+        self.content.select("drill");
+        self.new_quote();
+    }
+
+    /// Practices the letters and pairs of `letters` slower than `target_wpm` or often missed,
+    /// in place of the quotes. Each result changes what comes next.
+    pub fn start_practice(&mut self, letters: Letters, target_wpm: f32) {
+        let texts = self.library.quotes.iter().map(|quote| quote.text.as_str());
+        let practice = Practice::new(letters, target_wpm, texts);
+        self.content.register(match self.flow("practice") {
+            Some(flow) => practice.adaptive(flow),
+            None => practice,
+        });
+        self.content.select("practice");
+        self.new_quote();
+    }
+
+    /// Difficulty of the texts of a generator, from the accuracy of the results on it so far.
+    /// None when it is turned off.
+    fn flow(&self, category: &str) -> Option<Flow> {
+        self.config.adaptive_difficulty.then(|| {
+            Flow::replay(
+                self.history
+                    .iter()
+                    .filter(|record| record.category == category)
+                    .map(|record| record.accuracy),
+            )
+        })
+    }
+
+    /// Takes the next text from the content typed and starts over on it. Once a source like a
+    /// book runs out, its last text stays up.
+    pub fn new_quote(&mut self) {
+        // Only a race server takes requests, a host picks the quotes itself
+        if let Some(race) = self.race.as_mut().filter(|race| !race.is_host()) {
+            return race.request_start();
+        }
+        self.challenge = None;
+        // A ghost only knows the text of its replay
+        self.bots.retain(|bot| !bot.is_ghost());
+        // Nothing left to try once the gate is over
+        if self.gate.is_some_and(|gate| gate.is_over()) {
+            self.exit = true;
+            return;
+        }
+        if self.homework.is_some() {
+            return self.next_homework_text();
+        }
+        if self.session.is_some() && self.test.is_done() {
+            return self.next_session_test();
+        }
+        if self.selected_group >= self.groups().len() {
+            self.selected_group = 0;
+        }
+
+        if let Some(quote) = self.next_text() {
+            self.start_quote(&quote);
+            self.host_race();
+        }
+    }
+
+    /// The next text of the selected group, among the quotes rated as the config asks when
+    /// there are any.
+    fn next_text(&mut self) -> Option<Quote> {
+        let (group, ratings) = (self.selected_group, &self.ratings);
+        let provider = self.content.active_mut();
+        let source = provider.name().to_string();
+        match self.config.quote_filter {
+            Some(tag) => provider
+                .next_text_where(group, &|quote| ratings.has(&source, quote.id, tag))
+                .or_else(|| provider.next_text(group)),
+            None => provider.next_text(group),
+        }
+    }
+
+    pub(crate) fn start_quote(&mut self, quote: &Quote) {
+        self.sentence_source = quote.source.clone();
+        self.sentence_id = quote.id;
+        self.sentence_length = quote.length;
+        self.difficulty = stats::difficulty(&quote.text);
+        let text = content::prepare(&quote.text, self.config.punctuation);
+        let text = content::recase(&text, self.config.casing);
+        // Spaced out letters take more room, so lines hold fewer of them
+        let width = MAX_LENGTH_PER_LINE / (1 + self.config.letter_spacing as usize);
+        self.start_test(content::wrap(&text, width, self.config.trailing_space));
+
+        self.animations.intro();
+    }
+}
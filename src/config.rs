@@ -0,0 +1,432 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf, time::Duration};
+
+use wpm_core::{
+    announce::{Announcer, Room},
+    content::Casing,
+    engine::Rules,
+    history,
+    keyboard::{Fingering, Layout},
+    leaderboard,
+    punctuation::Punctuation,
+    rating::Tag,
+    sink::{JsonLog, StatsSink, Stdout, Webhook},
+    stats::CHARS_PER_WORD,
+    storage,
+};
+
+use crate::{
+    i18n::UiLanguage,
+    layout::Preset,
+    notification::Desktop,
+    session::{Step, StepKind},
+};
+
+// Longest nick most IRC networks take
+const IRC_NICK_LENGTH: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Segment {
+    Mode,
+    Language,
+    Wpm,
+    Accuracy,
+    Timer,
+    CapsLock,
+}
+
+/// How the character actually typed is shown next to a mistake.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ghost {
+    /// Only highlight the expected character
+    #[default]
+    Off,
+    Dim,
+    Strikethrough,
+    Superscript,
+}
+
+/// Colors of right and wrong, with sets that stay apart for the common kinds of color
+/// blindness.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Palette {
+    /// Green and red
+    #[default]
+    Default,
+    /// Blue and orange, for red-green color blindness
+    Deuteranopia,
+    /// Blue and yellow, red looking dark with protanopia
+    Protanopia,
+    /// Teal and pink, for blue-yellow color blindness
+    Tritanopia,
+}
+
+/// Whether the screen is drawn in color.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Colors {
+    /// Unless `NO_COLOR` is set or the terminal is dumb
+    #[default]
+    Auto,
+    Always,
+    /// Bold, underline, dim and reverse only
+    Never,
+}
+
+/// Pieces the text left to type is colored in, alternating to make it easier to follow.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Chunks {
+    #[default]
+    Off,
+    Words,
+    Syllables,
+}
+
+/// A mark on mistakes besides their color, for when the color alone doesn't show.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCue {
+    #[default]
+    Off,
+    Underline,
+    Strikethrough,
+    /// Swaps the text and background colors
+    Inverse,
+}
+
+/// Where the result of each finished test is sent.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sink {
+    /// The history file, and replays when they are recorded
+    History,
+    /// A line of JSON appended to the given file
+    Log(PathBuf),
+    /// A line of JSON printed after quitting
+    Stdout,
+    /// A desktop notification
+    Notification,
+    /// JSON POSTed to the given URL, with an optional `Authorization` header
+    Webhook {
+        url: String,
+        #[serde(default)]
+        authorization: Option<String>,
+    },
+    /// A message to a Matrix room on each new personal best
+    Matrix {
+        homeserver: String,
+        room: String,
+        token: String,
+    },
+    /// A message to an IRC channel on each new personal best, by a nick made from the player
+    /// name unless given
+    Irc {
+        server: String,
+        channel: String,
+        #[serde(default)]
+        nick: Option<String>,
+    },
+}
+
+/// Quotes downloaded from the web, in the JSON format of the built-in ones.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct QuoteSource {
+    pub name: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// Intro transition and personal best confetti
+    pub animations: bool,
+    /// No animations, and the numbers shown while typing only change at the end of each line
+    pub reduced_motion: bool,
+    /// Segments of the bottom status bar, in display order. Empty hides the bar
+    pub status_bar: Vec<Segment>,
+    /// Show WPM and accuracy while typing, they are always shown on the results screen
+    pub live_stats: bool,
+    /// Draw the line being typed with big block glyphs
+    pub large_text: bool,
+    /// For braille displays: the line being typed stays on the first row, left aligned and
+    /// without borders, with the cursor on the next character and the line mirrored plainly at
+    /// the bottom
+    pub linear: bool,
+    /// Blank cells between the characters of the text, when the screen is wide enough
+    pub letter_spacing: u8,
+    /// Blank lines between the lines of the text
+    pub line_gap: u8,
+    pub chunks: Chunks,
+    /// Show the quote info panel when starting, it can be toggled with F1
+    pub info_panel: bool,
+    /// Arrangement of the typing screen widgets
+    pub layout: Preset,
+    pub ghost_errors: Ghost,
+    /// Colors of right and wrong
+    pub palette: Palette,
+    /// Mark on mistakes besides their color
+    pub error_cue: ErrorCue,
+    pub colors: Colors,
+    /// Language of titles, instructions and labels
+    pub ui_language: UiLanguage,
+    /// Locale numbers and dates are written in, like `de_DE`, the one of the system (LC_ALL,
+    /// LC_NUMERIC or LANG) when empty
+    pub locale: String,
+    /// Physical layout of the keyboard, drills keep to its rows and fingers
+    pub keyboard_layout: Layout,
+    /// Layout the keys type in, whatever the one of the system, to learn a new one
+    pub emulate_layout: Option<Layout>,
+    /// Letters typed with another finger than the usual one for their column
+    pub fingering: Fingering,
+    /// Accept extra characters at the end of a word as errors instead of ignoring them
+    pub overtype: bool,
+    /// Only accept space where the quote has one, otherwise space jumps to the next word
+    /// and the letters skipped count as errors
+    pub strict_space: bool,
+    /// What to do with curly quotes, dashes and ellipses in quotes
+    pub punctuation: Punctuation,
+    /// Case of the letters of the texts, from any source
+    pub casing: Casing,
+    /// Drills and practice get longer words, rarer letters and punctuation while the accuracy
+    /// stays high, and simpler as soon as it drops
+    pub adaptive_difficulty: bool,
+    /// Combine accent keys with the next letter typed, for keyboards with dead keys
+    pub dead_keys: bool,
+    /// Accept whole composed strings (IME commits, pastes) as typed text, for CJK languages
+    pub composed_input: bool,
+    /// Refuse to move past a wrong character, it is only counted as an error
+    pub stop_on_error: bool,
+    /// Require the space at the end of each line, otherwise lines move on after their last word
+    pub trailing_space: bool,
+    /// Clicks on keys, a buzz on mistakes and a chime at the end, in builds with sound
+    pub sound: bool,
+    /// From 0 to 1
+    pub sound_volume: f32,
+    /// Folder of sounds in the config folder, or a pack built in with the `bundled-sounds`
+    /// feature: `mechanical` or `typewriter`. Built-in beeps when empty
+    pub sound_pack: String,
+    /// Keystrokes per minute the metronome ticks at while typing, off when 0
+    pub metronome: u32,
+    /// Speed to tick at instead, in words per minute of five keystrokes, when `metronome` is 0
+    pub metronome_wpm: u32,
+    /// Save every keystroke of each test to a replay file
+    pub record_replays: bool,
+    /// Tests of `wpm session`, in order
+    pub session: Vec<Step>,
+    /// Minutes to type each day, `wpm daemon` reminds of it until they are. No goal when 0
+    pub daily_goal: u32,
+    /// Times of the day `wpm daemon` reminds at, like `18:00`
+    pub reminders: Vec<String>,
+    /// Where results go, in order. Without `history` they are not kept between runs
+    pub sinks: Vec<Sink>,
+    /// Name shown to the other players of a race, the login name when empty
+    pub name: String,
+    /// Server `wpm sync` shares results with, off when empty
+    pub leaderboard_url: String,
+    /// Token the leaderboard server was set up with
+    pub leaderboard_token: String,
+    /// Quote packs to download, kept on disk to work offline
+    pub quote_sources: Vec<QuoteSource>,
+    /// Name of the quote pack to type, the built-in quotes when empty
+    pub quote_source: String,
+    /// Only pick quotes rated with this tag after typing them, like `fun`, any quote when not
+    /// set. Falls back to any quote when none of the length picked has it
+    pub quote_filter: Option<Tag>,
+    /// Local port streaming the test live over WebSocket, for stream overlays. Off when 0
+    pub telemetry_port: u16,
+    /// Local port serving Prometheus metrics of the history, with `wpm serve`, `wpm daemon` or
+    /// while typing. Off when 0
+    pub metrics_port: u16,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            animations: true,
+            reduced_motion: false,
+            status_bar: vec![
+                Segment::Mode,
+                Segment::Language,
+                Segment::Timer,
+                Segment::CapsLock,
+            ],
+            live_stats: true,
+            large_text: false,
+            linear: false,
+            letter_spacing: 0,
+            line_gap: 0,
+            chunks: Chunks::Off,
+            info_panel: true,
+            layout: Preset::Classic,
+            ghost_errors: Ghost::Off,
+            palette: Palette::Default,
+            error_cue: ErrorCue::Off,
+            colors: Colors::Auto,
+            ui_language: UiLanguage::Auto,
+            locale: String::new(),
+            keyboard_layout: Layout::Qwerty,
+            emulate_layout: None,
+            fingering: Fingering::new(),
+            overtype: true,
+            strict_space: true,
+            punctuation: Punctuation::Exact,
+            casing: Casing::Exact,
+            adaptive_difficulty: true,
+            dead_keys: true,
+            composed_input: false,
+            stop_on_error: false,
+            trailing_space: true,
+            sound: false,
+            sound_volume: 0.5,
+            sound_pack: String::new(),
+            metronome: 0,
+            metronome_wpm: 0,
+            record_replays: false,
+            session: vec![
+                Step::new(StepKind::Accuracy, 2),
+                Step::new(StepKind::Timed, 1),
+                Step::new(StepKind::Quote, 1),
+            ],
+            daily_goal: 10,
+            reminders: vec!["18:00".to_string()],
+            sinks: vec![Sink::History],
+            name: String::new(),
+            leaderboard_url: String::new(),
+            leaderboard_token: String::new(),
+            quote_sources: Vec::new(),
+            quote_source: String::new(),
+            quote_filter: None,
+            telemetry_port: 0,
+            metrics_port: 0,
+        }
+    }
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("wpm").join("config.toml"))
+    }
+
+    /// Reads the config file, falling back to the defaults if it is missing or invalid.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Name to race under.
+    pub fn player_name(&self) -> String {
+        if !self.name.is_empty() {
+            return self.name.clone();
+        }
+
+        ["USER", "USERNAME"]
+            .iter()
+            .find_map(|var| std::env::var(var).ok())
+            .unwrap_or_else(|| "player".to_string())
+    }
+
+    /// Client of the leaderboard server, when one is set.
+    pub fn leaderboard(&self) -> Option<leaderboard::Client> {
+        (!self.leaderboard_url.is_empty()).then(|| {
+            leaderboard::Client::new(&self.leaderboard_url, self.leaderboard_token.clone())
+        })
+    }
+
+    /// The result consumers set in the config, ready to take results.
+    pub fn sinks(&self) -> Vec<Box<dyn StatsSink>> {
+        self.sinks
+            .iter()
+            .map(|sink| -> Box<dyn StatsSink> {
+                match sink {
+                    Sink::History => Box::new(storage::Writer::spawn()),
+                    Sink::Log(path) => Box::new(JsonLog::new(path.clone())),
+                    Sink::Stdout => Box::new(Stdout::default()),
+                    Sink::Notification => Box::new(Desktop::default()),
+                    Sink::Webhook { url, authorization } => {
+                        Box::new(Webhook::spawn(url.clone(), authorization.clone()))
+                    }
+                    Sink::Matrix {
+                        homeserver,
+                        room,
+                        token,
+                    } => self.announcer(Room::Matrix {
+                        homeserver: homeserver.clone(),
+                        room: room.clone(),
+                        token: token.clone(),
+                    }),
+                    Sink::Irc {
+                        server,
+                        channel,
+                        nick,
+                    } => self.announcer(Room::Irc {
+                        server: server.clone(),
+                        channel: channel.clone(),
+                        nick: nick.clone().unwrap_or_else(|| self.irc_nick()),
+                    }),
+                }
+            })
+            .collect()
+    }
+
+    /// Whether to draw without colors, for terminals that have none.
+    pub fn monochrome(&self) -> bool {
+        match self.colors {
+            Colors::Always => false,
+            Colors::Never => true,
+            // https://no-color.org
+            Colors::Auto => {
+                std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+                    || std::env::var("TERM").is_ok_and(|term| term == "dumb")
+            }
+        }
+    }
+
+    /// Announces new personal bests to a room, against the bests in the history.
+    fn announcer(&self, room: Room) -> Box<dyn StatsSink> {
+        Box::new(Announcer::spawn(room, self.player_name(), history::load()))
+    }
+
+    /// The player name with only what IRC allows in a nick, which can't start with a digit.
+    fn irc_nick(&self) -> String {
+        let name: String = self
+            .player_name()
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+            .collect();
+        format!("wpm-{name}")
+            .chars()
+            .take(IRC_NICK_LENGTH)
+            .collect()
+    }
+
+    /// Time between two ticks of the metronome, none when it is off.
+    pub fn metronome_beat(&self) -> Option<Duration> {
+        let per_minute = match self.metronome {
+            0 => self.metronome_wpm as f32 * CHARS_PER_WORD,
+            keys => keys as f32,
+        };
+        (per_minute > 0.).then(|| Duration::from_secs_f32(60. / per_minute))
+    }
+
+    /// Layout the letters are typed in, the emulated one when there is one.
+    pub fn typing_layout(&self) -> Layout {
+        self.emulate_layout.unwrap_or(self.keyboard_layout)
+    }
+
+    /// The typing rules set in the config, as the engine takes them.
+    pub fn rules(&self) -> Rules {
+        Rules {
+            strict_space: self.strict_space,
+            stop_on_error: self.stop_on_error,
+            overtype: self.overtype,
+            punctuation: self.punctuation,
+            dead_keys: self.dead_keys,
+        }
+    }
+}
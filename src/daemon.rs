@@ -0,0 +1,80 @@
+use notify_rust::Notification;
+use std::{env, fs, io, path::PathBuf, thread};
+use wpm_core::{
+    history, metrics,
+    reminder::{self, Schedule},
+};
+
+use crate::config::Config;
+
+// Names of the units `wpm daemon --systemd` writes
+const TIMER_UNIT: &str = "wpm-reminder.timer";
+const SERVICE_UNIT: &str = "wpm-reminder.service";
+
+/// Waits for each reminder time of the config, and reminds of the daily goal when it isn't met
+/// yet, serving the metrics meanwhile when they are on. Runs until killed.
+pub fn run(config: &Config) -> io::Result<()> {
+    let schedule = schedule(config)?;
+    if config.metrics_port != 0 {
+        let addr = metrics::serve(config.metrics_port, history::load)?;
+        println!("Metrics at http://{addr}/metrics");
+    }
+    while let Some(wait) = schedule.until_next() {
+        thread::sleep(wait);
+        remind(config)?;
+    }
+    Ok(())
+}
+
+/// Shows a notification if the daily goal isn't met yet, for timers and cron.
+pub fn remind(config: &Config) -> io::Result<()> {
+    let done = reminder::minutes_since(&history::load(), history::start_of_today());
+    if config.daily_goal == 0 || done >= config.daily_goal as f32 {
+        return Ok(());
+    }
+
+    Notification::new()
+        .appname("wpm")
+        .summary("Time to practice typing")
+        .body(&format!(
+            "{:.0} of {} minutes typed today",
+            done, config.daily_goal
+        ))
+        .show()
+        .map(|_| ())
+        .map_err(io::Error::other)
+}
+
+/// Writes a systemd user timer reminding at the times of the config, in place of the daemon.
+/// Returns the folder of the units.
+pub fn install_systemd(config: &Config) -> io::Result<PathBuf> {
+    let schedule = schedule(config)?;
+    let dir = dirs::config_dir()
+        .ok_or(io::ErrorKind::NotFound)?
+        .join("systemd/user");
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join(TIMER_UNIT), schedule.systemd_timer())?;
+    fs::write(
+        dir.join(SERVICE_UNIT),
+        reminder::systemd_service(&env::current_exe()?),
+    )?;
+    Ok(dir)
+}
+
+fn schedule(config: &Config) -> io::Result<Schedule> {
+    if config.daily_goal == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no daily goal to remind of, set daily_goal in the config",
+        ));
+    }
+    let schedule = Schedule::parse(&config.reminders)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+    if schedule.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no times to remind at, set reminders in the config",
+        ));
+    }
+    Ok(schedule)
+}
@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+/// A speed to reach in a few timed attempts, for `wpm gate` in shell and git hooks.
+#[derive(Debug, Clone, Copy)]
+pub struct Gate {
+    pub wpm: f32,
+    /// Tests stop once this is up, or at the end of their quote
+    pub time: Duration,
+    pub max_attempts: u32,
+    pub attempts: u32,
+    pub passed: bool,
+}
+
+impl Gate {
+    pub fn new(wpm: f32, time: Duration, max_attempts: u32) -> Self {
+        Gate {
+            wpm,
+            time,
+            max_attempts,
+            attempts: 0,
+            passed: false,
+        }
+    }
+
+    /// Counts an attempt, results that can't be trusted never pass.
+    pub fn attempt(&mut self, wpm: f32, valid: bool) {
+        self.attempts += 1;
+        self.passed |= valid && wpm >= self.wpm;
+    }
+
+    /// Whether there is nothing left to try, the gate being passed or out of attempts.
+    pub fn is_over(&self) -> bool {
+        self.passed || self.attempts >= self.max_attempts
+    }
+}
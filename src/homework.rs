@@ -0,0 +1,65 @@
+use wpm_core::{
+    assignment::{Assignment, Attempt, StudentKey, Submission},
+    replay::Replay,
+};
+
+/// Working through the texts of an assignment in order, for `wpm assignment do`.
+#[derive(Debug, Clone)]
+pub struct Homework {
+    pub assignment: Assignment,
+    pub submission: Submission,
+    /// Index of the text being typed
+    pub text: usize,
+    /// On the text being typed
+    pub attempts: u32,
+    pub passed: bool,
+}
+
+impl Homework {
+    pub fn new(assignment: Assignment, student: String) -> Self {
+        Homework {
+            submission: Submission::new(student, &assignment),
+            assignment,
+            text: 0,
+            attempts: 0,
+            passed: false,
+        }
+    }
+
+    /// The text being typed, none once the assignment is over.
+    pub fn current(&self) -> Option<&str> {
+        self.assignment.texts.get(self.text).map(String::as_str)
+    }
+
+    /// Keeps a finished attempt for the teacher, results that can't be trusted never pass.
+    pub fn attempt(&mut self, replay: Replay, accuracy: f32, invalid: Option<&str>) {
+        self.attempts += 1;
+        self.passed |= invalid.is_none() && accuracy >= self.assignment.min_accuracy;
+        self.submission.attempts.push(Attempt {
+            text: self.text,
+            invalid: invalid.map(str::to_string),
+            replay,
+        });
+    }
+
+    /// Whether there is nothing left to try on the current text.
+    pub fn text_over(&self) -> bool {
+        self.passed || self.attempts >= self.assignment.attempts
+    }
+
+    /// Moves on to the next text once the current one is over.
+    pub fn advance(&mut self) {
+        if self.text_over() {
+            self.text += 1;
+            self.attempts = 0;
+            self.passed = false;
+        }
+    }
+
+    /// The submission, signed to send back.
+    pub fn signed(&self, key: &StudentKey) -> Submission {
+        let mut submission = self.submission.clone();
+        submission.sign(key);
+        submission
+    }
+}
@@ -0,0 +1,706 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use wpm_core::history;
+
+/// Language of the interface, quotes keep their own.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UiLanguage {
+    /// The language of the system locale when there is a translation, English otherwise
+    #[default]
+    Auto,
+    En,
+    Fr,
+}
+
+impl UiLanguage {
+    pub fn strings(self) -> &'static Strings {
+        match self {
+            UiLanguage::En => &EN,
+            UiLanguage::Fr => &FR,
+            UiLanguage::Auto => match locale("LC_MESSAGES") {
+                Some(locale) if locale.starts_with("fr") => &FR,
+                _ => &EN,
+            },
+        }
+    }
+}
+
+/// The locale of a category like `LC_MESSAGES`, like `fr_FR.UTF-8`, by the precedence POSIX
+/// gives the variables.
+fn locale(category: &str) -> Option<String> {
+    ["LC_ALL", category, "LANG"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()))
+}
+
+/// Order of the parts of a date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateStyle {
+    /// 2024-05-17
+    Iso,
+    /// 05/17/2024
+    MonthFirst,
+    /// 17/05/2024
+    DaySlash,
+    /// 17.05.2024
+    DayDot,
+    /// 17-05-2024
+    DayDash,
+}
+
+/// How numbers and dates are written, as expected in a locale. Only the common conventions
+/// are known, anything else is written the English way with ISO dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    decimal_comma: bool,
+    // a space before % and units, like "97,5 %"
+    spaced_units: bool,
+    dates: DateStyle,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale::new("C")
+    }
+}
+
+impl Locale {
+    /// The conventions of a locale name like `de_DE.UTF-8`, or of the system when it is empty.
+    pub fn new(name: &str) -> Self {
+        let name = match name {
+            "" => locale("LC_NUMERIC").unwrap_or_default(),
+            name => name.to_string(),
+        };
+        let name = name.split(['.', '@']).next().unwrap_or_default();
+        let (language, region) = name.split_once(['_', '-']).unwrap_or((name, ""));
+
+        let decimal_comma = matches!(
+            language,
+            "fr" | "de"
+                | "es"
+                | "it"
+                | "pt"
+                | "nl"
+                | "ru"
+                | "pl"
+                | "sv"
+                | "da"
+                | "fi"
+                | "nb"
+                | "nn"
+                | "no"
+                | "cs"
+                | "tr"
+        ) && !matches!(region, "CH" | "MX");
+        let spaced_units = matches!(
+            language,
+            "fr" | "de" | "es" | "ru" | "pl" | "sv" | "da" | "fi" | "nb" | "nn" | "no" | "cs"
+        );
+        let dates = match (language, region) {
+            ("en", "US") => DateStyle::MonthFirst,
+            ("en", "" | "CA") | ("ja" | "zh" | "ko" | "sv" | "lt" | "C" | "POSIX" | "", _) => {
+                DateStyle::Iso
+            }
+            ("de" | "ru" | "pl" | "cs" | "fi" | "nb" | "nn" | "no" | "tr" | "da", _) => {
+                DateStyle::DayDot
+            }
+            ("nl", _) => DateStyle::DayDash,
+            _ => DateStyle::DaySlash,
+        };
+
+        Locale {
+            decimal_comma,
+            spaced_units,
+            dates,
+        }
+    }
+
+    /// A number with `decimals` digits after the separator.
+    pub fn number(&self, value: f32, decimals: usize) -> String {
+        let number = format!("{value:.decimals$}");
+        if self.decimal_comma {
+            number.replace('.', ",")
+        } else {
+            number
+        }
+    }
+
+    /// A percentage, like "97.5%" or "97,5 %".
+    pub fn percent(&self, value: f32, decimals: usize) -> String {
+        self.with_unit(self.number(value, decimals), "%")
+    }
+
+    /// A duration in seconds, like "6.3s" or "6,3 s".
+    pub fn seconds(&self, value: f32, decimals: usize) -> String {
+        self.with_unit(self.number(value, decimals), "s")
+    }
+
+    fn with_unit(&self, number: String, unit: &str) -> String {
+        if self.spaced_units {
+            format!("{number} {unit}")
+        } else {
+            format!("{number}{unit}")
+        }
+    }
+
+    /// Day of a timestamp in the local time zone.
+    pub fn date(&self, timestamp: u64) -> String {
+        let iso = history::date(timestamp);
+        let (Some(year), Some(month), Some(day)) = (iso.get(..4), iso.get(5..7), iso.get(8..10))
+        else {
+            return iso;
+        };
+        match self.dates {
+            DateStyle::Iso => iso.clone(),
+            DateStyle::MonthFirst => format!("{month}/{day}/{year}"),
+            DateStyle::DaySlash => format!("{day}/{month}/{year}"),
+            DateStyle::DayDot => format!("{day}.{month}.{year}"),
+            DateStyle::DayDash => format!("{day}-{month}-{year}"),
+        }
+    }
+}
+
+/// Replaces each `{}` of a template with the next argument.
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut args = args.iter();
+    let mut parts = template.split("{}");
+    let mut filled = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        if let Some(arg) = args.next() {
+            filled.push_str(&arg.to_string());
+        }
+        filled.push_str(part);
+    }
+    filled
+}
+
+/// Every text of the interface in one language. Key names and commands are left out, they
+/// are typed as they are. Fields with `{}` are templates for `fill`.
+#[derive(Debug)]
+pub struct Strings {
+    // Typing screen
+    pub typing_title: &'static str,
+    pub replay_title: &'static str,
+    pub caps_lock_warning: &'static str,
+    pub resume: &'static str,
+    pub pause: &'static str,
+    pub speed: &'static str,
+    pub quit: &'static str,
+    pub start_typing: &'static str,
+    pub start_key: &'static str,
+    pub change_length: &'static str,
+    pub new_quote: &'static str,
+    pub restart: &'static str,
+    pub skip: &'static str,
+    pub info: &'static str,
+    pub length: &'static str,
+    pub accuracy: &'static str,
+    pub fixed_count: &'static str,
+    pub source: &'static str,
+    pub quotes_credit: &'static str,
+    pub quote: &'static str,
+    pub id: &'static str,
+    pub chars: &'static str,
+    pub difficulty: &'static str,
+    pub you: &'static str,
+    pub attempt_count: &'static str,
+    pub best: &'static str,
+    pub average: &'static str,
+    pub never_typed: &'static str,
+    pub rated: &'static str,
+    /// How a quote felt, in the order of `rating::Tag::ALL`
+    pub tags: [&'static str; 4],
+    pub caps_lock: &'static str,
+
+    // Results screen
+    pub results_title: &'static str,
+    /// Entries of the menu, in the order of `app::Action`
+    pub actions: [&'static str; 6],
+    pub rate: &'static str,
+    pub to_choose: &'static str,
+    pub to_confirm: &'static str,
+    pub to_copy: &'static str,
+    pub copied: &'static str,
+    pub chart_title: &'static str,
+    pub show_latency: &'static str,
+    pub latency_title: &'static str,
+    pub show_speed: &'static str,
+    /// Median interval, threshold of the pauses, how many, time they took and the speed without them
+    pub latency_summary: &'static str,
+    pub stats_title: &'static str,
+    pub time: &'static str,
+    pub words: &'static str,
+    pub per_minute: &'static str,
+    pub correct: &'static str,
+    pub incorrect: &'static str,
+    pub fixed: &'static str,
+    pub burst: &'static str,
+    /// Best word burst and best burst of all time
+    pub word_and_peak: &'static str,
+    pub mistakes: &'static str,
+    /// Kinds of mistakes, in the order of `mistakes::Kind`
+    pub mistake_kinds: [&'static str; 5],
+    pub invalid: &'static str,
+    pub case_errors: &'static str,
+    pub splits: &'static str,
+    pub line: &'static str,
+    pub best_title: &'static str,
+    pub not_counted: &'static str,
+    pub new_best: &'static str,
+    pub previous: &'static str,
+    pub delta: &'static str,
+    pub first_test: &'static str,
+    pub worst_words: &'static str,
+    pub flawless: &'static str,
+    pub challenge_friend: &'static str,
+    pub challenge_won: &'static str,
+    pub challenge_lost: &'static str,
+    pub me: &'static str,
+    pub them: &'static str,
+    pub short_speed: &'static str,
+    pub short_accuracy: &'static str,
+    pub short_time: &'static str,
+    pub gate_passed: &'static str,
+    pub short_of: &'static str,
+    pub gate_go_on: &'static str,
+    pub no_attempts_left: &'static str,
+    pub tab_try_again: &'static str,
+    pub needed: &'static str,
+    pub attempt_of: &'static str,
+    pub text_passed: &'static str,
+    pub accuracy_needed: &'static str,
+    pub tab_next_text: &'static str,
+    pub tab_finish: &'static str,
+    pub text: &'static str,
+    pub text_of: &'static str,
+    pub minutes_title: &'static str,
+    pub endurance_done: &'static str,
+    /// Test finished and how many there are
+    pub step_done: &'static str,
+    pub session_done: &'static str,
+    pub session: &'static str,
+    /// Tests, speed, accuracy and time of the session so far
+    pub session_of: &'static str,
+    pub tab_next_step: &'static str,
+    pub fatigue: &'static str,
+    /// Change of speed from the first minutes to the last
+    pub fatigue_of: &'static str,
+    pub too_short: &'static str,
+    pub best_run: &'static str,
+    pub budget_kept: &'static str,
+    pub budget_blown: &'static str,
+    /// Mistakes made and allowed
+    pub mistakes_of: &'static str,
+    pub best_streak: &'static str,
+    /// Right keys in a row
+    pub clean_streak: &'static str,
+    pub shift: &'static str,
+    pub rhythm: &'static str,
+    pub beat: &'static str,
+    /// Milliseconds between two keys on the beat
+    pub beat_of: &'static str,
+    pub off_beat: &'static str,
+    /// Intervals off the beat, and all of them
+    pub off_beat_of: &'static str,
+    /// Time lost for each shifted key, and in all
+    pub shift_penalty: &'static str,
+    pub lesson_passed: &'static str,
+    pub tab_next_lesson: &'static str,
+    pub lesson_of: &'static str,
+
+    // Races
+    pub race_on_port: &'static str,
+    pub room: &'static str,
+    pub race: &'static str,
+    pub starting_in: &'static str,
+    pub others_join: &'static str,
+    pub start_when_ready: &'static str,
+    pub waiting_for_host: &'static str,
+    pub start: &'static str,
+    /// Place in a race, like 1st
+    pub ordinal: fn(usize) -> String,
+
+    // Leaderboard
+    pub leaderboard_title: &'static str,
+    pub back: &'static str,
+    pub no_leaderboard: &'static str,
+    pub no_standings: &'static str,
+    pub name: &'static str,
+    pub best_column: &'static str,
+    pub average_column: &'static str,
+    pub tests: &'static str,
+
+    // Achievements
+    pub achievements_title: &'static str,
+    /// Name and what it takes, in the order of `achievements::Achievement`
+    pub achievements: [(&'static str, &'static str); 4],
+    /// Date it was unlocked
+    pub unlocked_on: &'static str,
+    pub locked: &'static str,
+    pub achievement_unlocked: &'static str,
+
+    // History
+    pub history_title: &'static str,
+    pub no_history: &'static str,
+    pub to_open: &'static str,
+    pub date_column: &'static str,
+    pub speed_column: &'static str,
+    pub accuracy_column: &'static str,
+    pub time_column: &'static str,
+    pub text_column: &'static str,
+    /// A test opened from the history that has no replay to show its text from
+    pub no_replay: &'static str,
+    /// Graphemes left wrong, fixed, skipped and typed in excess
+    pub diff_counts: &'static str,
+    // XP
+    /// Level, then points into it and points it takes
+    pub level: &'static str,
+}
+
+pub static EN: Strings = Strings {
+    typing_title: " Typing Test ",
+    replay_title: " Replay {}x ",
+    caps_lock_warning: " ⚠ Caps Lock is on ⚠ ",
+    resume: " Resume ",
+    pause: " Pause ",
+    speed: " Speed ",
+    quit: " Quit ",
+    start_typing: " Start typing to ",
+    start_key: "<start>",
+    change_length: " Change quote length ",
+    new_quote: " New quote ",
+    restart: " Restart ",
+    skip: " Skip ",
+    info: " Info ",
+    length: "  Length: ",
+    accuracy: "Accuracy: ",
+    fixed_count: " ({} fixed)",
+    source: "Source: ",
+    quotes_credit: "Quotes provided by monkeytype.com",
+    quote: " Quote ",
+    id: "Id: ",
+    chars: "{} chars",
+    difficulty: "Difficulty: ",
+    you: "You: ",
+    attempt_count: "{} attempts",
+    best: "Best: ",
+    average: "Average: ",
+    never_typed: "never typed this quote",
+    rated: "Rated: ",
+    tags: ["fun", "boring", "easy", "hard"],
+    caps_lock: "CAPS LOCK",
+
+    results_title: " Typing Test Completed ",
+    actions: [
+        "Retry",
+        "New quote",
+        "Change length",
+        "Details",
+        "Share",
+        "Quit",
+    ],
+    rate: "Rate this quote: ",
+    to_choose: " to choose, ",
+    to_confirm: " to confirm, ",
+    to_copy: " to copy ",
+    copied: " copied! ",
+    chart_title: " WPM over time ",
+    show_latency: " key intervals ",
+    latency_title: " Key intervals ",
+    show_speed: " speed ",
+    latency_summary: "median {} ms · pauses over {} s: {} ({} of the time) · {} {} without them",
+    stats_title: " Stats ",
+    time: "Time: ",
+    words: "Words: ",
+    per_minute: " ({} per minute)",
+    correct: "Correct: ",
+    incorrect: "Incorrect: ",
+    fixed: "Fixed: ",
+    burst: "Burst: ",
+    word_and_peak: " · word {}, peak {}",
+    mistakes: "Mistakes: ",
+    mistake_kinds: ["next key", "swapped", "left out", "extra", "other"],
+    invalid: "Invalid result: {}, not saved",
+    case_errors: "Caps Lock on? Mostly case errors",
+    splits: "Splits",
+    line: "line",
+    best_title: " Personal best ",
+    not_counted: "Not counted",
+    new_best: "New personal best!",
+    previous: "Previous: ",
+    delta: "Delta: ",
+    first_test: "First recorded test!",
+    worst_words: "Worst words",
+    flawless: "none, flawless!",
+    challenge_friend: "Challenge a friend",
+    challenge_won: "Challenge won!",
+    challenge_lost: "Challenge lost",
+    me: "You",
+    them: "Them",
+    short_speed: "Speed",
+    short_accuracy: "Acc",
+    short_time: "Time",
+    gate_passed: "Gate passed!",
+    short_of: "{} short",
+    gate_go_on: "TAB or ESC to go on",
+    no_attempts_left: "no attempts left",
+    tab_try_again: "TAB to try again",
+    needed: "Needed: ",
+    attempt_of: ", attempt {} of {}",
+    text_passed: "Text passed!",
+    accuracy_needed: "{}% accuracy needed",
+    tab_next_text: "TAB for the next text",
+    tab_finish: "TAB to finish",
+    text: "Text: ",
+    text_of: "{} of {}",
+    minutes_title: " WPM per minute ",
+    endurance_done: "Endurance run over!",
+    step_done: "Test {} of {} done!",
+    session_done: "Session over!",
+    session: "Session: ",
+    session_of: "{} tests, {}, {} accuracy, {}",
+    tab_next_step: "TAB for the next test",
+    fatigue: "Fatigue: ",
+    fatigue_of: "{} from the first minutes to the last",
+    too_short: "too short to tell",
+    best_run: "Best run this long: ",
+    budget_kept: "Within the error budget!",
+    budget_blown: "Over the error budget",
+    mistakes_of: "{} of {} mistakes",
+    best_streak: "Longest clean streak: ",
+    clean_streak: " ({} right keys in a row)",
+    shift: "Shift: ",
+    rhythm: "Rhythm: ",
+    beat: "Beat: ",
+    beat_of: "{} ms a key",
+    off_beat: "Off the beat: ",
+    off_beat_of: "{} of {} keys",
+    shift_penalty: "{} ms a shifted key ({} lost)",
+    lesson_passed: "Lesson passed!",
+    tab_next_lesson: "TAB for the next lesson",
+    lesson_of: "Lesson {} of {}",
+
+    race_on_port: " Race on port {} ",
+    room: " Room {} ",
+    race: " Race ",
+    starting_in: "Starting in {}...",
+    others_join: "Others join with ",
+    start_when_ready: ", start when everyone is there",
+    waiting_for_host: "Waiting for the host to start the race...",
+    start: " Start ",
+    ordinal: |place| {
+        let suffix = match (place % 10, place % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+        format!("{place}{suffix}")
+    },
+
+    leaderboard_title: " Leaderboard ",
+    back: " Back ",
+    no_leaderboard: "Set leaderboard_url in the config, then run wpm sync",
+    no_standings: "No standings yet, run wpm sync",
+    name: "Name",
+    best_column: "Best",
+    average_column: "Average",
+    tests: "Tests",
+
+    achievements_title: " Achievements ",
+    achievements: [
+        ("Century", "a test at 100 WPM or more"),
+        ("Week in a row", "tests on 7 days in a row"),
+        ("Regular", "100 tests"),
+        ("Sharpshooter", "99% accuracy on a text of 50 words or more"),
+    ],
+    unlocked_on: "unlocked on {}",
+    locked: "locked",
+    achievement_unlocked: "Achievement unlocked: ",
+
+    history_title: " History ",
+    no_history: "No tests yet",
+    to_open: " to open, ",
+    date_column: "Date",
+    speed_column: "Speed",
+    accuracy_column: "Accuracy",
+    time_column: "Time",
+    text_column: "Text",
+    no_replay: "No replay of this test, turn on record_replays in the config to keep them",
+    diff_counts: "{} left wrong, {} fixed, {} skipped, {} extra",
+    level: "Level {} · {}/{} XP  ",
+};
+
+pub static FR: Strings = Strings {
+    typing_title: " Test de frappe ",
+    replay_title: " Rediffusion {}x ",
+    caps_lock_warning: " ⚠ Verr. Maj. activé ⚠ ",
+    resume: " Reprendre ",
+    pause: " Pause ",
+    speed: " Vitesse ",
+    quit: " Quitter ",
+    start_typing: " Tapez pour ",
+    start_key: "<commencer>",
+    change_length: " Longueur ",
+    new_quote: " Nouvelle citation ",
+    restart: " Recommencer ",
+    skip: " Passer ",
+    info: " Infos ",
+    length: "  Longueur : ",
+    accuracy: "Précision : ",
+    fixed_count: " ({} corrigées)",
+    source: "Source : ",
+    quotes_credit: "Citations fournies par monkeytype.com",
+    quote: " Citation ",
+    id: "Id : ",
+    chars: "{} caractères",
+    difficulty: "Difficulté : ",
+    you: "Vous : ",
+    attempt_count: "{} essais",
+    best: "Record : ",
+    average: "Moyenne : ",
+    never_typed: "citation jamais tapée",
+    rated: "Avis : ",
+    tags: ["amusante", "ennuyeuse", "facile", "difficile"],
+    caps_lock: "VERR. MAJ.",
+
+    results_title: " Test terminé ",
+    actions: [
+        "Recommencer",
+        "Nouvelle citation",
+        "Longueur",
+        "Détails",
+        "Partager",
+        "Quitter",
+    ],
+    rate: "Votre avis : ",
+    to_choose: " pour choisir, ",
+    to_confirm: " pour valider, ",
+    to_copy: " pour copier ",
+    copied: " copié ! ",
+    chart_title: " WPM au fil du test ",
+    show_latency: " intervalles ",
+    latency_title: " Intervalles entre touches ",
+    show_speed: " vitesse ",
+    latency_summary: "médiane {} ms · pauses de plus de {} s : {} ({} du temps) · {} {} sans elles",
+    stats_title: " Statistiques ",
+    time: "Temps : ",
+    words: "Mots : ",
+    per_minute: " ({} par minute)",
+    correct: "Justes : ",
+    incorrect: "Fausses : ",
+    fixed: "Corrigées : ",
+    burst: "Pointe : ",
+    word_and_peak: " · mot {}, record {}",
+    mistakes: "Erreurs : ",
+    mistake_kinds: ["touche voisine", "inversion", "oubli", "en trop", "autre"],
+    invalid: "Résultat invalide : {}, non enregistré",
+    case_errors: "Verr. Maj. activé ? Surtout des erreurs de casse",
+    splits: "Temps par ligne",
+    line: "ligne",
+    best_title: " Record personnel ",
+    not_counted: "Non compté",
+    new_best: "Nouveau record !",
+    previous: "Précédent : ",
+    delta: "Écart : ",
+    first_test: "Premier test enregistré !",
+    worst_words: "Mots les plus ratés",
+    flawless: "aucun, parfait !",
+    challenge_friend: "Défier un ami",
+    challenge_won: "Défi gagné !",
+    challenge_lost: "Défi perdu",
+    me: "Vous",
+    them: "Eux",
+    short_speed: "Vit.",
+    short_accuracy: "Préc.",
+    short_time: "Temps",
+    gate_passed: "Seuil atteint !",
+    short_of: "{} de trop peu",
+    gate_go_on: "TAB ou ESC pour continuer",
+    no_attempts_left: "plus d'essais",
+    tab_try_again: "TAB pour réessayer",
+    needed: "Requis : ",
+    attempt_of: ", essai {} sur {}",
+    text_passed: "Texte réussi !",
+    accuracy_needed: "{}% de précision requis",
+    tab_next_text: "TAB pour le texte suivant",
+    tab_finish: "TAB pour terminer",
+    text: "Texte : ",
+    text_of: "{} sur {}",
+    minutes_title: " WPM par minute ",
+    endurance_done: "Course d'endurance terminée !",
+    step_done: "Test {} sur {} terminé !",
+    session_done: "Séance terminée !",
+    session: "Séance : ",
+    session_of: "{} tests, {}, {} de précision, {}",
+    tab_next_step: "TAB pour le test suivant",
+    fatigue: "Fatigue : ",
+    fatigue_of: "{} des premières minutes aux dernières",
+    too_short: "trop court pour le dire",
+    best_run: "Meilleure course de cette durée : ",
+    budget_kept: "Dans le budget d'erreurs !",
+    budget_blown: "Budget d'erreurs dépassé",
+    mistakes_of: "{} fautes sur {}",
+    best_streak: "Plus longue série sans faute : ",
+    clean_streak: " ({} touches justes d'affilée)",
+    shift: "Maj : ",
+    rhythm: "Rythme : ",
+    beat: "Tempo : ",
+    beat_of: "{} ms par touche",
+    off_beat: "Hors tempo : ",
+    off_beat_of: "{} touches sur {}",
+    shift_penalty: "{} ms par touche en majuscule ({} perdues)",
+    lesson_passed: "Leçon réussie !",
+    tab_next_lesson: "TAB pour la leçon suivante",
+    lesson_of: "Leçon {} sur {}",
+
+    race_on_port: " Course sur le port {} ",
+    room: " Salon {} ",
+    race: " Course ",
+    starting_in: "Départ dans {}...",
+    others_join: "Les autres rejoignent avec ",
+    start_when_ready: ", lancez quand tout le monde est là",
+    waiting_for_host: "En attente du départ donné par l'hôte...",
+    start: " Lancer ",
+    ordinal: |place| match place {
+        1 => "1er".to_string(),
+        _ => format!("{place}e"),
+    },
+
+    leaderboard_title: " Classement ",
+    back: " Retour ",
+    no_leaderboard: "Réglez leaderboard_url dans la config, puis lancez wpm sync",
+    no_standings: "Pas encore de classement, lancez wpm sync",
+    name: "Nom",
+    best_column: "Record",
+    average_column: "Moyenne",
+    tests: "Tests",
+
+    achievements_title: " Succès ",
+    achievements: [
+        ("Centurion", "un test à 100 WPM ou plus"),
+        ("Semaine complète", "des tests 7 jours d'affilée"),
+        ("Habitué", "100 tests"),
+        (
+            "Tireur d'élite",
+            "99 % de précision sur un texte de 50 mots ou plus",
+        ),
+    ],
+    unlocked_on: "débloqué le {}",
+    locked: "à débloquer",
+    achievement_unlocked: "Succès débloqué : ",
+
+    history_title: " Historique ",
+    no_history: "Aucun test pour l'instant",
+    to_open: " pour ouvrir, ",
+    date_column: "Date",
+    speed_column: "Vitesse",
+    accuracy_column: "Précision",
+    time_column: "Temps",
+    text_column: "Texte",
+    no_replay: "Pas de rediffusion de ce test, activez record_replays dans la config pour les garder",
+    diff_counts: "{} fautes restées, {} corrigées, {} sautées, {} en trop",
+    level: "Niveau {} · {}/{} XP  ",
+};
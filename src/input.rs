@@ -0,0 +1,280 @@
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+use ratatui::{
+    layout::{Position, Rect},
+    widgets::Block,
+};
+use std::{io, time::Duration};
+
+use crate::{
+    app::{Action, App, Msg},
+    ui::{group_label, span_areas},
+};
+use wpm_core::rating::Tag;
+
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    Group(usize),
+    Action(Action),
+    Tag(Tag),
+    /// A test of the history browser, by its place in the list
+    Row(usize),
+}
+
+/// Where the app gets its input from, so it can be driven by something else than a terminal.
+pub trait InputSource {
+    /// Waits up to `timeout` for input, and turns it into the message it stands for.
+    fn next_msg(&mut self, app: &mut App, timeout: Duration) -> io::Result<Option<Msg>>;
+}
+
+/// Events of the terminal, read with crossterm.
+#[derive(Debug, Default)]
+pub struct TerminalInput;
+
+impl InputSource for TerminalInput {
+    fn next_msg(&mut self, app: &mut App, timeout: Duration) -> io::Result<Option<Msg>> {
+        app.read_msg(timeout)
+    }
+}
+
+impl App {
+    /// Waits up to `timeout` for a terminal event, and turns it into the message it stands for.
+    fn read_msg(&mut self, timeout: Duration) -> io::Result<Option<Msg>> {
+        if !event::poll(timeout)? {
+            return Ok(None);
+        }
+
+        Ok(match event::read()? {
+            Event::Key(key_event) => self.key_msg(key_event),
+            Event::Mouse(mouse_event) => self.mouse_msg(mouse_event),
+            Event::Paste(text) => Some(Msg::Paste(text)),
+            Event::Resize(_, _) => Some(Msg::Resize),
+            _ => None,
+        })
+    }
+
+    /// Drops releases, and repeats of held keys unless they are editing keys. Terminals that
+    /// report releases (like on Windows) can send a held key as more presses, these count as
+    /// repeats too.
+    fn key_msg(&mut self, key_event: KeyEvent) -> Option<Msg> {
+        if self.playback.is_some() {
+            if key_event.kind != KeyEventKind::Press {
+                return None;
+            }
+
+            return match key_event.code {
+                KeyCode::Esc => Some(Msg::Quit),
+                KeyCode::Char(' ') => Some(Msg::TogglePause),
+                KeyCode::Right | KeyCode::Char('+') => Some(Msg::Faster),
+                KeyCode::Left | KeyCode::Char('-') => Some(Msg::Slower),
+                _ => None,
+            };
+        }
+
+        let repeat = match key_event.kind {
+            KeyEventKind::Release => {
+                self.reports_releases = true;
+                if self.held == Some(key_event.code) {
+                    self.held = None;
+                }
+                return None;
+            }
+            KeyEventKind::Repeat => true,
+            KeyEventKind::Press => self.reports_releases && self.held == Some(key_event.code),
+        };
+
+        if !repeat {
+            self.held = Some(key_event.code);
+        } else if !matches!(
+            key_event.code,
+            KeyCode::Backspace | KeyCode::Left | KeyCode::Right
+        ) {
+            return None;
+        }
+
+        self.shift = key_event.modifiers.contains(KeyModifiers::SHIFT);
+        self.shortcut(key_event)
+    }
+
+    fn shortcut(&self, key_event: KeyEvent) -> Option<Msg> {
+        if self.show_history {
+            return Self::history_key(key_event.code);
+        }
+        if self.test.is_done()
+            && key_event
+                .modifiers
+                .difference(KeyModifiers::SHIFT)
+                .is_empty()
+            && let Some(msg) = Self::menu_key(key_event.code)
+        {
+            return Some(msg);
+        }
+
+        let msg = match key_event.code {
+            KeyCode::Esc => Msg::Quit,
+            KeyCode::F(1) => Msg::ToggleInfo,
+            KeyCode::F(2) => Msg::ToggleLeaderboard,
+            KeyCode::F(3) => Msg::ToggleAchievements,
+            KeyCode::F(4) => Msg::ToggleHistory,
+            KeyCode::Left => Msg::PreviousGroup,
+            KeyCode::Right => Msg::NextGroup,
+            // Ctrl+Backspace where the terminal tells it from Backspace, and Alt+Backspace
+            KeyCode::Backspace
+                if key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                Msg::DeleteWord
+            }
+            // Like in shells, and what some terminals send for Ctrl+Backspace
+            KeyCode::Char('w') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                Msg::DeleteWord
+            }
+            KeyCode::Char('r') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                Msg::Restart
+            }
+            KeyCode::Char('n') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                Msg::NewQuote
+            }
+            KeyCode::Tab if self.test.is_untouched() || self.test.is_done() => Msg::NewQuote,
+            KeyCode::Char('c') if self.test.is_done() && key_event.modifiers.is_empty() => {
+                Msg::CopyResult
+            }
+            KeyCode::Char('l') if self.test.is_done() && key_event.modifiers.is_empty() => {
+                Msg::ToggleLatency
+            }
+            // Ctrl or Alt combinations are shortcuts, not text. Both at once is AltGr on Windows
+            KeyCode::Char(_)
+                if key_event.modifiers.contains(KeyModifiers::CONTROL)
+                    != key_event.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                return None;
+            }
+            KeyCode::Char(char) => Msg::Type(match self.config.emulate_layout {
+                Some(target) => self.config.keyboard_layout.emulate(target, char),
+                None => char,
+            }),
+            KeyCode::Backspace => Msg::Backspace,
+            _ => return None,
+        };
+
+        Some(msg)
+    }
+
+    /// Keys of the results menu: arrows to pick an action and Enter to do it, or a letter
+    /// for each. Letters rate the quote too.
+    fn menu_key(code: KeyCode) -> Option<Msg> {
+        let msg = match code {
+            KeyCode::Left | KeyCode::Up => Msg::PreviousAction,
+            KeyCode::Right | KeyCode::Down => Msg::NextAction,
+            KeyCode::Enter => Msg::Act,
+            KeyCode::Char(c) => match c.to_ascii_lowercase() {
+                'r' => Msg::Restart,
+                'n' => Msg::NewQuote,
+                'm' => Msg::ChangeLength,
+                'd' => Msg::ToggleDetails,
+                's' => Msg::ToggleShare,
+                'q' => Msg::Quit,
+                'f' => Msg::Rate(Tag::Fun),
+                'b' => Msg::Rate(Tag::Boring),
+                'e' => Msg::Rate(Tag::Easy),
+                'h' => Msg::Rate(Tag::Hard),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        Some(msg)
+    }
+
+    /// Keys of the history browser, which takes no others.
+    fn history_key(code: KeyCode) -> Option<Msg> {
+        let msg = match code {
+            KeyCode::Esc => Msg::Quit,
+            KeyCode::F(4) => Msg::ToggleHistory,
+            KeyCode::Up => Msg::PreviousRow,
+            KeyCode::Down => Msg::NextRow,
+            KeyCode::Enter => Msg::OpenRow,
+            _ => return None,
+        };
+        Some(msg)
+    }
+
+    pub(crate) fn mouse_msg(&self, mouse_event: MouseEvent) -> Option<Msg> {
+        let position = Position::new(mouse_event.column, mouse_event.row);
+
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => self
+                .hitboxes()
+                .into_iter()
+                .find(|(area, _)| area.contains(position))
+                .map(|(_, target)| match target {
+                    Target::Group(gid) => Msg::SelectGroup(gid),
+                    Target::Action(action) => action.msg(),
+                    Target::Tag(tag) => Msg::Rate(tag),
+                    Target::Row(row) => Msg::SelectRow(row),
+                }),
+            // The wheel scrolls the history list, and goes through lengths elsewhere
+            MouseEventKind::ScrollUp if self.show_history => {
+                self.history_detail.is_none().then_some(Msg::PreviousRow)
+            }
+            MouseEventKind::ScrollDown if self.show_history => {
+                self.history_detail.is_none().then_some(Msg::NextRow)
+            }
+            MouseEventKind::ScrollUp => Some(Msg::PreviousGroup),
+            MouseEventKind::ScrollDown => Some(Msg::NextGroup),
+            // Moves and drags are reported too, they change nothing on screen
+            _ => None,
+        }
+    }
+
+    /// Clickable regions of the current screen, laid out exactly like `render` draws them.
+    fn hitboxes(&self) -> Vec<(Rect, Target)> {
+        let mut hitboxes = Vec::new();
+        let area = self.screen_area(self.area);
+
+        if self.show_leaderboard || self.show_achievements || self.waiting_for_race() {
+            return hitboxes;
+        }
+        if self.show_history {
+            if self.history_detail.is_none() && !self.history.is_empty() {
+                let rows = self.history_rows(Block::bordered().inner(area));
+                hitboxes.extend(
+                    rows.into_iter()
+                        .map(|(row, index)| (row, Target::Row(index))),
+                );
+            }
+            return hitboxes;
+        }
+        if self.test.is_done() {
+            let [_, rating, menu] = self.results_layout(area);
+            // A label and its key make one entry, the rating starts with its prompt
+            let entries = span_areas(&self.menu_line(), menu);
+            for (action, spans) in Action::ALL.into_iter().zip(entries.chunks(2)) {
+                hitboxes.push((spans[0].union(spans[1]), Target::Action(action)));
+            }
+            let tags = span_areas(&self.rating_line(), rating);
+            for (tag, spans) in Tag::ALL.into_iter().zip(tags[1..].chunks(2)) {
+                hitboxes.push((spans[0].union(spans[1]), Target::Tag(tag)));
+            }
+            return hitboxes;
+        }
+
+        if self.test.is_untouched() {
+            let row = self.test_layout(self.area).header;
+            let mut x = row.x + self.strings.length.chars().count() as u16;
+
+            for (gid, group) in self.groups().iter().enumerate() {
+                let width = group_label(group).len() as u16;
+                hitboxes.push((
+                    Rect::new(x, row.y, width, 1).intersection(row),
+                    Target::Group(gid),
+                ));
+                x += width;
+            }
+        }
+
+        hitboxes
+    }
+}
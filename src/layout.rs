@@ -0,0 +1,108 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    widgets::Block,
+};
+use serde::{Deserialize, Serialize};
+
+// Width of the dashboard side panel
+const SIDEBAR_WIDTH: u16 = 36;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Preset {
+    /// Only the length selection and the quote
+    Minimal,
+    /// Live stats and quote info under the quote
+    #[default]
+    Classic,
+    /// Live stats and quote info in a side panel next to the quote
+    Dashboard,
+}
+
+/// Where each widget of the typing screen goes, widgets a preset doesn't show have no area.
+#[derive(Debug, Clone, Copy)]
+pub struct TestLayout {
+    /// Length selection, or the previously typed lines once started
+    pub header: Rect,
+    pub quote: Rect,
+    pub stats: Option<Rect>,
+    /// Quote info panel, or just its source when the panel is hidden
+    pub info: Option<Rect>,
+}
+
+impl TestLayout {
+    /// Lays out the typing screen inside its border.
+    pub fn new(preset: Preset, area: Rect, info_panel: bool) -> Self {
+        let inner = Block::bordered().inner(area);
+        // blank + source, or the bordered info panel
+        let info_height = if info_panel { 5 } else { 2 };
+
+        match preset {
+            Preset::Minimal => {
+                let [header, quote] = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(2), Constraint::Min(1)])
+                    .areas(inner);
+
+                TestLayout {
+                    header,
+                    quote,
+                    stats: None,
+                    info: None,
+                }
+            }
+            Preset::Classic => {
+                let [header, quote, stats, info] = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(2), // length selection + blank
+                        Constraint::Min(1),    // quote
+                        Constraint::Length(2), // blank + WPM
+                        Constraint::Length(info_height),
+                    ])
+                    .areas(inner);
+
+                TestLayout {
+                    header,
+                    quote,
+                    stats: Some(stats),
+                    info: Some(info),
+                }
+            }
+            Preset::Dashboard => {
+                let [main, sidebar] = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Min(1), Constraint::Length(SIDEBAR_WIDTH)])
+                    .areas(inner);
+                let [header, quote] = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(2), Constraint::Min(1)])
+                    .areas(main);
+                let [stats, info] = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(2), Constraint::Min(0)])
+                    .areas(sidebar);
+
+                TestLayout {
+                    header,
+                    quote,
+                    stats: Some(stats),
+                    info: Some(info),
+                }
+            }
+        }
+    }
+}
+
+/// Splits off the status bar row at the bottom, which is empty when it has no segments.
+pub fn split_status(area: Rect, status_bar: bool) -> (Rect, Rect) {
+    let [main, status] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(if status_bar { 1 } else { 0 }),
+        ])
+        .areas(area);
+
+    (main, status)
+}
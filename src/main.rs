@@ -1,526 +1,1193 @@
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
-use rand::{rng, seq::IndexedRandom};
-use ratatui::{
-    DefaultTerminal, Frame,
-    buffer::Buffer,
-    layout::{Constraint, Direction, Layout, Rect},
+use crossterm::{
+    event::{
+        DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    execute,
     style::Stylize,
-    symbols::border,
-    text::{Line, Span, Text},
-    widgets::{Block, Paragraph, Widget},
+    terminal::supports_keyboard_enhancement,
 };
-use serde::{Deserialize, Serialize};
+use rand::seq::IndexedRandom;
 use std::{
-    io,
-    time::{Duration, SystemTime},
+    fs::{self, File},
+    io::{self, IsTerminal},
+    net::TcpListener,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use wpm_core::{
+    assignment::{self, Assignment, StudentKey, Submission},
+    card,
+    challenge::Challenge,
+    content::{self, Library, Remote},
+    course::{self, LESSONS},
+    custom::Drills,
+    diff::{self, Mark},
+    endurance, history,
+    keyboard::{self, KeyFilter},
+    leaderboard::Cache,
+    letters::Letters,
+    metrics,
+    mistakes::{Kind, Mistakes},
+    race::{self, Bot, Race},
+    replay, review,
+    sim::Simulation,
+    status,
+    telemetry::Telemetry,
 };
 
-#[derive(Debug, Deserialize, Serialize)]
-struct Quote {
-    text: String,
-    source: String,
-    length: u32,
-    id: u32,
-}
-
-#[derive(Debug, Deserialize, Serialize)]
-struct EnglishData {
-    language: String,
-    groups: Vec<[u32; 2]>,
-    quotes: Vec<Quote>,
-}
-
-const MAX_LENGTH_PER_LINE: usize = 50;
-const ENGLISH_JSON: &str = include_str!("english.json");
+use app::{App, MAX_LENGTH_PER_LINE};
+use config::{Config, Sink};
+use gate::Gate;
+use homework::Homework;
+use i18n::Locale;
+use input::TerminalInput;
+use serde::Serialize;
+use session::Session;
+
+mod animation;
+mod app;
+mod config;
+mod daemon;
+mod gate;
+mod homework;
+mod i18n;
+mod input;
+mod layout;
+mod notification;
+mod session;
+mod sound;
+mod ui;
+
+// Results the average bot is matched to, and its speed without any
+const BOT_AVERAGE_OF: usize = 10;
+const BOT_DEFAULT_WPM: f32 = 40.;
+// What `wpm gate` asks for without options
+const GATE_DEFAULT_WPM: f32 = 40.;
+const GATE_DEFAULT_TIME: Duration = Duration::from_secs(30);
+const GATE_DEFAULT_ATTEMPTS: u32 = 3;
+// Tests `wpm mistakes` compares to all the others
+const RECENT_TESTS: usize = 10;
+// Speed letters are judged against, keybr.com's default target
+const LETTERS_TARGET_WPM: f32 = 35.;
+// Mistakes `wpm accuracy` allows without a number
+const ERROR_BUDGET: u32 = 3;
+// Rolling stretches `wpm stats` compares to the ones before, in days
+const WEEK: u64 = 7;
+const MONTH: u64 = 30;
+// Results drawn on a card when there is no replay of the test for its graph
+const CARD_TREND_OF: usize = 20;
 
 fn main() -> io::Result<()> {
-    let mut terminal = ratatui::init();
-
-    let mut app = App {
-        start: SystemTime::now(),
-
-        correct: 0,
-        incorrect: 0,
-        words: 0,
-
-        selected_group: 0,
-        current_line: 0,
-        groups: Vec::with_capacity(4),
-
-        sentence: Vec::new(),
-        sentence_source: "loading quote...".to_string(),
-        typing: Vec::with_capacity(MAX_LENGTH_PER_LINE),
-        typed: Vec::new(),
-
-        exit: false,
-        done: None,
-    };
-
-    app.new_quote();
-
-    let app_result = app.run(&mut terminal);
-    ratatui::restore();
-
-    app_result
-}
-
-#[derive(Debug)]
-pub struct App {
-    start: SystemTime,
-
-    correct: u32,
-    incorrect: u32,
-    words: u32,
-
-    current_line: usize,
-    selected_group: usize,
-    groups: Vec<[u32; 2]>,
-
-    sentence: Vec<String>,
-    sentence_source: String,
-    typed: Vec<String>,
-    typing: Vec<char>,
-
-    exit: bool,
-    done: Option<SystemTime>,
-}
-
-impl App {
-    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
-        while !self.exit {
-            terminal.draw(|frame| self.draw(frame))?;
-            self.handle_events()?;
-        }
-        Ok(())
+    let mut config = Config::load();
+
+    // `--offline` and `--json` go anywhere. The first keeps quote packs from being downloaded,
+    // the second prints what was typed as JSON after quitting
+    let (flags, args): (Vec<String>, Vec<String>) = std::env::args()
+        .skip(1)
+        .partition(|arg| arg == "--offline" || arg == "--json");
+    let offline = flags.iter().any(|flag| flag == "--offline");
+    let json = flags.iter().any(|flag| flag == "--json");
+    if json {
+        // Its lines would follow the JSON on stdout, which already holds the results
+        config.sinks.retain(|sink| *sink != Sink::Stdout);
     }
-
-    fn draw(&self, frame: &mut Frame) {
-        frame.render_widget(self, frame.area());
-    }
-
-    fn handle_events(&mut self) -> io::Result<()> {
-        match event::read()? {
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                self.handle_key_event(key_event)
-            }
-            _ => {}
-        };
-        Ok(())
-    }
-
-    fn count_mistakes(&mut self) {
-        for i in 0..self.typing.len() {
-            if self.typing[i]
-                == self.sentence[self.current_line]
-                    .chars()
-                    .nth(i)
-                    .unwrap_or(' ')
-            {
-                self.correct += 1;
+    let mut args = args.into_iter();
+    let mut replay = None;
+    let mut book = None;
+    let mut race = None;
+    let mut bots = Vec::new();
+    let mut challenge = None;
+    let mut gate = None;
+    let mut ghost = None;
+    let mut homework = None;
+    let mut drill = None;
+    let mut practice = false;
+    let mut lesson = None;
+    let mut review = None;
+    let mut custom = None;
+    let mut error_budget = None;
+    let mut endurance_run = None;
+    let mut session = false;
+    let mut rhythm = false;
+    match args.next().as_deref() {
+        // `wpm replay [file]` plays back a replay, the latest one by default
+        Some("replay") => replay = Some(replay::load(&replay_path(args.next())?)?),
+        // `wpm diff [file]` prints the text of a replay with its mistakes marked, the latest one
+        // by default
+        Some("diff") => {
+            let replay = replay::load(&replay_path(args.next())?)?;
+            print_diff(&diff::of(&replay), io::stdout().is_terminal());
+            return Ok(());
+        }
+        // `wpm ghost <file>` races the keystrokes of a replay file, sent by a friend or your own
+        Some("ghost") => {
+            let path = args.next().ok_or(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "usage: wpm ghost <replay file>",
+            ))?;
+            let replay = replay::load(Path::new(&path))?;
+            let name = match replay.player.as_str() {
+                "" => "ghost".to_string(),
+                player => format!("{player} (ghost)"),
+            };
+            let bot = Bot::from_replay(name, &replay).ok_or(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the replay doesn't finish its text, there is nothing to race",
+            ))?;
+            ghost = Some((bot, replay));
+        }
+        // `wpm book <file>` types a text file page by page
+        Some("book") => {
+            let path = PathBuf::from(args.next().ok_or(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "usage: wpm book <file>",
+            ))?);
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            book = Some(App::book(name.into_owned(), File::open(&path)?)?);
+        }
+        // `wpm host [port]` starts a race others can join with `wpm join <host:port>`
+        Some("host") => {
+            let port = match args.next() {
+                Some(port) => port
+                    .parse()
+                    .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "invalid port"))?,
+                None => race::DEFAULT_PORT,
+            };
+            race = Some(Race::host(config.player_name(), port)?);
+        }
+        // `wpm join <host:port> [room]`, the room code only matters on a race server
+        Some("join") => {
+            let addr = args.next().ok_or(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "usage: wpm join <host:port> [room]",
+            ))?;
+            let addr = if addr.contains(':') {
+                addr
             } else {
-                self.incorrect += 1;
+                format!("{addr}:{}", race::DEFAULT_PORT)
+            };
+            race = Some(Race::join(config.player_name(), addr, args.next())?);
+        }
+        // `wpm bots [<wpm>|average]...` races bots offline, one at your recent average by default
+        Some("bots") => {
+            for arg in args.by_ref() {
+                bots.push(match arg.as_str() {
+                    "average" => None,
+                    wpm => Some(wpm.parse::<f32>().map_err(|_| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "usage: wpm bots [<wpm>|average]...",
+                        )
+                    })?),
+                });
+            }
+            if bots.is_empty() {
+                bots.push(None);
             }
         }
-    }
-
-    fn handle_key_event(&mut self, key_event: KeyEvent) {
-        match key_event.code {
-            KeyCode::Esc => {
-                self.exit = true;
+        // `wpm challenge <code>` types the quote of a friend's result, to compare with it
+        Some("challenge") => {
+            let code: Vec<String> = args.by_ref().collect();
+            let invalid = || io::Error::new(io::ErrorKind::InvalidInput, "invalid challenge code");
+            let decoded = Challenge::decode(&code.concat()).ok_or_else(invalid)?;
+            Library::embedded()
+                .get(decoded.quote_id)
+                .ok_or_else(invalid)?;
+            challenge = Some(decoded);
+        }
+        // `wpm status [--format <format>]` prints a summary of the history, for status bars
+        Some("status") => {
+            let format = match (args.next().as_deref(), args.next()) {
+                (None, _) => status::DEFAULT_FORMAT.to_string(),
+                (Some("--format"), Some(format)) => format,
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "usage: wpm status [--format <format>]",
+                    ));
+                }
+            };
+            let records = history::load();
+            println!(
+                "{}",
+                status::summary(&format, &records, history::start_of_today())
+            );
+            return Ok(());
+        }
+        // `wpm gate [--wpm <wpm>] [--time <seconds>] [--max-attempts <n>]` for hooks, exits with
+        // an error unless the speed is reached in time
+        Some("gate") => {
+            // Nobody to type in CI or when piped, let the hook through
+            if std::env::var_os("CI").is_some() || !io::stdout().is_terminal() {
+                eprintln!("wpm gate: not in a terminal, skipped");
+                return Ok(());
             }
-            KeyCode::Left => {
-                if self.typing.len() != 0 || self.current_line != 0 {
-                    return;
+            gate = Some(parse_gate(&args.by_ref().collect::<Vec<_>>())?);
+        }
+        // `wpm export-card <result-id> --svg <file>` draws a result as a picture to share
+        Some("export-card") => return export_card(&args.collect::<Vec<_>>()),
+        // `wpm import-keybr <file>` keeps the letter stats of a keybr.com data export
+        Some("import-keybr") => {
+            let path = args.next().ok_or(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "usage: wpm import-keybr <file>",
+            ))?;
+            let mut letters = Letters::load();
+            let results = letters.import_keybr(&fs::read_to_string(path)?)?;
+            letters.save()?;
+
+            let weakest: String = letters
+                .weakest(LETTERS_TARGET_WPM)
+                .into_iter()
+                .take(5)
+                .collect();
+            println!("Imported {results} results, weakest letters: {weakest}");
+            return Ok(());
+        }
+        // `wpm mistakes` prints the kinds of mistakes of the last tests next to those of all time
+        Some("mistakes") => {
+            let records = history::load();
+            let sum = |records: &[history::Record]| {
+                let mut sum = Mistakes::default();
+                for record in records {
+                    sum.merge(&record.mistakes);
                 }
+                sum
+            };
+            let (recent, all) = (
+                sum(&records[records.len().saturating_sub(RECENT_TESTS)..]),
+                sum(&records),
+            );
+            if all.is_empty() {
+                println!("No mistakes recorded yet");
+                return Ok(());
+            }
 
-                if self.selected_group == 0 {
-                    self.selected_group = self.groups.len().saturating_sub(1);
-                } else {
-                    self.selected_group -= 1;
-                }
-                self.new_quote();
+            let locale = Locale::new(&config.locale);
+            let last = format!("Last {RECENT_TESTS}");
+            println!("{:<16}{:>10}{:>10}", "Mistake", last, "All time");
+            for kind in Kind::ALL {
+                println!(
+                    "{:<16}{:>10}{:>10}",
+                    kind.to_string(),
+                    locale.percent(recent.share(kind), 0),
+                    locale.percent(all.share(kind), 0),
+                );
             }
-            KeyCode::Right => {
-                if self.typing.len() != 0 || self.current_line != 0 {
-                    return;
+            if let Some((kind, _)) = recent.kinds().first() {
+                println!(
+                    "\n{}",
+                    match kind {
+                        Kind::Adjacent =>
+                            "Mostly keys next to the right one: work on finger placement, like with wpm drill",
+                        Kind::Transposition =>
+                            "Mostly swapped keys: work on rhythm, a steady pace beats bursts",
+                        Kind::Omission | Kind::Insertion =>
+                            "Mostly keys left out or doubled: slow down and aim for accuracy",
+                        Kind::Other => "Mostly wrong keys: slow down and aim for accuracy",
+                    }
+                );
+            }
+            return Ok(());
+        }
+        // `wpm stats` compares this week and month to the ones before
+        Some("stats") => {
+            let records = history::load();
+            if records.is_empty() {
+                println!("No results yet, type a test first");
+                return Ok(());
+            }
+            let locale = Locale::new(&config.locale);
+            let now = review::now();
+            print_comparison(
+                &history::compare(&records, now, WEEK),
+                ("This week", "Last week"),
+                &locale,
+            );
+            println!();
+            print_comparison(
+                &history::compare(&records, now, MONTH),
+                ("This month", "Last month"),
+                &locale,
+            );
+            let categories = history::categories(&records);
+            if !categories.is_empty() {
+                println!();
+                print_categories(&categories, &history::Period::of(&records), &locale);
+            }
+            return Ok(());
+        }
+        // `wpm accuracy [<mistakes>]` fails tests once they have more mistakes than that, fixed
+        // or not
+        Some("accuracy") => {
+            let budget = match args.next() {
+                Some(budget) => budget.parse().map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "usage: wpm accuracy [<mistakes>]",
+                    )
+                })?,
+                None => ERROR_BUDGET,
+            };
+            error_budget = Some(budget);
+        }
+        // `wpm endurance [<minutes>|log]` types continuous text for 10 to 30 minutes and plots
+        // the speed of each one, `wpm endurance log` lists the runs so far
+        Some("endurance") => {
+            let minutes = match args.next().as_deref() {
+                Some("log") => {
+                    print_runs(&endurance::load(), &Locale::new(&config.locale));
+                    return Ok(());
                 }
+                Some(minutes) => minutes
+                    .parse()
+                    .ok()
+                    .filter(|minutes| endurance::MINUTES.contains(minutes))
+                    .ok_or(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!(
+                            "usage: wpm endurance [<{}-{} minutes>|log]",
+                            endurance::MINUTES.start(),
+                            endurance::MINUTES.end()
+                        ),
+                    ))?,
+                None => endurance::DEFAULT_MINUTES,
+            };
+            endurance_run = Some(minutes);
+        }
+        // `wpm rhythm` shows how evenly keys come while typing, and scores it at the end
+        Some("rhythm") => rhythm = true,
+        // `wpm session` works through the practice plan of the config, test after test
+        Some("session") => {
+            if config.session.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "no tests in the session of the config",
+                ));
+            }
+            session = true;
+        }
+        // `wpm fingers` prints the load, errors and speed of each finger
+        Some("fingers") => {
+            let layout = config.typing_layout();
+            let stats = keyboard::finger_stats(&Letters::load(), layout, &config.fingering);
+            let keystrokes: u32 = stats.iter().map(|s| s.keystrokes).sum();
+            if keystrokes == 0 {
+                println!("No letter stats yet, type a test or run wpm import-keybr");
+                return Ok(());
+            }
 
-                if self.selected_group + 1 >= self.groups.len() {
-                    self.selected_group = 0;
-                } else {
-                    self.selected_group += 1;
-                }
-                self.new_quote();
+            println!("{keystrokes} keystrokes on {layout}\n");
+            let locale = Locale::new(&config.locale);
+            println!("{:<14}{:>8}{:>8}{:>8}", "Finger", "Load", "Errors", "Time");
+            for finger in stats {
+                println!(
+                    "{:<14}{:>8}{:>8}{:>5.0} ms",
+                    finger.to_string(),
+                    locale.percent(finger.load, 1),
+                    locale.percent(finger.error_rate(), 1),
+                    finger.time_ms,
+                );
             }
-            KeyCode::Tab => {
-                if self.typing.len() == 0 && self.current_line == 0 {
-                    return self.new_quote();
+            return Ok(());
+        }
+        // `wpm drill [left|right] [top|home|bottom]... [pinky|ring|middle|index]...` types words
+        // made of only those keys, like `wpm drill left home`
+        Some("drill") => {
+            let mut filter = KeyFilter::default();
+            for word in args.by_ref() {
+                if !filter.add(&word) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "usage: wpm drill [left|right] [top|home|bottom]... [pinky|ring|middle|index]...",
+                    ));
                 }
-
-                if self.done.is_some() {
-                    self.correct = 0;
-                    self.incorrect = 0;
-                    self.words = 0;
-
-                    self.current_line = 0;
-                    self.typing = Vec::with_capacity(MAX_LENGTH_PER_LINE);
-                    self.typed = Vec::new();
-
-                    self.done = None;
-
-                    self.new_quote();
+            }
+            if filter
+                .letters(config.typing_layout(), &config.fingering)
+                .is_empty()
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("no letter of the keyboard is typed with the {filter}"),
+                ));
+            }
+            drill = Some(filter);
+        }
+        // `wpm practice` types words with the letters and pairs that need it most, from the
+        // letter stats
+        Some("practice") => practice = true,
+        // `wpm review` types the missed words that are due for review
+        Some("review") => {
+            let queue = review::Queue::load();
+            let now = review::now();
+            if queue.due(now).is_empty() {
+                match queue.next_due() {
+                    Some(due) => println!(
+                        "Nothing to review, {} words in the queue, the next one in {} hours",
+                        queue.len(),
+                        (due - now).div_ceil(3600)
+                    ),
+                    None => println!("Nothing to review, words missed in tests will show up here"),
                 }
+                return Ok(());
             }
-            KeyCode::Char(char) => {
-                if self.typing.len() == 0 && self.current_line == 0 {
-                    self.start = SystemTime::now()
+            review = Some(queue);
+        }
+        // `wpm drills [build|run] <name> [<version>]` builds a drill out of the words and pairs of
+        // letters missed the most and keeps it by name, runs one, or lists them all
+        Some("drills") => {
+            let usage = || {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "usage: wpm drills [build <name> | run <name> [<version>]]",
+                )
+            };
+            let mut drills = Drills::load();
+            match (args.next().as_deref(), args.next()) {
+                (None, _) => {
+                    print_drills(&drills);
+                    return Ok(());
                 }
-
-                let part = &self.sentence[self.current_line];
-
-                if char.is_whitespace() {
-                    if part.chars().nth(self.typing.len()) != Some(' ') {
-                        return;
+                (Some("build"), Some(name)) => {
+                    let library = Library::embedded();
+                    let texts = library.quotes.iter().map(|quote| quote.text.as_str());
+                    let version = drills.build(
+                        &name,
+                        &review::Queue::load(),
+                        &Letters::load(),
+                        LETTERS_TARGET_WPM,
+                        texts,
+                        review::now(),
+                    );
+                    if version.is_empty() {
+                        println!("Nothing to drill yet, type a few tests first");
+                        return Ok(());
                     }
-
-                    self.words += 1;
+                    println!("{name} v{}", version.number);
+                    println!("  words: {}", version.words.join(" "));
+                    println!("  pairs: {}", version.bigrams.join(" "));
+                    drills.save()?;
+                    println!("Run it with wpm drills run \"{name}\"");
+                    return Ok(());
                 }
-                if !char.is_whitespace() && part.chars().nth(self.typing.len()) == Some(' ') {
-                    return;
+                (Some("run"), Some(name)) => {
+                    let number = match args.next() {
+                        Some(number) => Some(
+                            number
+                                .trim_start_matches('v')
+                                .parse()
+                                .map_err(|_| usage())?,
+                        ),
+                        None => None,
+                    };
+                    let version = drills.get(&name, number).cloned().ok_or(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("no such drill, build it with wpm drills build \"{name}\""),
+                    ))?;
+                    custom = Some((name, version));
                 }
-
-                self.typing.push(char);
-
-                if part.len() == self.typing.len() {
-                    self.count_mistakes();
-
-                    self.typed.push(self.typing.iter().collect::<String>());
-
-                    self.typing = Vec::with_capacity(MAX_LENGTH_PER_LINE);
-                    self.current_line += 1;
-
-                    if self.current_line + 1 > self.sentence.len() {
-                        return self.done = Some(SystemTime::now());
+                _ => return Err(usage()),
+            }
+        }
+        // `wpm course [list|<lesson>]` takes the next lesson of the course, or the one asked
+        // for once unlocked, `wpm course list` prints them all with the progress on each
+        Some("course") => {
+            let progress = course::Progress::load();
+            let index = match args.next().as_deref() {
+                Some("list") => {
+                    print_course(&progress);
+                    return Ok(());
+                }
+                Some(number) => {
+                    let index = number
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|number| number.checked_sub(1))
+                        .filter(|index| *index < LESSONS.len())
+                        .ok_or(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("usage: wpm course [list|<1-{}>]", LESSONS.len()),
+                        ))?;
+                    if !progress.unlocked(index) {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("lesson {number} is locked, pass the ones before it first"),
+                        ));
                     }
+                    index
                 }
-            }
-            KeyCode::Backspace => {
-                let char = self.typing.pop();
-                if let Some(is) = char {
-                    if is.is_whitespace() {
-                        self.words = self.words.saturating_sub(1);
+                None => match progress.next() {
+                    Some(index) => index,
+                    None => {
+                        let done = "Course complete! Run wpm course <lesson> to take one again";
+                        tell(done, json);
+                        return Ok(());
                     }
+                },
+            };
+            lesson = Some((progress, index));
+        }
+        // `wpm assignment do <file>` types a teacher's assignment, `wpm assignment grade <file>
+        // <results>...` sums up what the students sent back, `wpm assignment key` prints the key
+        // a student gives the teacher
+        Some("assignment") => {
+            let usage = || {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "usage: wpm assignment key | do <file> | grade <file> <results>...",
+                )
+            };
+            let action = args.next();
+            if action.as_deref() == Some("key") {
+                println!("{}", StudentKey::load()?.public());
+                return Ok(());
+            }
+            let path = args.next().ok_or_else(usage)?;
+            let assignment = load_assignment(Path::new(&path))?;
+            match action.as_deref() {
+                Some("do") => {
+                    let key = StudentKey::load()?;
+                    homework = Some((Homework::new(assignment, config.player_name()), (path, key)));
                 }
+                Some("grade") => return grade(&assignment, &args.collect::<Vec<_>>(), &config),
+                _ => return Err(usage()),
             }
-            _ => {}
         }
+        // `wpm daemon [--once|--systemd]` reminds of the daily goal at the times of the config
+        Some("daemon") => {
+            return match args.next().as_deref() {
+                None => daemon::run(&config),
+                Some("--once") => daemon::remind(&config),
+                Some("--systemd") => {
+                    let dir = daemon::install_systemd(&config)?;
+                    println!(
+                        "Wrote wpm-reminder.timer and wpm-reminder.service to {}",
+                        dir.display()
+                    );
+                    println!("Turn them on with: systemctl --user enable --now wpm-reminder.timer");
+                    Ok(())
+                }
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "usage: wpm daemon [--once|--systemd]",
+                )),
+            };
+        }
+        // `wpm sync` shares results with the leaderboard server, and fetches everyone's
+        Some("sync") => return sync(&config),
+        // `wpm serve [--port <port>]` runs a race server, with no game of its own
+        Some("serve") => {
+            let port = match (args.next().as_deref(), args.next()) {
+                (None, _) => race::DEFAULT_PORT,
+                (Some("--port"), Some(port)) => port
+                    .parse()
+                    .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "invalid port"))?,
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "usage: wpm serve [--port <port>]",
+                    ));
+                }
+            };
+            return serve(&config, port);
+        }
+        _ => {}
     }
 
-    fn new_quote(&mut self) {
-        let data: EnglishData =
-            serde_json::from_str(ENGLISH_JSON).expect("Failed to parse english.json");
-        let mut rng = rng();
-
-        self.groups = data.groups.clone();
+    // Before taking over the terminal, so failures are reported plainly
+    let sources = load_quote_sources(&config, offline)?;
+    let telemetry = match config.telemetry_port {
+        0 => None,
+        port => Some(Telemetry::serve(port)?),
+    };
+    if config.metrics_port != 0 {
+        metrics::serve(config.metrics_port, history::load)?;
+    }
 
-        if self.selected_group > data.groups.len() {
-            self.selected_group = 0;
-        }
+    let mut terminal = ratatui::init();
+    // Pastes and IME commits arrive as a single event instead of keystrokes
+    execute!(io::stdout(), EnableMouseCapture, EnableBracketedPaste)?;
+
+    // Lets terminals that support it tell Ctrl+Backspace and Esc apart from other keys
+    let enhanced = supports_keyboard_enhancement().unwrap_or(false);
+    if enhanced {
+        execute!(
+            io::stdout(),
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+        )?;
+    }
 
-        let group = data.groups[self.selected_group];
-        let valid_quotes: Vec<&Quote> = data
-            .quotes
-            .iter()
-            .filter(|q| group[0] < q.length && q.length < group[1])
-            .collect();
-
-        let picked = valid_quotes
-            .choose(&mut rng)
-            .expect("Could not pick a quote");
-
-        self.sentence_source = picked.source.clone();
-        self.sentence = Vec::new();
-
-        for word in picked.text.split(" ") {
-            if let Some(l) = self.sentence.last_mut() {
-                if l.len() + 1 + word.len() > MAX_LENGTH_PER_LINE {
-                    self.sentence.push(word.to_string());
-                } else {
-                    l.push_str(" ");
-                    l.push_str(word);
-                }
-            } else {
-                self.sentence.push(word.to_string());
+    let mut app = App::new(config);
+    for source in sources {
+        app.content.register(source);
+    }
+    if !app.config.quote_source.is_empty() {
+        app.content.select(&app.config.quote_source);
+    }
+    app.race = race;
+    app.telemetry = telemetry;
+    app.error_budget = error_budget;
+    app.rhythm = rhythm;
+    if let Some(gate) = gate {
+        app.gate = Some(gate);
+        // Long quotes, so the time runs out before the text
+        app.selected_group = app.groups().len().saturating_sub(1);
+    }
+    let average = history::recent_average(&app.history, BOT_AVERAGE_OF).unwrap_or(BOT_DEFAULT_WPM);
+    app.bots = bots
+        .into_iter()
+        .map(|wpm| {
+            let wpm = wpm.unwrap_or(average);
+            Bot::new(format!("bot {wpm:.0}"), wpm)
+        })
+        .collect();
+
+    let (homework, submission) = homework.unzip();
+    match (replay, book, challenge, ghost) {
+        (Some(replay), _, _, _) => app.load_replay(replay),
+        (_, Some(book), _, _) => app.open_book(book),
+        (_, _, Some(challenge), _) => app.accept_challenge(challenge),
+        (_, _, _, Some((bot, replay))) => app.race_ghost(bot, replay),
+        _ => match (homework, drill, lesson, review, custom, endurance_run) {
+            (Some(homework), _, _, _, _, _) => app.start_homework(homework),
+            (_, Some(filter), _, _, _, _) => app.start_drill(&filter),
+            (_, _, Some((progress, index)), _, _, _) => app.start_lesson(progress, index),
+            (_, _, _, Some(queue), _, _) => app.start_review(queue),
+            (_, _, _, _, Some((name, version)), _) => app.start_custom(&name, version),
+            (_, _, _, _, _, Some(minutes)) => app.start_endurance(minutes, endurance::load()),
+            _ if practice => app.start_practice(Letters::load(), LETTERS_TARGET_WPM),
+            _ if session => {
+                let session = Session::new(&app.config.session, app.selected_group);
+                app.start_session(session);
             }
-        }
+            _ => app.new_quote(),
+        },
+    }
 
-        for i in 0..self.sentence.len().saturating_sub(1) {
-            self.sentence[i].push_str(" ");
-        }
+    // Results of this run are the ones pushed after those loaded
+    let loaded = (app.history.len(), app.endurance_runs.len());
+    let app_result = app.run(&mut terminal, &mut TerminalInput);
+    if enhanced {
+        execute!(io::stdout(), PopKeyboardEnhancementFlags)?;
     }
+    execute!(io::stdout(), DisableBracketedPaste, DisableMouseCapture)?;
+    ratatui::restore();
+
+    app_result?;
+    if let (Some(homework), Some((path, key))) = (&app.homework, submission) {
+        let results = save_homework(homework, Path::new(&path), &key)?;
+        let saved = format!(
+            "Results saved to {}, send it to your teacher",
+            results.display()
+        );
+        tell(&saved, json);
+    }
+    if json {
+        let outcome = Outcome {
+            results: &app.history[loaded.0..],
+            runs: &app.endurance_runs[loaded.1..],
+            session: app.session.as_ref().map(Session::report),
+            gate_passed: app.gate.map(|gate| gate.passed),
+        };
+        println!("{}", serde_json::to_string(&outcome)?);
+    } else if let Some(session) = &app.session {
+        print_session(session, &Locale::new(&app.config.locale));
+    }
+    if app.gate.is_some_and(|gate| !gate.passed) {
+        // Exiting skips destructors, the sinks write what they hold when dropped
+        drop(app);
+        std::process::exit(1);
+    }
+    Ok(())
 }
 
-impl Widget for &App {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        if let Some(end) = self.done {
-            let title = Line::from(" Typing Test Completed ".bold().green());
-
-            let duration = end
-                .duration_since(self.start)
-                .unwrap_or(Duration::from_secs(0));
-            let seconds = duration.as_secs_f32();
-            let accuracy = if self.correct + self.incorrect > 0 {
-                (self.correct as f32 / (self.correct + self.incorrect) as f32) * 100.
-            } else {
-                0.
-            };
+/// Prints a message for the user, to stderr under `--json` to leave stdout to the JSON.
+fn tell(message: &str, json: bool) {
+    if json {
+        eprintln!("{message}");
+    } else {
+        println!("{message}");
+    }
+}
 
-            let block = Block::bordered()
-                .title(title.centered())
-                .title_bottom(
-                    Line::from(vec![
-                        " Press ".into(),
-                        "<ESC>".blue().bold(),
-                        " to exit or ".into(),
-                        "<TAB>".blue().bold(),
-                        " to try again".into(),
-                    ])
-                    .centered(),
-                )
-                .border_set(border::ROUNDED);
-
-            let inner = block.inner(area);
-            block.render(area, buf);
-
-            let rows = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([Constraint::Max(2), Constraint::Min(1)])
-                .split(inner);
-
-            let stats = vec![
-                Line::from(vec![
-                    "WPM: ".blue().bold(),
-                    get_wpm(self.words, self.start, end).green().bold(),
-                ])
-                .centered(),
-                Line::from(""),
-                Line::from(vec![
-                    "Time: ".blue().bold(),
-                    format!("{:.1}s", seconds).white(),
-                ])
-                .centered(),
-                Line::from(vec![
-                    "Words: ".blue().bold(),
-                    format!("{}", self.words).white(),
-                ])
-                .centered(),
-                Line::from(""),
-                Line::from(vec![
-                    "Accuracy: ".blue().bold(),
-                    format!("{:.1}%", accuracy).white(),
-                ])
-                .centered(),
-                Line::from(vec![
-                    "Correct: ".green().bold(),
-                    format!("{}", self.correct).white(),
-                    "  |  ".into(),
-                    "Incorrect: ".red().bold(),
-                    format!("{}", self.incorrect).white(),
-                ])
-                .centered(),
-            ];
-
-            Paragraph::new(stats).render(rows[1], buf);
-
-            return;
-        }
+/// What `--json` prints after quitting, for scripts wrapping the app.
+#[derive(Debug, Serialize)]
+struct Outcome<'a> {
+    /// Valid results typed, in order
+    results: &'a [history::Record],
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    runs: &'a [endurance::Run],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session: Option<session::Report>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gate_passed: Option<bool>,
+}
 
-        let title = Line::from(" Typing Test ".bold());
-        let instructions = Line::from(vec![
-            " Start typing to ".into(),
-            "<start>".blue().bold(),
-            " Change quote length ".into(),
-            "← →".blue().bold(),
-            " New quote ".into(),
-            "<TAB>".blue().bold(),
-            " Quit ".into(),
-            "<ESC> ".blue().bold(),
-        ]);
-        let block = Block::bordered()
-            .title(title.centered())
-            .title_bottom(instructions.centered())
-            .border_set(border::THICK);
-
-        let inner = block.inner(area);
-        block.render(area, buf);
-
-        let rows = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(2), // length selection + blank
-                Constraint::Min(1),    // quote
-                Constraint::Length(4), // blank + WPM + blank + source
-            ])
-            .split(inner);
-
-        // ROW 1: Length selection || previous text if typing
-        if self.typing.len() == 0 && self.current_line == 0 {
-            let mut length_spans: Vec<Span> = Vec::with_capacity(1 + self.groups.len());
-            length_spans.push("  Length: ".blue().bold());
-
-            for gid in 0..self.groups.len() {
-                length_spans.push(if self.selected_group == gid {
-                    format!(" {}-{} ", self.groups[gid][0], self.groups[gid][1])
-                        .underlined()
-                        .bold()
-                        .green()
-                } else {
-                    format!(" {}-{} ", self.groups[gid][0], self.groups[gid][1]).into()
+/// A replay file given on the command line, the latest one saved otherwise.
+fn replay_path(arg: Option<String>) -> io::Result<PathBuf> {
+    match arg {
+        Some(path) => Ok(PathBuf::from(path)),
+        None => replay::latest().ok_or(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no replay saved yet, turn on record_replays in the config",
+        )),
+    }
+}
+
+/// Prints the text of a replay with its mistakes marked inline: in colors in a terminal, and
+/// in CriticMarkup otherwise, each wrong run typed as a substitution, skipped graphemes as
+/// additions, extra ones as deletions and fixed ones highlighted, with what was typed first.
+fn print_diff(lines: &[Vec<diff::Cell>], color: bool) {
+    let (mut wrong, mut fixed, mut skipped, mut extra) = (0, 0, 0, 0);
+    for line in lines {
+        let mut out = String::new();
+        // Runs of the same mark, each fixed grapheme on its own to tell its attempts apart
+        let runs = line.chunk_by(|a, b| {
+            std::mem::discriminant(&a.mark) == std::mem::discriminant(&b.mark)
+                && !matches!(a.mark, Mark::Fixed(_))
+        });
+        for run in runs {
+            let expected: String = run.iter().map(|cell| cell.expected.as_str()).collect();
+            let typed: String = run
+                .iter()
+                .filter_map(|cell| match &cell.mark {
+                    Mark::Wrong(typed) | Mark::Extra(typed) => Some(typed.as_str()),
+                    _ => None,
                 })
+                .collect();
+            match &run[0].mark {
+                Mark::Wrong(_) => wrong += run.len(),
+                Mark::Skipped => skipped += run.len(),
+                Mark::Extra(_) => extra += typed.chars().count(),
+                Mark::Fixed(_) => fixed += 1,
+                Mark::Right | Mark::Untyped => {}
             }
-
-            let length_text = Line::from(length_spans);
-            Paragraph::new(length_text).render(rows[0], buf);
-        } else {
-            let mut lines: Vec<Line> = Vec::with_capacity(2);
-
-            if self.current_line >= 2 {
-                let mut spans: Vec<Span> = Vec::new();
-                for (cid, c) in self.sentence[self.current_line - 2].chars().enumerate() {
-                    if cid < self.typed[self.current_line - 2].len() {
-                        let typed_char = self.typed[self.current_line - 2]
-                            .chars()
-                            .nth(cid)
-                            .unwrap_or(' ');
-                        if c == typed_char {
-                            spans.push(c.to_string().gray().into());
-                        } else {
-                            spans.push(c.to_string().red().bold().into());
-                        }
-                    } else {
-                        spans.push(c.to_string().gray().into());
-                    }
+            out += &match (&run[0].mark, color) {
+                (Mark::Right, _) | (Mark::Untyped, false) => expected,
+                (Mark::Untyped, true) => expected.dark_grey().to_string(),
+                (Mark::Wrong(_), true) => {
+                    format!("{}{}", typed.red().crossed_out(), expected.green())
                 }
-                lines.push(Line::from(spans).centered())
-            }
-            if self.current_line >= 1 {
-                let mut spans: Vec<Span> = Vec::new();
-                for (cid, c) in self.sentence[self.current_line - 1].chars().enumerate() {
-                    if cid < self.typed[self.current_line - 1].len() {
-                        let typed_char = self.typed[self.current_line - 1]
-                            .chars()
-                            .nth(cid)
-                            .unwrap_or(' ');
-                        if c == typed_char {
-                            spans.push(c.to_string().gray().into());
-                        } else {
-                            spans.push(c.to_string().red().bold().into());
-                        }
-                    } else {
-                        spans.push(c.to_string().gray().into());
-                    }
+                (Mark::Wrong(_), false) => format!("{{~~{typed}~>{expected}~~}}"),
+                (Mark::Skipped, true) => expected.green().underlined().to_string(),
+                (Mark::Skipped, false) => format!("{{++{expected}++}}"),
+                (Mark::Extra(_), true) => typed.red().crossed_out().to_string(),
+                (Mark::Extra(_), false) => format!("{{--{typed}--}}"),
+                (Mark::Fixed(_), true) => expected.yellow().to_string(),
+                (Mark::Fixed(attempts), false) => {
+                    format!("{{=={expected}==}}{{>>{}<<}}", attempts.join(", "))
                 }
-                lines.push(Line::from(spans).centered())
-            }
+            };
+        }
+        println!("{out}");
+    }
 
-            if lines.len() == 1 {
-                lines.insert(0, Line::from(""))
-            }
+    let counts = [
+        (format!("{wrong} left wrong"), wrong),
+        (format!("{fixed} fixed"), fixed),
+        (format!("{skipped} skipped"), skipped),
+        (format!("{extra} extra"), extra),
+    ];
+    let counts: Vec<String> = counts
+        .into_iter()
+        .zip([
+            Stylize::red,
+            Stylize::yellow,
+            Stylize::green,
+            Stylize::crossed_out,
+        ])
+        .map(|((text, count), style)| match color && count > 0 {
+            true => style(text).to_string(),
+            false => text,
+        })
+        .collect();
+    println!("\n{}", counts.join(", "));
+}
+
+/// Prints each test of the session typed so far, and all of them together.
+fn print_session(session: &Session, locale: &Locale) {
+    if session.outcomes.is_empty() {
+        return;
+    }
+    for (i, (test, outcome)) in session.tests.iter().zip(&session.outcomes).enumerate() {
+        println!(
+            "{:>2}. {:<32}{:>5.0} wpm{:>8}{}",
+            i + 1,
+            test.to_string(),
+            outcome.wpm,
+            locale.percent(outcome.accuracy, 1),
+            if outcome.counted { "" } else { "  not counted" },
+        );
+    }
+    let report = session.report();
+    println!(
+        "Session: {} of {} tests, {:.0} wpm, {} accuracy, {}",
+        report.tests,
+        session.tests.len(),
+        report.wpm,
+        locale.percent(report.accuracy, 1),
+        locale.seconds(report.seconds, 0),
+    );
+}
 
-            Paragraph::new(Text::from(lines).centered()).render(rows[0], buf);
+fn load_assignment(path: &Path) -> io::Result<Assignment> {
+    toml::from_str(&fs::read_to_string(path)?)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))
+}
+
+/// Writes the signed results of an assignment next to where it is run, named after it and
+/// the student.
+fn save_homework(homework: &Homework, assignment: &Path, key: &StudentKey) -> io::Result<PathBuf> {
+    let stem = assignment.file_stem().unwrap_or_default().to_string_lossy();
+    let student: String = homework
+        .submission
+        .student
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    let path = PathBuf::from(format!("{stem}-{student}.json"));
+    homework.signed(key).save(&path)?;
+    Ok(path)
+}
+
+/// Prints how each student did on the assignment, from the results files they sent.
+fn grade(assignment: &Assignment, results: &[String], config: &Config) -> io::Result<()> {
+    if results.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "usage: wpm assignment grade <file> <results>...",
+        ));
+    }
+
+    let locale = Locale::new(&config.locale);
+    println!(
+        "{}: {} texts, {} accuracy to pass",
+        assignment.title,
+        assignment.texts.len(),
+        locale.percent(assignment.min_accuracy, 0)
+    );
+    for path in results {
+        let submission = Submission::load(Path::new(path))?;
+        let grade = assignment::grade(assignment, &submission);
+        println!(
+            "\n{}: {} of {} texts passed",
+            grade.student,
+            grade.passed(),
+            assignment.texts.len()
+        );
+        for (i, (score, attempts)) in grade.scores.iter().zip(&grade.attempts).enumerate() {
+            match score {
+                Some(score) => println!(
+                    "  text {}: {:.0} wpm, {} accuracy, {} in {attempts} attempts",
+                    i + 1,
+                    score.wpm,
+                    locale.percent(score.accuracy, 1),
+                    if score.passed { "passed" } else { "failed" },
+                ),
+                None => println!("  text {}: not done", i + 1),
+            }
         }
+        for problem in &grade.problems {
+            println!("  ! {problem}");
+        }
+    }
+    Ok(())
+}
 
-        // Row 2: Quote text (centered)
-        let mut quote_spans: Vec<Span> = Vec::with_capacity(self.typing.len() + 1);
-        let mut correct = 0u32;
-        let mut incorrect = 0u32;
-
-        for cid in 0..self.typing.len() {
-            if self.typing[cid]
-                == self.sentence[self.current_line]
-                    .chars()
-                    .nth(cid)
-                    .unwrap_or(' ')
-            {
-                quote_spans.push(self.typing[cid].to_string().into());
-                correct += 1;
-            } else {
-                quote_spans.push(
-                    self.sentence[self.current_line]
-                        .chars()
-                        .nth(cid)
-                        .unwrap_or(' ')
-                        .to_string()
-                        .on_red(),
-                );
-                incorrect += 1;
+/// Every quote pack in the config that could be loaded. The one picked to type has to be.
+fn load_quote_sources(config: &Config, offline: bool) -> io::Result<Vec<Remote>> {
+    let mut sources = Vec::new();
+    for source in &config.quote_sources {
+        match Remote::load(&source.name, &source.url, offline) {
+            Ok(remote) => sources.push(remote),
+            Err(error) if source.name == config.quote_source => {
+                return Err(io::Error::new(
+                    error.kind(),
+                    format!("quote pack {}: {error}", source.name),
+                ));
             }
+            Err(_) => {}
         }
+    }
+    Ok(sources)
+}
 
-        quote_spans.push(self.sentence[self.current_line][self.typing.len()..].gray());
+/// Reads the options of `wpm gate`, each with a default.
+fn parse_gate(args: &[String]) -> io::Result<Gate> {
+    let usage = || {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "usage: wpm gate [--wpm <wpm>] [--time <seconds>] [--max-attempts <n>]",
+        )
+    };
 
-        let active = Line::from(quote_spans);
+    let mut gate = Gate::new(GATE_DEFAULT_WPM, GATE_DEFAULT_TIME, GATE_DEFAULT_ATTEMPTS);
+    for pair in args.chunks(2) {
+        let [option, value] = pair else {
+            return Err(usage());
+        };
+        match option.as_str() {
+            "--wpm" => gate.wpm = value.parse().map_err(|_| usage())?,
+            "--time" => gate.time = Duration::from_secs(value.parse().map_err(|_| usage())?),
+            "--max-attempts" => gate.max_attempts = value.parse().map_err(|_| usage())?,
+            _ => return Err(usage()),
+        }
+    }
+    Ok(gate)
+}
 
-        let mut all: Vec<Line> =
-            Vec::with_capacity(self.sentence.len().saturating_sub(self.current_line) + 1);
-        all.push(active);
+/// Prints a stretch of days next to the one before, and how much better or worse it went.
+fn print_comparison(comparison: &history::Comparison, labels: (&str, &str), locale: &Locale) {
+    let (current, previous) = (comparison.current, comparison.previous);
+    let number = |value: Option<f32>| value.map_or("-".to_string(), |value| format!("{value:.0}"));
+    let percent =
+        |value: Option<f32>| value.map_or("-".to_string(), |value| locale.percent(value, 1));
+    let signed = |value: Option<f32>, text: &dyn Fn(Option<f32>) -> String| match value {
+        Some(value) if value > 0. => format!("+{}", text(Some(value))),
+        value => text(value),
+    };
 
-        for k in (self.current_line + 1)..self.sentence.len() {
-            all.push(Line::from(Span::from(self.sentence[k].clone().gray())))
-        }
+    println!("{:<12}{:>12}{:>12}{:>10}", "", labels.0, labels.1, "Change");
+    println!(
+        "{:<12}{:>12}{:>12}{:>10}",
+        "WPM",
+        number(current.wpm),
+        number(previous.wpm),
+        signed(comparison.wpm(), &number),
+    );
+    println!(
+        "{:<12}{:>12}{:>12}{:>10}",
+        "Accuracy",
+        percent(current.accuracy),
+        percent(previous.accuracy),
+        signed(comparison.accuracy(), &percent),
+    );
+    println!(
+        "{:<12}{:>12}{:>12}{:>10}",
+        "Tests",
+        current.tests,
+        previous.tests,
+        signed(Some(comparison.tests() as f32), &number),
+    );
+    println!(
+        "{:<12}{:>12}{:>12}{:>10}",
+        "Minutes",
+        number(Some(current.minutes)),
+        number(Some(previous.minutes)),
+        signed(Some(comparison.minutes()), &number),
+    );
+    println!(
+        "{:<12}{:>12}{:>12}{:>10}",
+        "Clean streak",
+        current.clean_streak,
+        previous.clean_streak,
+        signed(Some(comparison.clean_streak() as f32), &number),
+    );
+}
 
-        Paragraph::new(all).centered().render(rows[1], buf);
-
-        // Row 3: blank + WPM and stats + blank + source
-        let wpm_text = Line::from(vec![
-            "WPM: ".blue().bold(),
-            get_wpm(self.words, self.start, SystemTime::now()).into(),
-            "  |  ".into(),
-            "Accuracy: ".blue().bold(),
-            (self.correct + correct).to_string().green().bold(),
-            " - ".into(),
-            (self.incorrect + incorrect).to_string().red().bold(),
-        ])
-        .centered()
-        .bold();
-        Paragraph::new(vec![
-            Line::from(""),
-            wpm_text,
-            Line::from(""),
-            Line::from(vec![
-                "  Source: ".blue().bold(),
-                self.sentence_source.clone().italic(),
-                " - Quotes provided by monkeytype.com".into(),
-            ]),
-        ])
-        .render(rows[2], buf);
+/// Prints the results of each category next to those of all of them.
+fn print_categories(
+    categories: &[(&str, history::Period)],
+    all: &history::Period,
+    locale: &Locale,
+) {
+    println!(
+        "{:<24}{:>8}{:>8}{:>8}{:>10}",
+        "Category", "Tests", "WPM", "vs all", "Accuracy"
+    );
+    for (category, period) in categories {
+        let (wpm, versus) = match (period.wpm, all.wpm) {
+            (Some(wpm), Some(all)) => (format!("{wpm:.0}"), format!("{:+.0}", wpm - all)),
+            _ => ("-".to_string(), "-".to_string()),
+        };
+        println!(
+            "{:<24}{:>8}{:>8}{:>8}{:>10}",
+            category,
+            period.tests,
+            wpm,
+            versus,
+            period
+                .accuracy
+                .map_or("-".to_string(), |accuracy| locale.percent(accuracy, 1)),
+        );
     }
 }
 
-fn get_wpm(words: u32, start: SystemTime, end: SystemTime) -> String {
-    let duration = end.duration_since(start).unwrap_or(Duration::from_secs(0));
+/// Lists the endurance runs so far, with how much each one slowed down.
+fn print_runs(runs: &[endurance::Run], locale: &Locale) {
+    if runs.is_empty() {
+        println!("No endurance runs yet, start one with wpm endurance");
+        return;
+    }
+    println!(
+        "{:<12}{:>8}{:>8}{:>10}{:>10}",
+        "Date", "Minutes", "WPM", "Accuracy", "Fatigue"
+    );
+    for run in runs {
+        println!(
+            "{:<12}{:>8}{:>8.0}{:>10}{:>10}",
+            history::date(run.timestamp),
+            run.minutes,
+            run.wpm,
+            locale.percent(run.accuracy, 1),
+            run.fatigue()
+                .map_or("-".to_string(), |change| locale.percent(change, 0)),
+        );
+    }
+}
 
-    let mut minutes = duration.as_secs_f32() / 60.;
-    if minutes == 0. {
-        minutes = 0.01;
+/// Lists the drills built so far, with their versions and what the latest one types.
+fn print_drills(drills: &Drills) {
+    let mut any = false;
+    for (name, versions) in drills.iter() {
+        let Some(latest) = versions.last() else {
+            continue;
+        };
+        any = true;
+        println!(
+            "{name}: v{}, built {}, {} words and {} pairs",
+            latest.number,
+            history::date(latest.created),
+            latest.words.len(),
+            latest.bigrams.len(),
+        );
+    }
+    if !any {
+        println!("No drills yet, build one with wpm drills build <name>");
     }
+}
 
-    let wpm = words as f32 / minutes;
-    let emoji = if wpm < 10. {
-        "🦥"
-    } else if wpm < 25. {
-        "🐌"
-    } else if wpm < 50. {
-        "🐢"
-    } else if wpm < 75. {
-        "🐇"
-    } else if wpm < 100. {
-        "🐆"
-    } else if wpm < 125. {
-        "🚄"
-    } else {
-        "⚡"
+/// Lists the lessons of the course, with the best result on each and which can be taken.
+fn print_course(progress: &course::Progress) {
+    for (i, lesson) in LESSONS.iter().enumerate() {
+        let done = progress.get(i);
+        let status = if done.passed {
+            "✓"
+        } else if progress.unlocked(i) {
+            "▶"
+        } else {
+            "🔒"
+        };
+        let best = match done.attempts {
+            0 => String::new(),
+            _ => format!(
+                "  best {:.0} wpm, {:.0}%",
+                done.best_wpm, done.best_accuracy
+            ),
+        };
+        println!(
+            "{status} {:>2}. {:<42} {:.0} wpm, {:.0}%{best}",
+            i + 1,
+            lesson.title,
+            lesson.wpm,
+            lesson.accuracy,
+        );
+    }
+}
+
+/// Writes the card of a result, numbered from 1 in the history or `last`. Its graph shows the
+/// speed over the test when it has a replay, and the results before it otherwise.
+fn export_card(args: &[String]) -> io::Result<()> {
+    let [id, flag, path] = args else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "usage: wpm export-card <result-id|last> --svg <file>",
+        ));
+    };
+    if flag != "--svg" {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "cards can only be exported as SVG, with --svg",
+        ));
+    }
+
+    let records = history::load();
+    let index = match id.as_str() {
+        "last" => records.len().checked_sub(1),
+        id => id.parse::<usize>().ok().and_then(|n| n.checked_sub(1)),
+    }
+    .filter(|&index| index < records.len())
+    .ok_or(io::Error::new(
+        io::ErrorKind::NotFound,
+        "no such result, they are numbered from 1 in the order they were typed",
+    ))?;
+    let record = &records[index];
+
+    let source = Library::embedded()
+        .get(record.quote_id)
+        .map_or("", |quote| quote.source.as_str());
+    let (points, label) = match replay::find(record) {
+        Some(replay) => (
+            Simulation::from_replay(&replay).test().samples().to_vec(),
+            "speed over the test".to_string(),
+        ),
+        None => {
+            let trend = &records[(index + 1).saturating_sub(CARD_TREND_OF)..=index];
+            let points = trend
+                .iter()
+                .enumerate()
+                .map(|(i, record)| (i as f64, record.wpm as f64))
+                .collect();
+            (points, format!("last {} results", trend.len()))
+        }
     };
 
-    format!("{emoji} {:>3}", wpm.round() as u32)
+    fs::write(path, card::svg(record, source, &points, &label))?;
+    println!("Saved the card of result {} to {path}", index + 1);
+    Ok(())
+}
+
+/// Pushes the results not synced yet, then prints the standings pulled back.
+fn sync(config: &Config) -> io::Result<()> {
+    let client = config.leaderboard().ok_or(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "no leaderboard server, set leaderboard_url in the config",
+    ))?;
+
+    let mut cache = Cache::load();
+    let pushed = client.push_new(&config.player_name(), &history::load(), &mut cache)?;
+    // Saved before pulling, so a failed pull doesn't get the results pushed again
+    cache.save()?;
+    cache.standings = client.pull()?;
+    cache.save()?;
+
+    println!("Pushed {pushed} new results");
+    for (rank, standing) in cache.standings.iter().enumerate() {
+        println!(
+            "{:>3}. {:<16} {:>5.0} wpm best, {:.0} average over {} tests",
+            rank + 1,
+            standing.name,
+            standing.best_wpm,
+            standing.average_wpm,
+            standing.tests
+        );
+    }
+    Ok(())
+}
+
+/// Hosts races on random quotes, typed with the rules of this config.
+fn serve(config: &Config, port: u16) -> io::Result<()> {
+    let library = Library::embedded();
+    let rules = config.rules();
+    let trailing_space = config.trailing_space;
+    let casing = config.casing;
+
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    println!("Race server listening on port {port}");
+    if config.metrics_port != 0 {
+        let addr = metrics::serve(config.metrics_port, history::load)?;
+        println!("Metrics at http://{addr}/metrics");
+    }
+    race::serve(listener, move || {
+        let quote = library.quotes.choose(&mut rand::rng())?;
+        let text = content::recase(&content::prepare(&quote.text, rules.punctuation), casing);
+        Some(race::Start {
+            quote_id: quote.id,
+            source: quote.source.clone(),
+            lines: content::wrap(&text, MAX_LENGTH_PER_LINE, trailing_space),
+            rules,
+        })
+    })
 }
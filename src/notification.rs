@@ -0,0 +1,40 @@
+use notify_rust::Notification;
+use std::thread::{self, JoinHandle};
+use wpm_core::{history::Record, replay::Replay, sink::StatsSink};
+
+/// Pops up a desktop notification with each result, for tests typed in a terminal out of sight.
+/// Dropping it waits for the notifications still being sent.
+#[derive(Debug, Default)]
+pub struct Desktop {
+    pending: Vec<JoinHandle<()>>,
+}
+
+impl StatsSink for Desktop {
+    fn record(&mut self, record: &Record, _replay: Option<&Replay>) {
+        let body = format!(
+            "{:.0} {}, {:.1}% accuracy in {:.0}s",
+            record.wpm,
+            record.unit(),
+            record.accuracy,
+            record.seconds
+        );
+
+        self.pending.retain(|handle| !handle.is_finished());
+        // Showing one waits on the notification daemon, failures have nowhere to go
+        self.pending.push(thread::spawn(move || {
+            let _ = Notification::new()
+                .appname("wpm")
+                .summary("Typing test completed")
+                .body(&body)
+                .show();
+        }));
+    }
+}
+
+impl Drop for Desktop {
+    fn drop(&mut self) {
+        for handle in self.pending.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, time::Duration};
+
+// What a step of the plan asks for when the config doesn't say
+const DEFAULT_MISTAKES: u32 = 3;
+const DEFAULT_SECONDS: u32 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepKind {
+    /// A quote that fails over a few mistakes, like `wpm accuracy`
+    Accuracy,
+    /// As much as can be typed in some seconds
+    Timed,
+    Quote,
+}
+
+/// A few tests of the same kind in a practice plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Step {
+    pub kind: StepKind,
+    /// Tests in a row
+    #[serde(default = "one")]
+    pub count: u32,
+    /// Mistakes allowed in accuracy tests, 3 when not given
+    #[serde(default)]
+    pub mistakes: Option<u32>,
+    /// Length of timed tests, a minute when not given
+    #[serde(default)]
+    pub seconds: Option<u32>,
+}
+
+fn one() -> u32 {
+    1
+}
+
+impl Step {
+    pub fn new(kind: StepKind, count: u32) -> Self {
+        Step {
+            kind,
+            count,
+            mistakes: None,
+            seconds: None,
+        }
+    }
+
+    fn test(&self) -> Test {
+        match self.kind {
+            StepKind::Accuracy => Test::Accuracy(self.mistakes.unwrap_or(DEFAULT_MISTAKES)),
+            StepKind::Timed => Test::Timed(Duration::from_secs(
+                self.seconds.unwrap_or(DEFAULT_SECONDS) as u64,
+            )),
+            StepKind::Quote => Test::Quote,
+        }
+    }
+}
+
+/// A test of a session, with what it asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Test {
+    /// Mistakes allowed
+    Accuracy(u32),
+    Timed(Duration),
+    Quote,
+}
+
+impl fmt::Display for Test {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Test::Accuracy(mistakes) => write!(f, "accuracy, {mistakes} mistakes allowed"),
+            Test::Timed(time) => write!(f, "timed {} s", time.as_secs()),
+            Test::Quote => write!(f, "quote"),
+        }
+    }
+}
+
+/// How a test of the session went.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Outcome {
+    pub wpm: f32,
+    pub accuracy: f32,
+    pub seconds: f32,
+    /// Results that can't be trusted, or over the error budget, don't count
+    pub counted: bool,
+}
+
+/// The tests of a session summed up, averages over those that counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Report {
+    pub tests: usize,
+    pub counted: usize,
+    pub wpm: f32,
+    pub accuracy: f32,
+    /// Typing time of every test
+    pub seconds: f32,
+}
+
+/// Working through the tests of the practice plan in order, for `wpm session`.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub tests: Vec<Test>,
+    /// Index of the test being typed
+    pub step: usize,
+    pub outcomes: Vec<Outcome>,
+    /// Length group of the quotes, timed tests taking the longest ones
+    pub group: usize,
+}
+
+impl Session {
+    /// The tests of `plan`, each step repeated as many times as it asks.
+    pub fn new(plan: &[Step], group: usize) -> Self {
+        Session {
+            tests: plan
+                .iter()
+                .flat_map(|step| std::iter::repeat_n(step.test(), step.count as usize))
+                .collect(),
+            step: 0,
+            outcomes: Vec::new(),
+            group,
+        }
+    }
+
+    /// The test being typed, none when the plan is empty.
+    pub fn current(&self) -> Option<Test> {
+        self.tests.get(self.step).copied()
+    }
+
+    /// Keeps the result of the test being typed, once.
+    pub fn finish(&mut self, outcome: Outcome) {
+        if self.outcomes.len() == self.step {
+            self.outcomes.push(outcome);
+        }
+    }
+
+    /// Whether the test being typed is finished.
+    pub fn step_over(&self) -> bool {
+        self.outcomes.len() > self.step
+    }
+
+    /// Whether every test of the plan is finished.
+    pub fn is_over(&self) -> bool {
+        self.outcomes.len() >= self.tests.len()
+    }
+
+    /// Moves on to the next test once the current one is finished.
+    pub fn advance(&mut self) {
+        if self.step_over() {
+            self.step += 1;
+        }
+    }
+
+    pub fn report(&self) -> Report {
+        let counted: Vec<&Outcome> = self.outcomes.iter().filter(|o| o.counted).collect();
+        let average = |value: fn(&Outcome) -> f32| match counted.len() {
+            0 => 0.,
+            n => counted.iter().map(|o| value(o)).sum::<f32>() / n as f32,
+        };
+        Report {
+            tests: self.outcomes.len(),
+            counted: counted.len(),
+            wpm: average(|o| o.wpm),
+            accuracy: average(|o| o.accuracy),
+            seconds: self.outcomes.iter().map(|o| o.seconds).sum(),
+        }
+    }
+}
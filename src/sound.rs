@@ -0,0 +1,343 @@
+use std::{fmt, time::Duration};
+
+/// What a sound is played for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cue {
+    /// A right character
+    Key,
+    /// A right space, between two words
+    Space,
+    /// A mistake
+    Error,
+    /// The end of a test
+    Done,
+}
+
+/// Plays the cues of a sound pack, built-in beeps standing in for the ones it lacks. Silent
+/// without an audio device, or when built without the `sound` feature.
+#[derive(Default)]
+pub struct Sounds {
+    #[cfg(feature = "sound")]
+    output: Option<player::Output>,
+    // time between two ticks of the metronome, off when none
+    #[cfg(feature = "sound")]
+    beat: Option<Duration>,
+}
+
+impl fmt::Debug for Sounds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Sounds").finish_non_exhaustive()
+    }
+}
+
+impl Sounds {
+    /// Sounds of the named pack at `volume`, from 0 to 1, with a metronome ticking every
+    /// `beat` when there is one. An empty name only has the beeps.
+    pub fn new(pack: &str, volume: f32, beat: Option<Duration>) -> Self {
+        #[cfg(feature = "sound")]
+        return Sounds {
+            output: player::Output::open(pack, volume.clamp(0., 1.)),
+            beat,
+        };
+        #[cfg(not(feature = "sound"))]
+        {
+            let _ = (pack, volume, beat);
+            Sounds::default()
+        }
+    }
+
+    /// Starts the sound of a cue over whatever is still playing.
+    pub fn play(&self, cue: Cue) {
+        #[cfg(feature = "sound")]
+        if let Some(output) = &self.output {
+            output.play(cue);
+        }
+        #[cfg(not(feature = "sound"))]
+        let _ = cue;
+    }
+
+    /// Ticks the metronome from now on, in time with the start of a test.
+    pub fn start_metronome(&mut self) {
+        #[cfg(feature = "sound")]
+        if let (Some(output), Some(beat)) = (&mut self.output, self.beat) {
+            output.start_metronome(beat);
+        }
+    }
+
+    pub fn stop_metronome(&mut self) {
+        #[cfg(feature = "sound")]
+        if let Some(output) = &mut self.output {
+            output.stop_metronome();
+        }
+    }
+}
+
+#[cfg(feature = "sound")]
+mod player {
+    use rand::seq::IndexedRandom;
+    use rodio::{
+        Decoder, OutputStream, OutputStreamBuilder, Sink, Source, buffer::SamplesBuffer,
+        source::SineWave,
+    };
+    use std::{
+        collections::HashMap,
+        fs::{self, File},
+        io::{BufReader, Read, Seek},
+        path::PathBuf,
+        time::Duration,
+    };
+
+    use super::Cue;
+    #[cfg(feature = "bundled-sounds")]
+    use super::bundled;
+
+    // Formats a pack can hold its sounds in, tried in order
+    const EXTENSIONS: [&str; 4] = ["wav", "ogg", "flac", "mp3"];
+    // The beeps are pure sine waves, loud at full scale
+    const BEEP_VOLUME: f32 = 0.3;
+    // Name of the metronome tick in a sound pack
+    const TICK: &str = "tick";
+    // File of a pack listing the sounds of each cue, optional
+    const MAPPING: &str = "pack.toml";
+
+    impl Cue {
+        const ALL: [Cue; 4] = [Cue::Key, Cue::Space, Cue::Error, Cue::Done];
+
+        /// Names of the cue in a sound pack, the first one it has a sound for is used.
+        fn names(self) -> &'static [&'static str] {
+            match self {
+                Cue::Key => &["key"],
+                Cue::Space => &["space"],
+                Cue::Error => &["error"],
+                // Packs made before `finish` called it `done`
+                Cue::Done => &["finish", "done"],
+            }
+        }
+    }
+
+    /// Where the sounds of a pack come from.
+    enum Pack {
+        /// A folder of the config, with files like `key.wav` or `error.ogg`, or those listed
+        /// for each name in its `pack.toml`, like `key = ["key1.wav", "key2.wav"]`
+        Folder(PathBuf, HashMap<String, Vec<String>>),
+        /// Sounds built into the app, each with its name
+        #[cfg(feature = "bundled-sounds")]
+        Bundled(bundled::Samples),
+    }
+
+    impl Pack {
+        /// The pack of the given name, in the config folder or else built in.
+        fn find(name: &str) -> Option<Pack> {
+            if name.is_empty() {
+                return None;
+            }
+            let dir = dirs::config_dir().map(|dir| dir.join("wpm").join("sounds").join(name));
+            if let Some(dir) = dir.filter(|dir| dir.is_dir()) {
+                let mapping = fs::read_to_string(dir.join(MAPPING))
+                    .ok()
+                    .and_then(|content| toml::from_str(&content).ok())
+                    .unwrap_or_default();
+                return Some(Pack::Folder(dir, mapping));
+            }
+            #[cfg(feature = "bundled-sounds")]
+            if let Some((_, sounds)) = bundled::PACKS.iter().find(|(pack, _)| *pack == name) {
+                return Some(Pack::Bundled(sounds));
+            }
+            None
+        }
+
+        /// Every sound of the given name that decodes, none when the pack has no such sound.
+        fn load(&self, name: &str) -> Vec<SamplesBuffer> {
+            match self {
+                Pack::Folder(dir, mapping) => match mapping.get(name) {
+                    Some(files) => files
+                        .iter()
+                        .filter_map(|file| decode(BufReader::new(File::open(dir.join(file)).ok()?)))
+                        .collect(),
+                    None => (EXTENSIONS.iter())
+                        .find_map(|extension| {
+                            let file = File::open(dir.join(format!("{name}.{extension}"))).ok()?;
+                            decode(BufReader::new(file))
+                        })
+                        .into_iter()
+                        .collect(),
+                },
+                #[cfg(feature = "bundled-sounds")]
+                Pack::Bundled(sounds) => sounds
+                    .iter()
+                    .filter(|(sound, _)| *sound == name)
+                    .filter_map(|(_, bytes)| decode(std::io::Cursor::new(*bytes)))
+                    .collect(),
+            }
+        }
+
+        fn cue(&self, cue: Cue) -> Vec<SamplesBuffer> {
+            cue.names()
+                .iter()
+                .map(|name| self.load(name))
+                .find(|sounds| !sounds.is_empty())
+                .unwrap_or_default()
+        }
+    }
+
+    pub struct Output {
+        stream: OutputStream,
+        // decoded up front, so a key press never waits on the disk, one picked at random
+        // when a cue has several
+        cues: [Vec<SamplesBuffer>; 4],
+        tick: SamplesBuffer,
+        volume: f32,
+        // loops the tick on its own, so that it keeps time whatever the keys do
+        metronome: Option<Sink>,
+    }
+
+    impl Output {
+        pub fn open(pack: &str, volume: f32) -> Option<Self> {
+            let mut stream = OutputStreamBuilder::open_default_stream().ok()?;
+            // It would print over the shell once the terminal is restored
+            stream.log_on_drop(false);
+            let pack = Pack::find(pack);
+            let mut cues =
+                Cue::ALL.map(|cue| pack.as_ref().map(|pack| pack.cue(cue)).unwrap_or_default());
+            // Spaces sound like the other keys in packs without a sound of their own for them
+            if cues[Cue::Space as usize].is_empty() {
+                cues[Cue::Space as usize] = cues[Cue::Key as usize].clone();
+            }
+            for (cue, sounds) in Cue::ALL.into_iter().zip(&mut cues) {
+                if sounds.is_empty() {
+                    sounds.push(beep(cue));
+                }
+            }
+            let tick = pack
+                .as_ref()
+                .and_then(|pack| pack.load(TICK).into_iter().next())
+                .unwrap_or_else(|| tone(2000., Duration::from_millis(10)));
+            Some(Output {
+                stream,
+                cues,
+                tick,
+                volume,
+                metronome: None,
+            })
+        }
+
+        pub fn play(&self, cue: Cue) {
+            if let Some(sound) = self.cues[cue as usize].choose(&mut rand::rng()) {
+                self.stream.mixer().add(sound.clone().amplify(self.volume));
+            }
+        }
+        /// Loops the tick padded with silence to the length of a beat.
+        pub fn start_metronome(&mut self, beat: Duration) {
+            let (channels, sample_rate) = (self.tick.channels(), self.tick.sample_rate());
+            let length = beat.as_secs_f64() * sample_rate as f64 * channels as f64;
+            // Whole frames, or the channels would swap every other beat
+            let length = (length as usize).max(1).next_multiple_of(channels as usize);
+            let mut samples: Vec<f32> = self.tick.clone().take(length).collect();
+            samples.resize(length, 0.);
+
+            self.stop_metronome();
+            let sink = Sink::connect_new(self.stream.mixer());
+            sink.set_volume(self.volume);
+            sink.append(SamplesBuffer::new(channels, sample_rate, samples).repeat_infinite());
+            self.metronome = Some(sink);
+        }
+
+        pub fn stop_metronome(&mut self) {
+            if let Some(sink) = self.metronome.take() {
+                sink.stop();
+            }
+        }
+    }
+
+    fn decode(reader: impl Read + Seek + Send + Sync + 'static) -> Option<SamplesBuffer> {
+        Some(samples(Decoder::new(reader).ok()?))
+    }
+
+    /// A short fading beep, high for keys, low for mistakes and long for the end.
+    fn beep(cue: Cue) -> SamplesBuffer {
+        let (frequency, length) = match cue {
+            Cue::Key | Cue::Space => (1200., Duration::from_millis(15)),
+            Cue::Error => (220., Duration::from_millis(120)),
+            Cue::Done => (880., Duration::from_millis(400)),
+        };
+        tone(frequency, length)
+    }
+
+    fn tone(frequency: f32, length: Duration) -> SamplesBuffer {
+        samples(
+            SineWave::new(frequency)
+                .take_duration(length)
+                .fade_out(length)
+                .amplify(BEEP_VOLUME),
+        )
+    }
+
+    fn samples(source: impl Source) -> SamplesBuffer {
+        let (channels, sample_rate) = (source.channels(), source.sample_rate());
+        SamplesBuffer::new(channels, sample_rate, source.collect::<Vec<_>>())
+    }
+}
+
+/// The packs built into the app, so they play without any files.
+#[cfg(feature = "bundled-sounds")]
+mod bundled {
+    /// Sounds of a pack, each with its name.
+    pub type Samples = &'static [(&'static str, &'static [u8])];
+
+    pub static PACKS: &[(&str, Samples)] = &[
+        (
+            "mechanical",
+            &[
+                (
+                    "key",
+                    include_bytes!("../assets/sounds/mechanical/key1.wav"),
+                ),
+                (
+                    "key",
+                    include_bytes!("../assets/sounds/mechanical/key2.wav"),
+                ),
+                (
+                    "key",
+                    include_bytes!("../assets/sounds/mechanical/key3.wav"),
+                ),
+                (
+                    "space",
+                    include_bytes!("../assets/sounds/mechanical/space.wav"),
+                ),
+                (
+                    "error",
+                    include_bytes!("../assets/sounds/mechanical/error.wav"),
+                ),
+                (
+                    "finish",
+                    include_bytes!("../assets/sounds/mechanical/finish.wav"),
+                ),
+            ],
+        ),
+        (
+            "typewriter",
+            &[
+                (
+                    "key",
+                    include_bytes!("../assets/sounds/typewriter/key1.wav"),
+                ),
+                (
+                    "key",
+                    include_bytes!("../assets/sounds/typewriter/key2.wav"),
+                ),
+                (
+                    "space",
+                    include_bytes!("../assets/sounds/typewriter/space.wav"),
+                ),
+                (
+                    "error",
+                    include_bytes!("../assets/sounds/typewriter/error.wav"),
+                ),
+                (
+                    "finish",
+                    include_bytes!("../assets/sounds/typewriter/finish.wav"),
+                ),
+            ],
+        ),
+    ];
+}
@@ -0,0 +1,44 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    symbols::border,
+    text::Line,
+    widgets::{Block, Paragraph, Widget},
+};
+use wpm_core::achievements::Achievement;
+
+use crate::{app::App, i18n::fill};
+
+impl App {
+    /// Every achievement, those unlocked with their date and the others dimmed.
+    pub(super) fn render_achievements(&self, area: Rect, buf: &mut Buffer) {
+        let strings = self.strings;
+        let block = Block::bordered()
+            .title(Line::from(strings.achievements_title.bold()).centered())
+            .title_bottom(Line::from(vec![strings.back.into(), "<F3> ".blue().bold()]).centered())
+            .border_set(border::THICK);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let mut lines = Vec::new();
+        for achievement in Achievement::ALL {
+            let (name, description) = strings.achievements[achievement as usize];
+            lines.push(match self.achievements.get(achievement) {
+                Some(timestamp) => Line::from(vec![
+                    " ★ ".yellow().bold(),
+                    name.bold(),
+                    format!(" - {description}, ").into(),
+                    fill(strings.unlocked_on, &[&self.locale.date(timestamp)]).green(),
+                ]),
+                None => Line::from(vec![
+                    " ☆ ".dark_gray(),
+                    name.dark_gray().bold(),
+                    format!(" - {description}, {}", strings.locked).dark_gray(),
+                ]),
+            });
+        }
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
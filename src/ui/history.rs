@@ -0,0 +1,165 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::Stylize,
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Widget},
+};
+use wpm_core::{
+    diff::{Cell, Mark},
+    history::Record,
+};
+
+use crate::{app::App, i18n::fill};
+
+impl App {
+    /// Past tests, the latest first and the selected one highlighted, or the text of the one
+    /// opened with its mistakes marked.
+    pub(super) fn render_history(&self, area: Rect, buf: &mut Buffer) {
+        let strings = self.strings;
+        let hints = match self.history_detail {
+            Some(_) => vec![strings.back.into(), "<ENTER> ".blue().bold()],
+            None => vec![
+                " ↑ ↓".blue().bold(),
+                strings.to_choose.into(),
+                "<ENTER>".blue().bold(),
+                strings.to_open.into(),
+                strings.back.into(),
+                "<F4> ".blue().bold(),
+            ],
+        };
+        let block = Block::bordered()
+            .title(Line::from(strings.history_title.bold()).centered())
+            .title_bottom(Line::from(hints).centered())
+            .border_set(border::THICK);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if self.history.is_empty() {
+            return Paragraph::new(Line::from(strings.no_history.italic()))
+                .centered()
+                .render(inner, buf);
+        }
+        match &self.history_detail {
+            Some(lines) => self.render_history_detail(lines, inner, buf),
+            None => self.render_history_list(inner, buf),
+        }
+    }
+
+    fn render_history_list(&self, area: Rect, buf: &mut Buffer) {
+        let strings = self.strings;
+        let [header, _] = Self::history_list_layout(area);
+        let columns = [
+            strings.date_column,
+            strings.speed_column,
+            strings.accuracy_column,
+            strings.time_column,
+            strings.text_column,
+        ];
+        Paragraph::new(
+            Line::from(Self::history_columns(columns.map(String::from)))
+                .blue()
+                .bold(),
+        )
+        .render(header, buf);
+
+        let records: Vec<&Record> = self.history.iter().rev().collect();
+        for (row, index) in self.history_rows(area) {
+            let line = Line::from(Self::history_columns(self.history_cells(records[index])));
+            let line = if index == self.history_row {
+                line.black().on_green().bold()
+            } else {
+                line
+            };
+            Paragraph::new(line).render(row, buf);
+        }
+    }
+
+    /// Each visible row of the list and the index of its test, the latest first, scrolled to
+    /// keep the selected one in view. `area` is inside the border of the screen.
+    pub(crate) fn history_rows(&self, area: Rect) -> Vec<(Rect, usize)> {
+        let [_, rows] = Self::history_list_layout(area);
+        let visible = rows.height as usize;
+        let offset = (self.history_row + 1).saturating_sub(visible);
+        (offset..self.history.len())
+            .take(visible)
+            .enumerate()
+            .map(|(y, index)| (Rect::new(rows.x, rows.y + y as u16, rows.width, 1), index))
+            .collect()
+    }
+
+    fn history_list_layout(area: Rect) -> [Rect; 2] {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .areas(area)
+    }
+
+    fn history_columns([date, speed, accuracy, time, text]: [String; 5]) -> String {
+        format!(" {date:<10}  {speed:>8}  {accuracy:>9}  {time:>6}  {text}")
+    }
+
+    fn history_cells(&self, record: &Record) -> [String; 5] {
+        [
+            self.locale.date(record.timestamp),
+            format!("{:.0} {}", record.wpm, record.unit()),
+            self.locale.percent(record.accuracy, 1),
+            self.locale.seconds(record.seconds, 0),
+            record.category.clone(),
+        ]
+    }
+
+    /// The text as it was typed: what was left wrong struck through before the right
+    /// graphemes, skipped ones underlined, extra ones struck through and fixed ones in yellow.
+    fn render_history_detail(&self, lines: &[Vec<Cell>], area: Rect, buf: &mut Buffer) {
+        let strings = self.strings;
+        if lines.is_empty() {
+            return Paragraph::new(Line::from(strings.no_replay.italic()))
+                .centered()
+                .render(area, buf);
+        }
+
+        let record = self.history.iter().rev().nth(self.history_row);
+        let summary = record.map(|record| self.history_cells(record).join(" · "));
+        let mut text = vec![
+            Line::from(summary.unwrap_or_default().bold()),
+            Line::default(),
+        ];
+        let (mut wrong, mut fixed, mut skipped, mut extra) = (0, 0, 0, 0);
+        for line in lines {
+            let mut spans = Vec::with_capacity(line.len());
+            for cell in line {
+                let expected = cell.expected.clone();
+                match &cell.mark {
+                    Mark::Right => spans.push(Span::raw(expected)),
+                    Mark::Untyped => spans.push(expected.dark_gray()),
+                    Mark::Wrong(typed) => {
+                        wrong += 1;
+                        spans.push(typed.clone().fg(self.bad()).crossed_out());
+                        spans.push(expected.fg(self.good()));
+                    }
+                    Mark::Skipped => {
+                        skipped += 1;
+                        spans.push(expected.fg(self.good()).underlined());
+                    }
+                    Mark::Extra(typed) => {
+                        extra += typed.chars().count();
+                        spans.push(typed.clone().fg(self.bad()).crossed_out());
+                    }
+                    Mark::Fixed(_) => {
+                        fixed += 1;
+                        spans.push(expected.yellow());
+                    }
+                }
+            }
+            text.push(Line::from(spans));
+        }
+        text.push(Line::default());
+        text.push(Line::from(
+            fill(strings.diff_counts, &[&wrong, &fixed, &skipped, &extra]).dark_gray(),
+        ));
+
+        Paragraph::new(text).render(area.inner(Margin::new(1, 0)), buf);
+    }
+}
@@ -0,0 +1,64 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    symbols::border,
+    text::Line,
+    widgets::{Block, Paragraph, Widget},
+};
+
+use crate::app::App;
+
+// Longest name shown, longer ones are cut
+const MAX_NAME_WIDTH: usize = 16;
+
+impl App {
+    /// Standings of the group as of the last `wpm sync`, this player's row in bold.
+    pub(super) fn render_leaderboard(&self, area: Rect, buf: &mut Buffer) {
+        let strings = self.strings;
+        let block = Block::bordered()
+            .title(Line::from(strings.leaderboard_title.bold()).centered())
+            .title_bottom(Line::from(vec![strings.back.into(), "<F2> ".blue().bold()]).centered())
+            .border_set(border::THICK);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if self.leaderboard.is_empty() {
+            let message = if self.config.leaderboard_url.is_empty() {
+                strings.no_leaderboard
+            } else {
+                strings.no_standings
+            };
+            return Paragraph::new(Line::from(message.italic()))
+                .centered()
+                .render(inner, buf);
+        }
+
+        let me = self.config.player_name();
+        let mut lines = vec![
+            Line::from(format!(
+                " {:>3}  {:<MAX_NAME_WIDTH$} {:>6} {:>8} {:>6}",
+                "#", strings.name, strings.best_column, strings.average_column, strings.tests
+            ))
+            .blue()
+            .bold(),
+        ];
+        for (rank, standing) in self.leaderboard.iter().enumerate() {
+            let name: String = standing.name.chars().take(MAX_NAME_WIDTH).collect();
+            let line = Line::from(format!(
+                " {:>3}  {name:<MAX_NAME_WIDTH$} {:>6.0} {:>8.0} {:>6}",
+                rank + 1,
+                standing.best_wpm,
+                standing.average_wpm,
+                standing.tests
+            ));
+            lines.push(if standing.name == me {
+                line.green().bold()
+            } else {
+                line
+            });
+        }
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
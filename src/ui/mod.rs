@@ -0,0 +1,238 @@
+use ratatui::{
+    Frame,
+    buffer::Buffer,
+    layout::{Position, Rect},
+    style::{Color, Modifier, Stylize},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::{
+    app::App,
+    config::{ErrorCue, Palette, Segment},
+    layout::{TestLayout, split_status},
+};
+
+mod achievements;
+mod history;
+mod leaderboard;
+mod race;
+mod results;
+#[cfg(test)]
+mod tests;
+mod typing;
+
+// Letters typed in the wrong case in a row before assuming Caps Lock is on
+const CAPS_LOCK_STREAK: usize = 3;
+
+/// Draws the whole app from its state, without changing it.
+pub fn view(app: &App, frame: &mut Frame) {
+    frame.render_widget(app, frame.area());
+    // Screen readers and braille displays follow the cursor
+    if let Some(caret) = app.linear_caret(frame.area()) {
+        frame.set_cursor_position(caret);
+    }
+    if app.monochrome {
+        monochrome(frame.buffer_mut());
+    }
+}
+
+/// Trades colors for attributes: highlights are reversed and gray text dimmed, which any
+/// terminal shows.
+fn monochrome(buf: &mut Buffer) {
+    for cell in &mut buf.content {
+        if cell.bg != Color::Reset {
+            cell.modifier.toggle(Modifier::REVERSED);
+        }
+        if matches!(cell.fg, Color::Gray | Color::DarkGray) {
+            cell.modifier.insert(Modifier::DIM);
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
+}
+
+impl Widget for &App {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (area, status) = split_status(area, !self.config.status_bar.is_empty());
+        self.render_status(status, buf);
+        let (area, race) = self.split_race(area);
+        self.render_race(race, buf);
+
+        if self.show_leaderboard {
+            return self.render_leaderboard(area, buf);
+        }
+        if self.show_achievements {
+            return self.render_achievements(area, buf);
+        }
+        if self.show_history {
+            return self.render_history(area, buf);
+        }
+        if self.waiting_for_race() {
+            return self.render_waiting(area, buf);
+        }
+        if self.test.is_done() {
+            return self.render_results(area, buf);
+        }
+        if self.config.linear {
+            return self.render_linear(area, buf);
+        }
+
+        self.render_typing(area, buf);
+    }
+}
+
+impl Palette {
+    /// Right characters and good news.
+    fn good(self) -> Color {
+        match self {
+            Palette::Default => Color::Green,
+            Palette::Deuteranopia | Palette::Protanopia => Color::Indexed(33),
+            Palette::Tritanopia => Color::Indexed(37),
+        }
+    }
+
+    /// Mistakes and bad news.
+    fn bad(self) -> Color {
+        match self {
+            Palette::Default => Color::Red,
+            Palette::Deuteranopia => Color::Indexed(208),
+            Palette::Protanopia => Color::Indexed(220),
+            Palette::Tritanopia => Color::Indexed(204),
+        }
+    }
+}
+
+impl App {
+    fn good(&self) -> Color {
+        self.config.palette.good()
+    }
+
+    fn bad(&self) -> Color {
+        self.config.palette.bad()
+    }
+
+    /// Marks a mistake the way the config asks, on top of its color. Without colors, mistakes
+    /// are always marked.
+    fn mistake<'a>(&self, span: Span<'a>) -> Span<'a> {
+        match self.config.error_cue {
+            ErrorCue::Off if self.monochrome => span.underlined(),
+            ErrorCue::Off => span,
+            ErrorCue::Underline => span.underlined(),
+            ErrorCue::Strikethrough => span.crossed_out(),
+            ErrorCue::Inverse => span.reversed(),
+        }
+    }
+
+    /// Room left to the screens in a frame of the given size, beside the status bar and race.
+    pub(crate) fn screen_area(&self, area: Rect) -> Rect {
+        let (area, _) = split_status(area, !self.config.status_bar.is_empty());
+        self.split_race(area).0
+    }
+
+    /// Layout of the typing screen for a frame of the given size.
+    pub(crate) fn test_layout(&self, area: Rect) -> TestLayout {
+        TestLayout::new(self.config.layout, self.screen_area(area), self.show_info)
+    }
+
+    /// Where the next character goes on the linear typing screen, none on other screens.
+    fn linear_caret(&self, area: Rect) -> Option<Position> {
+        if !self.config.linear
+            || self.show_leaderboard
+            || self.show_history
+            || self.waiting_for_race()
+            || self.test.is_done()
+        {
+            return None;
+        }
+        let area = self.screen_area(area);
+        let typed = Line::from(self.typed_line()).width() as u16;
+        Some(Position::new(
+            area.x + typed.min(area.width.saturating_sub(1)),
+            area.y,
+        ))
+    }
+
+    /// Caps Lock guessed from the last letters typed in the wrong case. Terminals don't report
+    /// it with the keys that type text.
+    fn caps_lock_suspected(&self) -> bool {
+        self.test.wrong_case_streak(CAPS_LOCK_STREAK)
+    }
+
+    fn render_status(&self, area: Rect, buf: &mut Buffer) {
+        let stats = self.live_stats();
+
+        let mut spans: Vec<Span> = Vec::with_capacity(self.config.status_bar.len() * 2);
+
+        for segment in &self.config.status_bar {
+            let span = match segment {
+                Segment::Wpm | Segment::Accuracy
+                    if !self.config.live_stats && !self.test.is_done() =>
+                {
+                    continue;
+                }
+                Segment::Mode => self.mode_label().blue().bold(),
+                Segment::Language => match self.config.emulate_layout {
+                    Some(layout) => {
+                        format!("{}, {layout}", self.content.active().language()).into()
+                    }
+                    None => self.content.active().language().into(),
+                },
+                Segment::Wpm => format!("{:.0} {}", stats.speed, self.test.unit()).green(),
+                Segment::Accuracy => self.locale.percent(stats.accuracy, 1).into(),
+                Segment::Timer => self.locale.seconds(stats.elapsed.as_secs_f32(), 1).into(),
+                Segment::CapsLock if self.caps_lock_suspected() => {
+                    self.strings.caps_lock.black().on_yellow().bold()
+                }
+                Segment::CapsLock => continue,
+            };
+
+            if !spans.is_empty() {
+                spans.push(" │ ".dark_gray());
+            }
+            spans.push(span);
+        }
+
+        spans.insert(0, " ".into());
+        Paragraph::new(Line::from(spans)).render(area, buf);
+    }
+}
+
+pub fn group_label(group: &[u32; 2]) -> String {
+    format!(" {}-{} ", group[0], group[1])
+}
+
+/// Where each span of a line is drawn when it is centered on the first row of `area`.
+pub(crate) fn span_areas(line: &Line, area: Rect) -> Vec<Rect> {
+    // The way `Paragraph` centers lines
+    let mut x = area.x + (area.width / 2).saturating_sub(line.width() as u16 / 2);
+    let row = Rect::new(area.x, area.y, area.width, area.height.min(1));
+    line.spans
+        .iter()
+        .map(|span| {
+            let width = span.width() as u16;
+            x += width;
+            Rect::new(x - width, row.y, width, row.height).intersection(row)
+        })
+        .collect()
+}
+
+fn get_wpm(wpm: f32) -> String {
+    let emoji = if wpm < 10. {
+        "🦥"
+    } else if wpm < 25. {
+        "🐌"
+    } else if wpm < 50. {
+        "🐢"
+    } else if wpm < 75. {
+        "🐇"
+    } else if wpm < 100. {
+        "🐆"
+    } else if wpm < 125. {
+        "🚄"
+    } else {
+        "⚡"
+    };
+
+    format!("{emoji} {:>3}", wpm.round() as u32)
+}
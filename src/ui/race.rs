@@ -0,0 +1,141 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Stylize,
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Widget},
+};
+
+use wpm_core::race::Race;
+
+use crate::{app::App, i18n::fill};
+
+// Longest name shown, longer ones are cut
+const MAX_NAME_WIDTH: usize = 16;
+
+impl App {
+    /// Splits off the race standings at the bottom, one row per player inside a border.
+    pub(crate) fn split_race(&self, area: Rect) -> (Rect, Rect) {
+        let height = match self.standings() {
+            Some(standings) => standings.len() as u16 + 2,
+            None => 0,
+        };
+        let [main, race] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(height)])
+            .areas(area);
+
+        (main, race)
+    }
+
+    /// A progress bar per player, best placed first.
+    pub(super) fn render_race(&self, area: Rect, buf: &mut Buffer) {
+        let Some(standings) = self.standings() else {
+            return;
+        };
+
+        let race = self.race.as_ref();
+        let title = match (race.and_then(Race::port), race.and_then(Race::room)) {
+            (Some(port), _) => fill(self.strings.race_on_port, &[&port]),
+            (_, Some(room)) => fill(self.strings.room, &[&room]),
+            _ => self.strings.race.to_string(),
+        };
+        let me = race.map_or_else(|| self.config.player_name(), |race| race.name().to_string());
+        let block = Block::bordered()
+            .title(title.bold())
+            .border_set(border::ROUNDED);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let name_width = standings
+            .iter()
+            .map(|p| p.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(MAX_NAME_WIDTH);
+        // Wins are only counted by race servers
+        let wins_width = if standings.iter().any(|p| p.wins > 0) {
+            5
+        } else {
+            0
+        };
+        // place, name, speed and wins around the bar
+        let bar_width = (inner.width as usize).saturating_sub(name_width + 18 + wins_width);
+
+        let lines: Vec<Line> = standings
+            .iter()
+            .map(|player| {
+                let filled = (player.progress * bar_width as f32).round() as usize;
+                let name: String = player.name.chars().take(name_width).collect();
+                let name = format!("{name:<name_width$} ");
+                let place = match player.place {
+                    Some(place) => format!(" {:>4} ", (self.strings.ordinal)(place))
+                        .green()
+                        .bold(),
+                    None => "      ".into(),
+                };
+
+                let wins = if wins_width > 0 {
+                    format!(" {:>3}★", player.wins).yellow()
+                } else {
+                    "".into()
+                };
+
+                Line::from(vec![
+                    place,
+                    if player.name == me {
+                        name.bold()
+                    } else {
+                        Span::from(name)
+                    },
+                    "█".repeat(filled).blue(),
+                    "░".repeat(bar_width.saturating_sub(filled)).dark_gray(),
+                    format!(" {:>3.0} {}", player.wpm, self.test.unit()).into(),
+                    wins,
+                ])
+            })
+            .collect();
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+
+    /// Shown to guests until the race starts.
+    pub(super) fn render_waiting(&self, area: Rect, buf: &mut Buffer) {
+        let countdown = self.race.as_ref().and_then(|race| race.countdown());
+        let room = self.race.as_ref().and_then(|race| race.room());
+        let strings = self.strings;
+        let message = match (countdown, &room) {
+            (Some(seconds), _) => Line::from(fill(strings.starting_in, &[&seconds]).bold()),
+            (None, Some(room)) => Line::from(vec![
+                strings.others_join.italic(),
+                format!("wpm join <server> {room}").blue(),
+                strings.start_when_ready.italic(),
+            ]),
+            (None, None) => Line::from(strings.waiting_for_host.italic()),
+        };
+
+        let mut instructions = Vec::new();
+        if room.is_some() && countdown.is_none() {
+            instructions.extend([strings.start.into(), "<TAB>".blue().bold()]);
+        }
+        instructions.extend([strings.quit.into(), "<ESC> ".blue().bold()]);
+
+        let block = Block::bordered()
+            .title(Line::from(strings.race.bold()).centered())
+            .title_bottom(Line::from(instructions).centered())
+            .border_set(border::THICK);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [_, middle, _] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(1),
+                Constraint::Min(0),
+            ])
+            .areas(inner);
+        Paragraph::new(message).centered().render(middle, buf);
+    }
+}
@@ -0,0 +1,897 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    symbols::{self, border},
+    text::Line,
+    widgets::{Axis, Block, Chart, Clear, Dataset, GraphType, Paragraph, Widget},
+};
+use std::time::Duration;
+use wpm_core::{
+    course::LESSONS,
+    endurance, history,
+    latency::{self, Histogram},
+    mistakes,
+    rating::Tag,
+    shift, stats,
+};
+
+use super::{CAPS_LOCK_STREAK, get_wpm};
+use crate::{
+    app::{Action, App},
+    i18n::fill,
+};
+
+const WORST_WORDS_SHOWN: usize = 5;
+// Key doing each entry of the menu, in the order of `Action::ALL`
+const ACTION_KEYS: [&str; 6] = ["R", "N", "M", "D", "S", "Q"];
+// Key giving each tag, in the order of `Tag::ALL`
+const TAG_KEYS: [&str; 4] = ["F", "B", "E", "H"];
+// Size of the box of details, the info panel of the typing screen
+const DETAILS_WIDTH: u16 = 80;
+const DETAILS_HEIGHT: u16 = 5;
+
+impl App {
+    pub(super) fn render_results(&self, area: Rect, buf: &mut Buffer) {
+        let strings = self.strings;
+        let title = Line::from(strings.results_title.bold().green());
+        let block = Block::bordered()
+            .title(title.centered())
+            .title_bottom(
+                Line::from(vec![
+                    " ← →".blue().bold(),
+                    strings.to_choose.into(),
+                    "<ENTER>".blue().bold(),
+                    strings.to_confirm.into(),
+                    "<C>".blue().bold(),
+                    if self.copied {
+                        strings.copied.green()
+                    } else {
+                        strings.to_copy.into()
+                    },
+                ])
+                .centered(),
+            )
+            .border_set(border::ROUNDED);
+
+        let [inner, rating, menu] = self.results_layout(area);
+        block.render(area, buf);
+        Paragraph::new(self.rating_line()).render(rating, buf);
+        Paragraph::new(self.menu_line()).render(menu, buf);
+
+        // Side by side when there is room for it, stacked otherwise
+        let panels = if inner.width >= 100 {
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Percentage(45), // wpm chart
+                    Constraint::Percentage(25), // stats + splits
+                    Constraint::Percentage(30), // pb + worst words
+                ])
+                .split(inner)
+        } else {
+            Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(6),
+                    Constraint::Length(9),
+                    Constraint::Length(7),
+                ])
+                .split(inner)
+        };
+
+        let duration = self.test.elapsed();
+
+        if self.show_latency {
+            self.render_latency(duration, panels[0], buf);
+        } else if self.endurance.is_some() {
+            self.render_minutes(panels[0], buf);
+        } else {
+            self.render_chart(duration, panels[0], buf);
+        }
+        self.render_stats(duration, panels[1], buf);
+        self.render_best(duration, panels[2], buf);
+
+        if self.show_details {
+            self.render_details(inner, buf);
+        }
+        if self.show_share {
+            self.render_share(inner, buf);
+        }
+        self.animations.render_confetti(inner, buf);
+        self.render_toast(inner, buf);
+    }
+
+    /// The panels inside the border of the results, the rating under them if the quote can be
+    /// rated, and the menu at the bottom.
+    pub(crate) fn results_layout(&self, area: Rect) -> [Rect; 3] {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(self.rateable() as u16),
+                Constraint::Length(1),
+            ])
+            .areas(Block::bordered().inner(area))
+    }
+
+    /// What to do next, the selected action highlighted, each with its key: a label and a key
+    /// span per entry, in the order of `Action::ALL`.
+    pub(crate) fn menu_line(&self) -> Line<'static> {
+        let mut spans = Vec::with_capacity(Action::ALL.len() * 2);
+        for (action, key) in Action::ALL.into_iter().zip(ACTION_KEYS) {
+            let label = format!(" {} ", self.strings.actions[action as usize]);
+            spans.push(if action == self.action {
+                label.black().on_green().bold()
+            } else {
+                label.into()
+            });
+            spans.push(format!("<{key}>  ").blue().bold());
+        }
+        Line::from(spans).centered()
+    }
+
+    /// The tags the quote can be given, those it has highlighted, each with its key: a label
+    /// and a key span per tag, in the order of `Tag::ALL`, after the prompt.
+    pub(crate) fn rating_line(&self) -> Line<'static> {
+        let (mut spans, tags) = (vec![self.strings.rate.into()], self.tags());
+        for (tag, key) in Tag::ALL.into_iter().zip(TAG_KEYS) {
+            let label = format!(" {} ", self.strings.tags[tag as usize]);
+            spans.push(if tags.contains(&tag) {
+                label.black().on_yellow().bold()
+            } else {
+                label.into()
+            });
+            spans.push(format!("<{key}>  ").blue().bold());
+        }
+        Line::from(spans).centered()
+    }
+
+    /// The card to share in the middle of the results, light on dark whatever the colors of
+    /// the terminal so that its QR code scans.
+    fn render_share(&self, area: Rect, buf: &mut Buffer) {
+        let card = self.share_card();
+        let width = card
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0) as u16;
+        let share = centered(area, width, card.lines().count() as u16);
+        Clear.render(share, buf);
+        Paragraph::new(card).white().on_black().render(share, buf);
+    }
+
+    /// The text of the test and how it went before, over the middle of the results.
+    fn render_details(&self, area: Rect, buf: &mut Buffer) {
+        let details = centered(area, DETAILS_WIDTH, DETAILS_HEIGHT);
+        Clear.render(details, buf);
+        self.render_info(details, buf);
+    }
+
+    /// A box in the top right corner naming the achievements the test unlocked.
+    fn render_toast(&self, area: Rect, buf: &mut Buffer) {
+        if self.unlocked.is_empty() {
+            return;
+        }
+        let strings = self.strings;
+        let lines: Vec<Line> = self
+            .unlocked
+            .iter()
+            .map(|achievement| {
+                Line::from(vec![
+                    format!(" ★ {}", strings.achievement_unlocked)
+                        .yellow()
+                        .bold(),
+                    format!("{} ", strings.achievements[*achievement as usize].0).white(),
+                ])
+            })
+            .collect();
+        let width = (lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 2).min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let toast = Rect::new(area.right() - width, area.y, width, height);
+        Clear.render(toast, buf);
+        Paragraph::new(lines)
+            .block(Block::bordered().border_set(border::ROUNDED).yellow())
+            .render(toast, buf);
+    }
+
+    fn render_chart(&self, duration: Duration, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .title(Line::from(vec![
+                self.strings.chart_title.bold(),
+                "<L>".blue().bold(),
+                self.strings.show_latency.into(),
+            ]))
+            .border_set(border::ROUNDED);
+
+        let max_x = duration.as_secs_f64().max(1.);
+        let max_y = self
+            .test
+            .samples()
+            .iter()
+            .map(|(_, wpm)| *wpm)
+            .fold(10., f64::max)
+            * 1.2;
+
+        let dataset = Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Green))
+            .data(self.test.samples());
+
+        Chart::new(vec![dataset])
+            .block(block)
+            .x_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0., max_x])
+                    .labels(["0s".to_string(), format!("{:.0}s", max_x)]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0., max_y])
+                    .labels(["0".to_string(), format!("{:.0}", max_y)]),
+            )
+            .render(area, buf);
+    }
+
+    /// Speed over each minute of an endurance run, how it drops with fatigue.
+    fn render_minutes(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .title(Line::from(vec![
+                self.strings.minutes_title.bold(),
+                "<L>".blue().bold(),
+                self.strings.show_latency.into(),
+            ]))
+            .border_set(border::ROUNDED);
+
+        let minutes: Vec<(f64, f64)> = self
+            .test
+            .minute_speeds()
+            .into_iter()
+            .enumerate()
+            .map(|(minute, speed)| (minute as f64 + 0.5, speed as f64))
+            .collect();
+        let max_x = (minutes.len() as f64).max(1.);
+        let max_y = minutes.iter().map(|(_, speed)| *speed).fold(10., f64::max) * 1.2;
+
+        let datasets = vec![
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Bar)
+                .style(Style::default().fg(Color::Green))
+                .data(&minutes),
+        ];
+        Chart::new(datasets)
+            .block(block)
+            .x_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0., max_x])
+                    .labels(["0".to_string(), format!("{max_x:.0} min")]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0., max_y])
+                    .labels(["0".to_string(), format!("{:.0}", max_y)]),
+            )
+            .render(area, buf);
+    }
+
+    /// How long keys took after the one before, for this test in bars and for all of them in a
+    /// line, with what the long pauses cost.
+    fn render_latency(&self, duration: Duration, area: Rect, buf: &mut Buffer) {
+        let strings = self.strings;
+        let test = Histogram::of(self.test.keys());
+        let lost = Duration::from_millis(test.lost_ms());
+        let summary = fill(
+            strings.latency_summary,
+            &[
+                &test.median(),
+                &self.locale.number(latency::HESITATION_MS as f32 / 1000., 1),
+                &test.hesitations(),
+                &self.locale.percent(test.lost_share(), 0),
+                &format!("{:.0}", self.test.speed(duration.saturating_sub(lost))),
+                &self.test.unit(),
+            ],
+        );
+        let block = Block::bordered()
+            .title(Line::from(vec![
+                strings.latency_title.bold(),
+                "<L>".blue().bold(),
+                strings.show_speed.into(),
+            ]))
+            .title_bottom(Line::from(format!(" {summary} ").gray()))
+            .border_set(border::ROUNDED);
+
+        let points = |histogram: &Histogram| -> Vec<(f64, f64)> {
+            histogram
+                .shares()
+                .iter()
+                .enumerate()
+                .map(|(bucket, share)| {
+                    let ms = bucket as u32 * latency::BUCKET_MS + latency::BUCKET_MS / 2;
+                    (ms as f64, *share as f64)
+                })
+                .collect()
+        };
+        let (this_test, all_tests) = (points(&test), points(&self.latency));
+        let max_x = (latency::BUCKETS as u32 * latency::BUCKET_MS) as f64;
+        let max_share = this_test
+            .iter()
+            .chain(&all_tests)
+            .map(|(_, share)| *share)
+            .fold(10., f64::max);
+        let max_y = (max_share * 1.2).min(100.);
+
+        let datasets = vec![
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Bar)
+                .style(Style::default().fg(Color::Green))
+                .data(&this_test),
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::DarkGray))
+                .data(&all_tests),
+        ];
+        Chart::new(datasets)
+            .block(block)
+            .x_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0., max_x])
+                    .labels(["0".to_string(), "0.5 s".to_string(), "1 s+".to_string()]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0., max_y])
+                    .labels(["0".to_string(), format!("{:.0}%", max_y)]),
+            )
+            .render(area, buf);
+    }
+
+    fn render_stats(&self, duration: Duration, area: Rect, buf: &mut Buffer) {
+        let strings = self.strings;
+        let block = Block::bordered()
+            .title(strings.stats_title.bold())
+            .border_set(border::ROUNDED);
+
+        let mut stats = vec![
+            Line::from(vec![
+                format!("{}: ", self.test.unit().to_uppercase())
+                    .blue()
+                    .bold(),
+                get_wpm(self.test.speed(duration)).green().bold(),
+            ]),
+            Line::from(vec![
+                strings.time.blue().bold(),
+                self.locale.seconds(duration.as_secs_f32(), 1).white(),
+            ]),
+            Line::from(vec![
+                strings.words.blue().bold(),
+                format!("{}", self.test.words()).white(),
+                fill(
+                    strings.per_minute,
+                    &[&format!(
+                        "{:.0}",
+                        stats::per_minute(self.test.words(), duration)
+                    )],
+                )
+                .dark_gray(),
+            ]),
+            Line::from(vec![
+                strings.accuracy.blue().bold(),
+                self.locale.percent(self.test.accuracy(), 1).white(),
+                fill(strings.clean_streak, &[&self.test.clean_streak()]).dark_gray(),
+            ]),
+            Line::from(vec![
+                strings.burst.blue().bold(),
+                format!("{:.0} {}", self.test.burst(), self.test.unit()).white(),
+                fill(
+                    strings.word_and_peak,
+                    &[
+                        &format!("{:.0}", self.test.word_burst()),
+                        &format!("{:.0}", self.peak_burst()),
+                    ],
+                )
+                .dark_gray(),
+            ]),
+            Line::from(vec![
+                strings.correct.fg(self.good()).bold(),
+                format!("{}", self.test.correct()).white(),
+                " | ".into(),
+                strings.incorrect.fg(self.bad()).bold(),
+                format!("{}", self.test.incorrect()).white(),
+                " | ".into(),
+                strings.fixed.yellow().bold(),
+                format!("{}", self.test.corrected()).white(),
+            ]),
+        ];
+
+        let mistakes = mistakes::classify(&self.test, self.config.typing_layout());
+        if !mistakes.is_empty() {
+            let kinds: Vec<String> = mistakes
+                .kinds()
+                .into_iter()
+                .map(|(kind, count)| format!("{count} {}", strings.mistake_kinds[kind as usize]))
+                .collect();
+            stats.push(Line::from(vec![
+                strings.mistakes.blue().bold(),
+                kinds.join(", ").white(),
+            ]));
+        }
+
+        let shift = shift::Penalty::of(
+            self.test.keys(),
+            self.test.shifted(),
+            self.config.typing_layout(),
+        );
+        if let Some(per_key) = shift.per_key() {
+            stats.push(Line::from(vec![
+                strings.shift.blue().bold(),
+                fill(
+                    strings.shift_penalty,
+                    &[
+                        &format!("{per_key:+.0}"),
+                        &self.locale.seconds(shift.lost_ms() / 1000., 1),
+                    ],
+                )
+                .white(),
+            ]));
+        }
+
+        if let Some(reason) = self.test.invalid() {
+            stats.push(Line::from(
+                fill(strings.invalid, &[&reason]).fg(self.bad()).bold(),
+            ));
+        }
+
+        // More than half of the errors being the wrong case almost always means Caps Lock
+        if self.test.case_errors() >= CAPS_LOCK_STREAK as u32
+            && self.test.case_errors() * 2 > self.test.incorrect() + self.test.corrected()
+        {
+            stats.push(Line::from(strings.case_errors.yellow()));
+        }
+
+        stats.push(Line::from(""));
+        stats.push(Line::from(strings.splits.blue().bold()));
+
+        for (line, (split, line_wpm)) in self
+            .test
+            .splits()
+            .iter()
+            .zip(self.test.line_wpms())
+            .enumerate()
+        {
+            stats.push(Line::from(vec![
+                format!("  {} {:>2}: ", strings.line, line + 1).gray(),
+                self.locale.seconds(split.as_secs_f32(), 1).white(),
+                format!(" {:.0} {}", line_wpm, self.test.unit()).dark_gray(),
+            ]));
+        }
+
+        Paragraph::new(stats).block(block).render(area, buf);
+    }
+
+    /// Best burst of all time, this test's included.
+    fn peak_burst(&self) -> f32 {
+        history::peak_burst_in(&self.history, self.test.metric())
+            .unwrap_or(0.)
+            .max(self.test.burst())
+    }
+
+    fn render_best(&self, duration: Duration, area: Rect, buf: &mut Buffer) {
+        let strings = self.strings;
+        let block = Block::bordered()
+            .title(strings.best_title.bold())
+            .border_set(border::ROUNDED);
+
+        let current = self.test.speed(duration);
+        let mut lines = match self.previous_best {
+            _ if self.gate.is_some() => self.gate_status(current),
+            _ if self.homework.is_some() => self.homework_status(),
+            _ if self.lesson.is_some() => self.lesson_status(current),
+            _ if self.session.is_some() => self.session_status(),
+            _ if self.rhythm => self.rhythm_status(),
+            _ if self.error_budget.is_some() => self.budget_status(),
+            _ if self.endurance.is_some() => self.endurance_status(current),
+            _ if self.challenge.is_some() => self.head_to_head(duration),
+            _ if self.test.invalid().is_some() => {
+                vec![Line::from(strings.not_counted.fg(self.bad()).bold())]
+            }
+            Some(best) if current > best => vec![
+                Line::from(strings.new_best.fg(self.good()).bold()),
+                Line::from(vec![
+                    strings.previous.blue().bold(),
+                    format!("{:.0}", best).white(),
+                    format!(" (+{:.0})", current - best).fg(self.good()),
+                ]),
+            ],
+            Some(best) => vec![
+                Line::from(vec![
+                    strings.best.blue().bold(),
+                    format!("{:.0}", best).white(),
+                ]),
+                Line::from(vec![
+                    strings.delta.blue().bold(),
+                    format!("{:.0}", current - best).fg(self.bad()),
+                ]),
+            ],
+            None => vec![Line::from(strings.first_test.fg(self.good()).bold())],
+        };
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(strings.worst_words.blue().bold()));
+
+        let mut worst: Vec<(&String, &u32)> = self.test.word_errors().iter().collect();
+        worst.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+
+        if worst.is_empty() {
+            lines.push(Line::from(format!("  {}", strings.flawless).gray()));
+        }
+        for (word, errors) in worst.into_iter().take(WORST_WORDS_SHOWN) {
+            lines.push(Line::from(vec![
+                format!("  {word} ").white(),
+                format!("({errors})").fg(self.bad()),
+            ]));
+        }
+
+        if let Some(code) = self.challenge_code() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(strings.challenge_friend.blue().bold()));
+            lines.push(Line::from(vec!["  wpm challenge ".gray(), code.white()]));
+        }
+
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+
+    /// This result next to the one of the challenge, the better of each in green.
+    fn head_to_head(&self, duration: Duration) -> Vec<Line<'static>> {
+        let Some(challenge) = self.challenge else {
+            return Vec::new();
+        };
+
+        let row = |label: &str, mine: f32, theirs: f32, unit: &str, higher_wins: bool| {
+            let (mine_text, theirs_text) =
+                (format!("{mine:.0}{unit}"), format!("{theirs:.0}{unit}"));
+            let won = if higher_wins {
+                mine > theirs
+            } else {
+                mine < theirs
+            };
+            Line::from(vec![
+                format!("{label:<6}").blue().bold(),
+                if won {
+                    format!("{mine_text:>6}").fg(self.good()).bold()
+                } else {
+                    format!("{mine_text:>6}").white()
+                },
+                if won {
+                    format!("{theirs_text:>7}").white()
+                } else {
+                    format!("{theirs_text:>7}").fg(self.good()).bold()
+                },
+            ])
+        };
+
+        let strings = self.strings;
+        let wpm = self.test.speed(duration);
+        vec![
+            if wpm > challenge.wpm {
+                Line::from(strings.challenge_won.fg(self.good()).bold())
+            } else {
+                Line::from(strings.challenge_lost.fg(self.bad()).bold())
+            },
+            Line::from(format!("{:6}{:>6}{:>7}", "", strings.me, strings.them).gray()),
+            row(strings.short_speed, wpm, challenge.wpm, "", true),
+            row(
+                strings.short_accuracy,
+                self.test.accuracy(),
+                challenge.accuracy,
+                "%",
+                true,
+            ),
+            row(
+                strings.short_time,
+                duration.as_secs_f32(),
+                challenge.seconds,
+                "s",
+                false,
+            ),
+        ]
+    }
+
+    /// Whether this attempt opened the gate, and what is left to do.
+    fn gate_status(&self, current: f32) -> Vec<Line<'static>> {
+        let Some(gate) = self.gate else {
+            return Vec::new();
+        };
+        let strings = self.strings;
+
+        let verdict = if gate.passed {
+            Line::from(strings.gate_passed.fg(self.good()).bold())
+        } else if self.test.invalid().is_some() {
+            Line::from(strings.not_counted.fg(self.bad()).bold())
+        } else {
+            Line::from(
+                fill(strings.short_of, &[&format!("{:.0}", gate.wpm - current)])
+                    .fg(self.bad())
+                    .bold(),
+            )
+        };
+        let next = if gate.passed {
+            format!("  {}", strings.gate_go_on).gray()
+        } else if gate.is_over() {
+            format!("  {}", strings.no_attempts_left).gray()
+        } else {
+            format!("  {}", strings.tab_try_again).gray()
+        };
+
+        vec![
+            verdict,
+            Line::from(vec![
+                strings.needed.blue().bold(),
+                format!("{:.0}", gate.wpm).white(),
+                fill(strings.attempt_of, &[&gate.attempts, &gate.max_attempts]).gray(),
+            ]),
+            Line::from(next),
+        ]
+    }
+
+    /// Whether this attempt passed the text of the assignment, and what comes next.
+    fn homework_status(&self) -> Vec<Line<'static>> {
+        let Some(homework) = &self.homework else {
+            return Vec::new();
+        };
+        let assignment = &homework.assignment;
+        let strings = self.strings;
+
+        let verdict = if homework.passed {
+            Line::from(strings.text_passed.fg(self.good()).bold())
+        } else if self.test.invalid().is_some() {
+            Line::from(strings.not_counted.fg(self.bad()).bold())
+        } else {
+            Line::from(
+                fill(
+                    strings.accuracy_needed,
+                    &[&format!("{:.0}", assignment.min_accuracy)],
+                )
+                .fg(self.bad())
+                .bold(),
+            )
+        };
+        let next = if !homework.text_over() {
+            format!("  {}", strings.tab_try_again).gray()
+        } else if homework.text + 1 < assignment.texts.len() {
+            format!("  {}", strings.tab_next_text).gray()
+        } else {
+            format!("  {}", strings.tab_finish).gray()
+        };
+
+        vec![
+            verdict,
+            Line::from(vec![
+                strings.text.blue().bold(),
+                fill(
+                    strings.text_of,
+                    &[&(homework.text + 1), &assignment.texts.len()],
+                )
+                .white(),
+                fill(
+                    strings.attempt_of,
+                    &[&homework.attempts, &assignment.attempts],
+                )
+                .gray(),
+            ]),
+            Line::from(next),
+        ]
+    }
+
+    /// How much the run slowed down, and the best one as long.
+    fn endurance_status(&self, current: f32) -> Vec<Line<'static>> {
+        let Some(minutes) = self.endurance else {
+            return Vec::new();
+        };
+        let strings = self.strings;
+
+        let verdict = match self.test.invalid() {
+            Some(_) => Line::from(strings.not_counted.fg(self.bad()).bold()),
+            None => Line::from(strings.endurance_done.fg(self.good()).bold()),
+        };
+        let fatigue = match endurance::Run::of(&self.test, minutes).fatigue() {
+            Some(change) => {
+                let percent = self.locale.percent(change, 0);
+                let percent = if change > 0. {
+                    format!("+{percent}")
+                } else {
+                    percent
+                };
+                fill(strings.fatigue_of, &[&percent]).white()
+            }
+            None => strings.too_short.gray(),
+        };
+        let best = endurance::best(&self.endurance_runs, minutes).unwrap_or(0.);
+        vec![
+            verdict,
+            Line::from(vec![strings.fatigue.blue().bold(), fatigue]),
+            Line::from(vec![
+                strings.best_run.blue().bold(),
+                format!("{:.0} {}", best.max(current), self.test.unit()).white(),
+            ]),
+        ]
+    }
+
+    /// How the test went, the session so far, and what comes next.
+    fn session_status(&self) -> Vec<Line<'static>> {
+        let Some(session) = &self.session else {
+            return Vec::new();
+        };
+        let strings = self.strings;
+
+        let verdict = if self.over_budget() {
+            Line::from(strings.budget_blown.fg(self.bad()).bold())
+        } else if self.test.invalid().is_some() {
+            Line::from(strings.not_counted.fg(self.bad()).bold())
+        } else if session.is_over() {
+            Line::from(strings.session_done.fg(self.good()).bold())
+        } else {
+            Line::from(
+                fill(
+                    strings.step_done,
+                    &[&(session.step + 1), &session.tests.len()],
+                )
+                .fg(self.good())
+                .bold(),
+            )
+        };
+        let report = session.report();
+        let next = if session.is_over() {
+            format!("  {}", strings.tab_finish).gray()
+        } else {
+            format!("  {}", strings.tab_next_step).gray()
+        };
+
+        vec![
+            verdict,
+            Line::from(vec![
+                strings.session.blue().bold(),
+                fill(
+                    strings.session_of,
+                    &[
+                        &report.tests,
+                        &format!("{:.0} {}", report.wpm, self.test.unit()),
+                        &self.locale.percent(report.accuracy, 1),
+                        &self.locale.seconds(report.seconds, 0),
+                    ],
+                )
+                .white(),
+            ]),
+            Line::from(next),
+        ]
+    }
+
+    /// How evenly the keys came, against what beat, and how many missed it.
+    fn rhythm_status(&self) -> Vec<Line<'static>> {
+        let strings = self.strings;
+        let Some(cadence) = self.cadence() else {
+            return vec![Line::from(strings.too_short.gray())];
+        };
+
+        vec![
+            Line::from(vec![
+                strings.rhythm.blue().bold(),
+                self.locale.percent(cadence.score(), 1).white().bold(),
+            ]),
+            Line::from(vec![
+                strings.beat.blue().bold(),
+                fill(strings.beat_of, &[&format!("{:.0}", cadence.beat_ms())]).white(),
+            ]),
+            Line::from(vec![
+                strings.off_beat.blue().bold(),
+                fill(
+                    strings.off_beat_of,
+                    &[&cadence.off_beat(), &cadence.intervals().len()],
+                )
+                .white(),
+            ]),
+        ]
+    }
+
+    /// Whether this attempt kept to the error budget, and the longest clean streak so far.
+    fn budget_status(&self) -> Vec<Line<'static>> {
+        let Some(budget) = self.error_budget else {
+            return Vec::new();
+        };
+        let strings = self.strings;
+
+        let verdict = if self.over_budget() {
+            Line::from(strings.budget_blown.fg(self.bad()).bold())
+        } else if self.test.invalid().is_some() {
+            Line::from(strings.not_counted.fg(self.bad()).bold())
+        } else {
+            Line::from(strings.budget_kept.fg(self.good()).bold())
+        };
+        let longest = history::longest_clean_streak(&self.history).max(self.test.clean_streak());
+        vec![
+            verdict,
+            Line::from(vec![
+                strings.mistakes.blue().bold(),
+                fill(strings.mistakes_of, &[&self.mistakes(), &budget]).white(),
+            ]),
+            Line::from(vec![
+                strings.best_streak.blue().bold(),
+                format!("{longest}").white(),
+            ]),
+        ]
+    }
+
+    /// Whether this attempt passed the lesson, and what comes next.
+    fn lesson_status(&self, current: f32) -> Vec<Line<'static>> {
+        let Some(index) = self.lesson else {
+            return Vec::new();
+        };
+        let lesson = LESSONS[index];
+        let strings = self.strings;
+
+        let verdict = if self.lesson_passed {
+            Line::from(strings.lesson_passed.fg(self.good()).bold())
+        } else if self.test.invalid().is_some() {
+            Line::from(strings.not_counted.fg(self.bad()).bold())
+        } else if current < lesson.wpm {
+            Line::from(
+                fill(strings.short_of, &[&format!("{:.0}", lesson.wpm - current)])
+                    .fg(self.bad())
+                    .bold(),
+            )
+        } else {
+            Line::from(
+                fill(
+                    strings.accuracy_needed,
+                    &[&format!("{:.0}", lesson.accuracy)],
+                )
+                .fg(self.bad())
+                .bold(),
+            )
+        };
+        let next = if !self.lesson_passed {
+            format!("  {}", strings.tab_try_again).gray()
+        } else if index + 1 < LESSONS.len() {
+            format!("  {}", strings.tab_next_lesson).gray()
+        } else {
+            format!("  {}", strings.tab_finish).gray()
+        };
+
+        vec![
+            verdict,
+            Line::from(vec![
+                strings.needed.blue().bold(),
+                format!("{:.0} {}, ", lesson.wpm, self.test.unit()).white(),
+                self.locale.percent(lesson.accuracy, 0).white(),
+            ]),
+            Line::from(
+                format!(
+                    "  {}",
+                    fill(strings.lesson_of, &[&(index + 1), &LESSONS.len()])
+                )
+                .gray(),
+            ),
+            Line::from(next),
+        ]
+    }
+}
+
+/// A box of the given size in the middle of `area`, cut to fit it.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let (width, height) = (width.min(area.width), height.min(area.height));
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
@@ -0,0 +1,798 @@
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    Terminal,
+    backend::TestBackend,
+    layout::Rect,
+    style::{Color, Modifier},
+};
+use std::{collections::VecDeque, env, fs, io, path::PathBuf, time::Duration};
+use wpm_core::{
+    achievements::{Achievement, Unlocked},
+    assignment::Assignment,
+    course::Progress,
+    diff,
+    engine::Rules,
+    history,
+    latency::Histogram,
+    leaderboard::Standing,
+    rating::{Ratings, Tag},
+    sim::Simulation,
+};
+
+use super::view;
+use crate::{
+    app::{Action, App, LiveStats, Msg},
+    config::{Chunks, Colors, Config, ErrorCue, Palette, Segment},
+    gate::Gate,
+    homework::Homework,
+    i18n::{FR, Locale, UiLanguage},
+    input::InputSource,
+    session::{Session, Step, StepKind},
+};
+
+// A short quote that fits on two lines
+const QUOTE_ID: u32 = 1;
+const QUOTE: &str = "You have the power to heal your life, and you need to know that.";
+const WIDTH: u16 = 100;
+const HEIGHT: u16 = 30;
+
+/// An app on a fixed quote, with nothing that depends on the clock or the user's files.
+fn app() -> App {
+    let config = Config {
+        animations: false,
+        // The timer and live speed would change between runs
+        status_bar: vec![Segment::Mode, Segment::Language],
+        live_stats: false,
+        // NO_COLOR in the environment would change the styles
+        colors: Colors::Always,
+        // And so would the locale
+        ui_language: UiLanguage::En,
+        locale: "C".to_string(),
+        ..Config::default()
+    };
+
+    let mut app = App::new(config);
+    app.history = Vec::new();
+    app.latency = Histogram::default();
+    app.achievements = Unlocked::default();
+    app.leaderboard = Vec::new();
+    app.ratings = Ratings::default();
+    app.storage = None;
+    let quote = app.library.get(QUOTE_ID).expect("quote to exist");
+    app.start_quote(quote);
+    app
+}
+
+/// A simulation of typing the text of the app, with the time between keys in hand.
+fn simulation(app: &App) -> Simulation {
+    let lines = app.test.lines().iter().map(|line| line.concat()).collect();
+    Simulation::with_lines(lines, app.config.rules())
+}
+
+/// Types the whole quote a key every `ms` milliseconds, through to the results.
+fn finish(app: &mut App, ms: u64) {
+    let mut sim = simulation(app);
+    sim.type_text(QUOTE, ms);
+    app.test = sim.into_test();
+}
+
+/// Messages given in advance, then a quit once they run out.
+struct ScriptedInput(VecDeque<Msg>);
+
+impl InputSource for ScriptedInput {
+    fn next_msg(&mut self, _app: &mut App, _timeout: Duration) -> io::Result<Option<Msg>> {
+        Ok(Some(self.0.pop_front().unwrap_or(Msg::Quit)))
+    }
+}
+
+fn render(app: &App) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|frame| view(app, frame)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    (0..HEIGHT)
+        .map(|y| {
+            let row: String = (0..WIDTH).map(|x| buffer[(x, y)].symbol()).collect();
+            row.trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// What the mouse does at a column and row of a screen of the size `render` draws.
+fn mouse(app: &mut App, kind: MouseEventKind, column: u16, row: u16) -> Option<Msg> {
+    app.area = Rect::new(0, 0, WIDTH, HEIGHT);
+    app.mouse_msg(MouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+/// The middle of where `text` first shows on the screen.
+fn position(app: &App, text: &str) -> (u16, u16) {
+    let screen = render(app);
+    let (row, line) = screen
+        .lines()
+        .enumerate()
+        .find(|(_, line)| line.contains(text))
+        .unwrap_or_else(|| panic!("{text} is not on the screen:\n{screen}"));
+    let column = line[..line.find(text).unwrap()].chars().count() + text.chars().count() / 2;
+    (column as u16, row as u16)
+}
+
+/// A click on `text`, to check that hitboxes are where the screen draws what they stand for.
+fn click(app: &mut App, text: &str) -> Option<Msg> {
+    let (column, row) = position(app, text);
+    mouse(app, MouseEventKind::Down(MouseButton::Left), column, row)
+}
+
+/// Compares the screen to the one saved in `snapshots/`. Set `UPDATE_SNAPSHOTS` to save it
+/// again after an intended change.
+fn assert_snapshot(name: &str, app: &App) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/ui/snapshots")
+        .join(format!("{name}.txt"));
+    let screen = render(app);
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() || !path.exists() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, screen + "\n").unwrap();
+        return;
+    }
+
+    let saved = fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        screen,
+        saved.trim_end_matches('\n'),
+        "{name} screen changed, run with UPDATE_SNAPSHOTS=1 if it is intended"
+    );
+}
+
+#[test]
+fn start_screen() {
+    assert_snapshot("start", &app());
+}
+
+#[test]
+fn typing_screen() {
+    let mut app = app();
+    for char in "You have teh".chars() {
+        app.update(Msg::Type(char));
+    }
+
+    assert_snapshot("typing", &app);
+}
+
+#[test]
+fn mistakes_take_the_palette_and_cue() {
+    let mut app = app();
+    app.config.palette = Palette::Deuteranopia;
+    app.config.error_cue = ErrorCue::Underline;
+    for char in "Yx".chars() {
+        app.update(Msg::Type(char));
+    }
+
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|frame| view(&app, frame)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let mistakes: Vec<_> = buffer
+        .content
+        .iter()
+        .filter(|cell| cell.bg == Palette::Deuteranopia.bad())
+        .collect();
+    assert_eq!(mistakes.len(), 1);
+    assert_eq!(mistakes[0].symbol(), "o");
+    assert!(mistakes[0].modifier.contains(Modifier::UNDERLINED));
+}
+
+#[test]
+fn replays_mark_where_the_typist_paused() {
+    let mut app = app();
+    let mut sim = simulation(&app);
+    sim.type_text("You ", 100).wait(800).type_text("have", 100);
+    app.load_replay(sim.test().replay(QUOTE_ID));
+    for key in "You have".chars() {
+        app.test.play_key(key);
+    }
+
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|frame| view(&app, frame)).unwrap();
+    let marked: Vec<_> = terminal
+        .backend()
+        .buffer()
+        .content
+        .iter()
+        .filter(|cell| cell.modifier.contains(Modifier::UNDERLINED) && cell.fg == Color::Yellow)
+        .collect();
+    assert_eq!(marked.len(), 1);
+    assert_eq!(marked[0].symbol(), "h");
+}
+
+#[test]
+fn monochrome_uses_attributes_only() {
+    let mut app = app();
+    app.monochrome = true;
+    for char in "Yx".chars() {
+        app.update(Msg::Type(char));
+    }
+
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|frame| view(&app, frame)).unwrap();
+    let buffer = terminal.backend().buffer();
+    assert!(
+        buffer
+            .content
+            .iter()
+            .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset)
+    );
+    // The mistake is reversed from its background, and underlined, the text left to type dimmed
+    let at = buffer
+        .content
+        .iter()
+        .position(|cell| cell.modifier.contains(Modifier::REVERSED))
+        .unwrap();
+    let (mistake, next) = (&buffer.content[at], &buffer.content[at + 1]);
+    assert_eq!(mistake.symbol(), "o");
+    assert!(mistake.modifier.contains(Modifier::UNDERLINED));
+    assert_eq!(next.symbol(), "u");
+    assert!(next.modifier.contains(Modifier::DIM));
+}
+
+#[test]
+fn reduced_motion_holds_numbers_until_the_line_ends() {
+    let mut app = app();
+    app.config.reduced_motion = true;
+    app.update(Msg::Restart);
+
+    let first_line = app.test.lines()[0].concat();
+    for char in first_line.chars().take(5) {
+        app.update(Msg::Type(char));
+    }
+    assert_eq!(app.live_stats(), LiveStats::default());
+
+    for char in first_line.chars().skip(5) {
+        app.update(Msg::Type(char));
+    }
+    let line_end = app.live_stats();
+    assert_eq!(line_end.correct, first_line.chars().count() as u32);
+    app.update(Msg::Type('x'));
+    assert_eq!(app.live_stats(), line_end);
+}
+
+#[test]
+fn interface_follows_the_language() {
+    let mut app = app();
+    app.strings = &FR;
+    let screen = render(&app);
+    assert!(screen.contains(" Test de frappe "), "{screen}");
+    assert!(screen.contains("  Longueur : "), "{screen}");
+
+    for char in app.test.lines()[0].concat().chars() {
+        app.update(Msg::Type(char));
+    }
+    for char in app.test.lines()[1].concat().chars() {
+        app.update(Msg::Type(char));
+    }
+    let screen = render(&app);
+    assert!(screen.contains(" Test terminé "), "{screen}");
+}
+
+#[test]
+fn text_can_be_spaced_out_and_chunked() {
+    let mut app = app();
+    app.config.letter_spacing = 1;
+    app.config.line_gap = 1;
+    app.config.chunks = Chunks::Words;
+    let quote = app.library.get(QUOTE_ID).unwrap();
+    app.start_quote(quote);
+    assert!(app.test.lines().iter().all(|line| line.len() <= 25));
+
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|frame| view(&app, frame)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let screen = render(&app);
+    let rows: Vec<&str> = screen.lines().collect();
+    let first = rows
+        .iter()
+        .position(|row| row.contains("Y o u   h a v e"))
+        .unwrap();
+    // One blank line, then the next line of the text
+    assert_eq!(rows[first + 1].trim_matches([' ', '┃']), "");
+    assert!(!rows[first + 2].trim_matches([' ', '┃']).is_empty());
+
+    let x = rows[first].chars().position(|c| c == 'Y').unwrap() as u16;
+    assert_eq!(buffer[(x, first as u16)].fg, Color::Gray);
+    // "have" is the next word
+    assert_eq!(buffer[(x + 8, first as u16)].symbol(), "h");
+    assert_eq!(buffer[(x + 8, first as u16)].fg, Color::Cyan);
+}
+
+#[test]
+fn linear_mode_keeps_the_line_on_the_first_row() {
+    let mut app = app();
+    app.config.linear = true;
+    for char in "You hx".chars() {
+        app.update(Msg::Type(char));
+    }
+
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|frame| view(&app, frame)).unwrap();
+    let line = app.test.lines()[0].concat();
+    let screen = render(&app);
+    let rows: Vec<&str> = screen.lines().collect();
+    // Typed as typed, then the rest of the line, with the cursor after what was typed
+    assert_eq!(rows[0], format!("You hx{}", &line[6..]).trim_end());
+    assert_eq!(rows[HEIGHT as usize - 2], line.trim_end());
+    let cursor = terminal.get_cursor_position().unwrap();
+    assert_eq!((cursor.x, cursor.y), (6, 0));
+}
+
+#[test]
+fn runs_without_a_terminal() {
+    let mut app = app();
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    let mut input = ScriptedInput("You have teh".chars().map(Msg::Type).collect());
+    app.run(&mut terminal, &mut input).unwrap();
+
+    assert!(app.exit);
+    assert_snapshot("typing", &app);
+}
+
+#[test]
+fn results_screen() {
+    let mut app = app();
+    let mut sim = simulation(&app);
+    sim.type_text("You have the power to heal yuor life, ", 120)
+        .type_text("and you need to know that.", 100);
+    app.test = sim.into_test();
+
+    assert_snapshot("results", &app);
+}
+
+#[test]
+fn results_swap_the_chart_for_key_intervals() {
+    let mut app = app();
+    let mut sim = simulation(&app);
+    sim.type_text("You have the power to heal your life, ", 120)
+        .wait(2000)
+        .type_text("and you need to know that.", 120);
+    app.test = sim.into_test();
+
+    app.update(Msg::ToggleLatency);
+    let screen = render(&app);
+    assert!(screen.contains("Key intervals"), "{screen}");
+    assert!(
+        screen.contains("median 125 ms · pauses over 0.5 s: 1"),
+        "{screen}"
+    );
+
+    app.update(Msg::ToggleLatency);
+    assert!(render(&app).contains("WPM over time"));
+}
+
+#[test]
+fn results_menu_picks_what_comes_next() {
+    let mut app = app();
+    app.sinks = Vec::new();
+    for char in QUOTE.chars() {
+        app.update(Msg::Type(char));
+    }
+    assert!(app.test.is_done());
+    assert_eq!(app.action, Action::NewQuote);
+
+    app.update(Msg::NextAction);
+    assert_eq!(app.action, Action::ChangeLength);
+    app.update(Msg::NextAction);
+    app.update(Msg::Act);
+    assert!(render(&app).contains("Source: Meditations to Heal Your Life"));
+    app.update(Msg::ToggleDetails);
+    assert!(!render(&app).contains("Source:"));
+
+    app.update(Msg::PreviousAction);
+    app.update(Msg::Act);
+    assert_eq!(app.selected_group, 1);
+    assert!(app.test.is_untouched());
+}
+
+#[test]
+fn share_card_holds_the_challenge_as_a_qr_code() {
+    let mut app = app();
+    finish(&mut app, 100);
+    let code = app.challenge_code().expect("a code for a built-in quote");
+
+    app.update(Msg::ToggleShare);
+    let screen = render(&app);
+    assert!(
+        screen.contains(&format!("│ wpm challenge {code} │")),
+        "{screen}"
+    );
+    assert!(screen.contains("│ ██████"), "{screen}");
+    assert_eq!(app.share_card().lines().count(), 21);
+
+    app.update(Msg::ToggleShare);
+    assert!(!render(&app).contains("wpm challenge"));
+}
+
+#[test]
+fn rated_quotes_show_their_tags_and_can_be_picked_by_them() {
+    let mut app = app();
+    app.sinks = Vec::new();
+    finish(&mut app, 100);
+    assert!(render(&app).contains("Rate this quote:  fun <F>   boring <B>"));
+
+    app.update(Msg::Rate(Tag::Fun));
+    app.update(Msg::Rate(Tag::Hard));
+    // Boring in place of fun
+    app.update(Msg::Rate(Tag::Boring));
+    assert_eq!(app.tags(), [Tag::Boring, Tag::Hard]);
+
+    // Shown when the quote comes back
+    let quote = app.library.get(QUOTE_ID).unwrap().clone();
+    app.start_quote(&quote);
+    let screen = render(&app);
+    assert!(screen.contains("Rated: boring, hard"), "{screen}");
+
+    // The only quote rated hard of its length, picked every time
+    app.config.quote_filter = Some(Tag::Hard);
+    app.selected_group = app
+        .groups()
+        .iter()
+        .position(|group| group[0] < quote.length && quote.length < group[1])
+        .unwrap();
+    for _ in 0..5 {
+        app.new_quote();
+        assert_eq!(app.sentence_id, QUOTE_ID);
+    }
+}
+
+#[test]
+fn result_card_sums_up_the_test() {
+    let mut app = app();
+    finish(&mut app, 100);
+
+    let card = app.result_card();
+    let lines: Vec<&str> = card.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[1].ends_with("wpm · 100.0% accuracy · 6.3s"), "{card}");
+    assert_eq!(
+        lines[2],
+        format!("{} · {}", app.mode_label(), app.sentence_source)
+    );
+
+    // Left for the terminal to copy, not written anywhere yet
+    app.update(Msg::CopyResult);
+    assert_eq!(app.to_copy, Some(card));
+    assert!(!app.copied);
+}
+
+#[test]
+fn numbers_and_dates_follow_the_locale() {
+    let mut app = app();
+    app.locale = Locale::new("de_DE.UTF-8");
+    finish(&mut app, 100);
+
+    let card = app.result_card();
+    assert!(card.contains("100,0 % accuracy · 6,3 s"), "{card}");
+    let date = card.lines().next().unwrap().trim_start_matches("wpm · ");
+    assert_eq!(date.split('.').map(str::len).collect::<Vec<_>>(), [2, 2, 4]);
+
+    assert!(render(&app).contains("Accuracy: 100,0 %"));
+    assert_eq!(Locale::new("en_US").percent(97.46, 1), "97.5%");
+    assert_eq!(Locale::new("fr_FR").seconds(6.25, 2), "6,25 s");
+}
+
+#[test]
+fn gates_stop_tests_and_let_through_after_the_last_attempt() {
+    let mut app = app();
+    app.sinks = Vec::new();
+    app.gate = Some(Gate::new(200., Duration::ZERO, 1));
+
+    app.update(Msg::Type('x'));
+    app.update(Msg::Tick);
+    assert!(app.test.is_done());
+    let gate = app.gate.unwrap();
+    assert_eq!(gate.attempts, 1);
+    assert!(!gate.passed && gate.is_over());
+
+    app.update(Msg::NewQuote);
+    assert!(app.exit);
+}
+
+#[test]
+fn homework_moves_on_once_a_text_is_over() {
+    let mut app = app();
+    app.sinks = Vec::new();
+    let assignment = Assignment {
+        title: "Week 1".to_string(),
+        texts: vec!["ab cd".to_string(), "ef gh".to_string()],
+        rules: Rules::default(),
+        min_accuracy: 100.,
+        attempts: 1,
+        students: Default::default(),
+    };
+    app.start_homework(Homework::new(assignment, "ann".to_string()));
+    assert_eq!(app.sentence_source, "Week 1");
+
+    for key in "ab cd".chars() {
+        app.update(Msg::Type(key));
+    }
+    assert!(app.homework.as_ref().unwrap().passed);
+    app.update(Msg::NewQuote);
+    assert_eq!(app.test.lines()[0].concat(), "ef gh");
+
+    // Out of attempts on the last text
+    for key in "ef gx".chars() {
+        app.update(Msg::Type(key));
+    }
+    app.update(Msg::Restart);
+    assert!(app.exit);
+    assert_eq!(app.homework.unwrap().submission.attempts.len(), 2);
+}
+
+#[test]
+fn lessons_move_on_once_passed() {
+    let mut app = app();
+    app.sinks = Vec::new();
+    app.start_lesson(Progress::default(), 0);
+    assert_eq!(app.mode_label(), "lesson 1");
+
+    let text = app.test.lines().concat().concat();
+    assert!(text.chars().all(|c| "fj ".contains(c)), "{text}");
+    for key in text.trim_end().chars() {
+        app.update(Msg::Type(key));
+        // Keys this fast would not count
+        app.recent_keys.clear();
+    }
+    assert!(app.lesson_passed);
+    assert!(app.course.get(0).passed);
+
+    app.update(Msg::Restart);
+    assert_eq!(app.mode_label(), "lesson 2");
+    assert!(!app.lesson_passed);
+}
+
+#[test]
+fn error_budget_fails_the_test_once_spent() {
+    let mut app = app();
+    app.sinks = Vec::new();
+    app.error_budget = Some(1);
+    for key in "Yxu hxve".chars() {
+        app.update(Msg::Type(key));
+        app.recent_keys.clear();
+    }
+    assert!(app.test.is_done());
+    assert_eq!(app.test.invalid(), Some("over the error budget"));
+    assert!(app.history.is_empty());
+    assert!(render(&app).contains("Over the error budget"));
+}
+
+#[test]
+fn sessions_walk_through_the_plan() {
+    let mut app = app();
+    app.sinks = Vec::new();
+    let plan = [
+        Step {
+            mistakes: Some(0),
+            ..Step::new(StepKind::Accuracy, 1)
+        },
+        Step {
+            seconds: Some(0),
+            ..Step::new(StepKind::Timed, 1)
+        },
+    ];
+    app.start_session(Session::new(&plan, 0));
+    assert_eq!(app.mode_label(), "session 1/2 mistakes 0/0");
+
+    app.update(Msg::Type('§'));
+    assert!(app.test.is_done());
+    assert!(render(&app).contains("Over the error budget"));
+
+    app.update(Msg::NewQuote);
+    assert_eq!(app.mode_label(), "session 2/2 timed 0 s");
+    assert_eq!(app.error_budget, None);
+    let first = app.test.expected().unwrap().chars().next().unwrap();
+    app.update(Msg::Type(first));
+    app.update(Msg::Tick);
+    assert!(app.test.is_done());
+    assert!(render(&app).contains("Session over!"));
+    let report = app.session.as_ref().unwrap().report();
+    assert_eq!((report.tests, report.counted), (2, 1));
+
+    app.update(Msg::Restart);
+    assert!(app.exit);
+}
+
+#[test]
+fn rhythm_mode_draws_the_cadence_and_scores_it() {
+    let mut app = app();
+    app.rhythm = true;
+    let mut sim = simulation(&app);
+    sim.type_text("You have ", 150).type_text("the", 300);
+    app.test = sim.into_test();
+    assert_eq!(app.mode_label(), "rhythm");
+    let screen = render(&app);
+    // Eight even intervals, then three twice as long, against their average
+    assert!(screen.contains("Rhythm: ▃▃▃▃▃▃▃▃▆▆▆ 69%"), "{screen}");
+
+    finish(&mut app, 150);
+    let screen = render(&app);
+    assert!(screen.contains("Rhythm: 100.0%"), "{screen}");
+    assert!(screen.contains("Off the beat: 0 of 63 keys"), "{screen}");
+}
+
+#[test]
+fn achievements_unlock_with_a_toast() {
+    let mut app = app();
+    app.sinks = Vec::new();
+    app.history = (0..99)
+        .map(|_| history::Record::now(QUOTE_ID, 0, 50., 95., 20, 30.))
+        .collect();
+    let mut sim = simulation(&app);
+    sim.type_text(QUOTE.trim_end_matches('.'), 150);
+    app.test = sim.into_test();
+    // The last key goes through the app, to finish the test there
+    app.update(Msg::Type('.'));
+    assert_eq!(app.unlocked, [Achievement::Tests]);
+    assert!(render(&app).contains("★ Achievement unlocked: Regular"));
+
+    app.update(Msg::ToggleAchievements);
+    let screen = render(&app);
+    assert!(
+        screen.contains("★ Regular - 100 tests, unlocked on"),
+        "{screen}"
+    );
+    assert!(screen.contains("☆ Century - a test at 100 WPM or more, locked"));
+}
+
+#[test]
+fn info_panel_toggles() {
+    let mut app = app();
+    app.update(Msg::ToggleInfo);
+
+    assert_snapshot("no_info", &app);
+}
+
+#[test]
+fn leaderboard_screen() {
+    let mut app = app();
+    app.leaderboard = [("ada", 112., 98.), ("linus", 87., 80.)]
+        .into_iter()
+        .map(|(name, best_wpm, average_wpm)| Standing {
+            name: name.to_string(),
+            best_wpm,
+            average_wpm,
+            tests: 24,
+        })
+        .collect();
+    app.update(Msg::ToggleLeaderboard);
+
+    assert_snapshot("leaderboard", &app);
+}
+
+#[test]
+fn history_lists_past_tests_and_opens_them() {
+    let mut app = app();
+    app.history = [(1_700_000_000, 64.), (1_700_086_400, 72.)]
+        .into_iter()
+        .map(|(timestamp, wpm)| history::Record {
+            timestamp,
+            category: "quotes".to_string(),
+            ..history::Record::now(QUOTE_ID, 0, wpm, 96.5, 12, 30.)
+        })
+        .collect();
+    app.update(Msg::ToggleHistory);
+    let screen = render(&app);
+    assert!(screen.contains(" History "), "{screen}");
+    let latest = screen.find("72 wpm").expect("the latest test to be listed");
+    assert!(latest < screen.find("64 wpm").unwrap(), "{screen}");
+
+    app.update(Msg::NextRow);
+    app.update(Msg::NextRow);
+    assert_eq!(app.history_row, 1);
+    // No replay was kept of these
+    app.update(Msg::OpenRow);
+    assert!(render(&app).contains("No replay of this test"));
+    app.update(Msg::OpenRow);
+    assert_eq!(app.history_detail, None);
+}
+
+#[test]
+fn history_detail_marks_the_mistakes() {
+    let mut app = app();
+    app.history = vec![history::Record::now(QUOTE_ID, 0, 60., 90., 12, 30.)];
+    let mut sim = simulation(&app);
+    sim.type_text("Yoy have", 100);
+    app.update(Msg::ToggleHistory);
+    app.history_detail = Some(diff::of(&sim.test().replay(QUOTE_ID)));
+
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|frame| view(&app, frame)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let struck: String = buffer
+        .content
+        .iter()
+        .filter(|cell| cell.modifier.contains(Modifier::CROSSED_OUT))
+        .map(|cell| cell.symbol())
+        .collect();
+    assert_eq!(struck, "y");
+    let screen = render(&app);
+    assert!(
+        screen.contains("1 left wrong, 0 fixed, 0 skipped, 0 extra"),
+        "{screen}"
+    );
+}
+
+#[test]
+fn lengths_can_be_clicked() {
+    let mut app = app();
+    assert_eq!(click(&mut app, "101-300"), Some(Msg::SelectGroup(1)));
+    assert_eq!(click(&mut app, "601-9999"), Some(Msg::SelectGroup(3)));
+    assert_eq!(click(&mut app, "Length:"), None);
+    assert_eq!(click(&mut app, "power"), None);
+
+    // Not once the test is going
+    let (column, row) = position(&app, "101-300");
+    app.update(Msg::Type('Y'));
+    let left = MouseEventKind::Down(MouseButton::Left);
+    assert_eq!(mouse(&mut app, left, column, row), None);
+}
+
+#[test]
+fn results_menu_and_tags_can_be_clicked() {
+    let mut app = app();
+    finish(&mut app, 100);
+    assert_eq!(click(&mut app, "Retry"), Some(Msg::Restart));
+    assert_eq!(click(&mut app, "New quote"), Some(Msg::NewQuote));
+    assert_eq!(click(&mut app, "<M>"), Some(Msg::ChangeLength));
+    assert_eq!(click(&mut app, "Quit"), Some(Msg::Quit));
+    assert_eq!(click(&mut app, "boring"), Some(Msg::Rate(Tag::Boring)));
+    assert_eq!(click(&mut app, "<H>"), Some(Msg::Rate(Tag::Hard)));
+    assert_eq!(click(&mut app, "Rate this quote"), None);
+
+    // Homework can't be rated, the menu is where it is drawn all the same
+    app.sinks = Vec::new();
+    let assignment = Assignment {
+        title: "Week 1".to_string(),
+        texts: vec!["ab".to_string()],
+        rules: Rules::default(),
+        min_accuracy: 100.,
+        attempts: 1,
+        students: Default::default(),
+    };
+    app.start_homework(Homework::new(assignment, "ann".to_string()));
+    app.update(Msg::Type('a'));
+    app.update(Msg::Type('b'));
+    assert!(!render(&app).contains("Rate this quote"));
+    assert_eq!(click(&mut app, "Details"), Some(Msg::ToggleDetails));
+}
+
+#[test]
+fn history_rows_can_be_clicked_and_scrolled() {
+    let mut app = app();
+    app.history = (0..40)
+        .map(|wpm| history::Record {
+            timestamp: 1_700_000_000,
+            ..history::Record::now(QUOTE_ID, 0, wpm as f32, 95., 12, 30.)
+        })
+        .collect();
+    app.update(Msg::ToggleHistory);
+    // The latest first
+    assert_eq!(click(&mut app, " 38 wpm"), Some(Msg::SelectRow(1)));
+    assert_eq!(click(&mut app, "Accuracy"), None);
+
+    let down = mouse(&mut app, MouseEventKind::ScrollDown, 0, 0);
+    assert_eq!(down, Some(Msg::NextRow));
+    for _ in 0..30 {
+        app.update(Msg::NextRow);
+    }
+    // Scrolled to keep the selected row, the 31st, at the bottom
+    assert!(!render(&app).contains(" 39 wpm"));
+    assert_eq!(click(&mut app, " 9 wpm"), Some(Msg::SelectRow(30)));
+
+    app.update(Msg::SelectRow(30));
+    assert_eq!(app.history_detail, Some(Vec::new()));
+    assert_eq!(mouse(&mut app, MouseEventKind::ScrollUp, 0, 0), None);
+    assert_eq!(click(&mut app, "No replay"), None);
+}
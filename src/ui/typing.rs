@@ -0,0 +1,585 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Stylize,
+    symbols::border,
+    text::{Line, Span, Text},
+    widgets::{Block, Paragraph, Widget, Wrap},
+};
+use tui_big_text::{BigText, PixelSize};
+use unicode_segmentation::UnicodeSegmentation;
+use wpm_core::{content, engine::SKIPPED, history, rhythm, xp};
+
+use super::{get_wpm, group_label};
+use crate::{
+    app::App,
+    config::{Chunks, Ghost},
+    i18n::fill,
+    layout::TestLayout,
+};
+
+// Intervals shown on the cadence bar, the last ones typed
+const CADENCE_BARS: usize = 24;
+// Heights of the cadence bar, the beat being the middle one
+const BAR_HEIGHTS: [char; 7] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇'];
+
+// Terminal cells taken by one character of the large text
+const LARGE_CHAR_WIDTH: u16 = 4;
+const LARGE_CHAR_HEIGHT: u16 = 4;
+
+impl App {
+    /// The screen shown while typing, or watching a replay.
+    pub(super) fn render_typing(&self, area: Rect, buf: &mut Buffer) {
+        let strings = self.strings;
+        let title = if let Some(playback) = &self.playback {
+            Line::from(
+                fill(strings.replay_title, &[&playback.speed()])
+                    .magenta()
+                    .bold(),
+            )
+        } else if self.caps_lock_suspected() {
+            Line::from(strings.caps_lock_warning.black().on_yellow().bold())
+        } else {
+            Line::from(strings.typing_title.bold())
+        };
+        let instructions = if let Some(playback) = &self.playback {
+            Line::from(vec![
+                if playback.is_paused() {
+                    strings.resume.into()
+                } else {
+                    strings.pause.into()
+                },
+                "<SPACE>".blue().bold(),
+                strings.speed.into(),
+                "← →".blue().bold(),
+                strings.quit.into(),
+                "<ESC> ".blue().bold(),
+            ])
+        } else {
+            Line::from(vec![
+                strings.start_typing.into(),
+                strings.start_key.blue().bold(),
+                strings.change_length.into(),
+                "← →".blue().bold(),
+                strings.new_quote.into(),
+                "<TAB>".blue().bold(),
+                strings.restart.into(),
+                "<^R>".blue().bold(),
+                strings.skip.into(),
+                "<^N>".blue().bold(),
+                strings.info.into(),
+                "<F1>".blue().bold(),
+                strings.quit.into(),
+                "<ESC> ".blue().bold(),
+            ])
+        };
+        let block = Block::bordered()
+            .title(title.centered())
+            .title_bottom(instructions.centered())
+            .border_set(border::THICK);
+
+        block.render(area, buf);
+        let layout = TestLayout::new(self.config.layout, area, self.show_info);
+        let current_line = self.test.current_line();
+        let spacing = self.letter_spacing(layout.quote.width);
+
+        // ROW 1: Length selection || previous text if typing
+        if self.test.is_untouched() {
+            let mut length_spans: Vec<Span> = Vec::with_capacity(1 + self.groups().len());
+            length_spans.push(strings.length.blue().bold());
+
+            for gid in 0..self.groups().len() {
+                length_spans.push(if self.selected_group == gid {
+                    group_label(&self.groups()[gid]).underlined().bold().green()
+                } else {
+                    group_label(&self.groups()[gid]).into()
+                })
+            }
+
+            let length_text = Line::from(length_spans);
+            Paragraph::new(length_text).render(layout.header, buf);
+
+            let level = xp::Level::of(xp::total(&self.history));
+            let level = fill(strings.level, &[&level.level, &level.xp, &level.needed]);
+            Paragraph::new(Line::from(level.yellow()).right_aligned()).render(layout.header, buf);
+        } else {
+            let mut lines: Vec<Line> = Vec::with_capacity(2);
+
+            if current_line >= 2 {
+                lines.push(
+                    Line::from(spaced(self.completed_line(current_line - 2), spacing)).centered(),
+                )
+            }
+            if current_line >= 1 {
+                let mut spans = spaced(self.completed_line(current_line - 1), spacing);
+                spans.extend(self.line_popup());
+                lines.push(Line::from(spans).centered())
+            }
+
+            if lines.len() == 1 {
+                lines.insert(0, Line::from(""))
+            }
+
+            Paragraph::new(Text::from(lines).centered()).render(layout.header, buf);
+        }
+
+        // Row 2: Quote text (centered)
+        let mut quote_spans: Vec<Span> = Vec::with_capacity(self.test.typing().len() + 1);
+
+        let line = &self.test.lines()[current_line];
+        // Extras are kept in typing order, so they are walked along with the line
+        let mut extras = self.test.extras().iter().peekable();
+        for (cid, typed) in self.test.typing().iter().enumerate() {
+            if let Some((_, extra)) = extras.next_if(|(pos, _)| *pos == cid) {
+                quote_spans.push(self.extra(extra));
+            }
+            let expected = line.get(cid).map(String::as_str).unwrap_or(" ");
+            if self.test.matches(typed, expected) {
+                quote_spans.push(self.pause(typed.as_str().into(), current_line, cid));
+            } else {
+                quote_spans.push(self.mistake(expected.bg(self.bad())));
+                quote_spans.extend(self.ghost(typed));
+            }
+        }
+
+        if let Some((_, extra)) = extras.next() {
+            quote_spans.push(self.extra(extra));
+        }
+
+        let progress = self.animations.intro_progress();
+        quote_spans.extend(self.upcoming(line, self.test.typing().len(), progress));
+
+        let quote_area = if self.config.large_text {
+            self.render_large_line(layout.quote, buf)
+        } else {
+            layout.quote
+        };
+
+        let mut all: Vec<Line> = Vec::with_capacity(quote_area.height as usize);
+        all.push(Line::from(spaced(quote_spans, spacing)));
+
+        // Lines below the area are never drawn, no need to build them
+        let gap = self.config.line_gap as usize;
+        for line in self.test.lines()[current_line + 1..]
+            .iter()
+            .take((quote_area.height as usize).saturating_sub(1) / (gap + 1))
+        {
+            all.extend(std::iter::repeat_n(Line::from(""), gap));
+            all.push(Line::from(spaced(
+                self.upcoming(line, 0, progress),
+                spacing,
+            )))
+        }
+
+        Paragraph::new(all).centered().render(quote_area, buf);
+
+        // Row 3: blank + WPM and stats + blank + source
+        let wpm_text = if self.config.live_stats {
+            let stats = self.live_stats();
+            Line::from(vec![
+                format!("{}: ", self.test.unit().to_uppercase())
+                    .blue()
+                    .bold(),
+                get_wpm(stats.speed).into(),
+                "  |  ".into(),
+                strings.accuracy.blue().bold(),
+                stats.correct.to_string().fg(self.good()).bold(),
+                " - ".into(),
+                stats.incorrect.to_string().fg(self.bad()).bold(),
+                fill(strings.fixed_count, &[&stats.fixed]).yellow(),
+            ])
+            .centered()
+            .bold()
+        } else {
+            Line::from("")
+        };
+        let cadence = match self.rhythm {
+            true => self.cadence_bar(),
+            false => Line::from(""),
+        };
+        if let Some(area) = layout.stats {
+            Paragraph::new(vec![cadence, wpm_text]).render(area, buf);
+        }
+
+        match layout.info {
+            Some(area) if self.show_info => self.render_info(area, buf),
+            Some(area) => Paragraph::new(vec![
+                Line::from(""),
+                Line::from(vec![
+                    format!("  {}", strings.source).blue().bold(),
+                    self.sentence_source.clone().italic(),
+                    format!(" - {}", strings.quotes_credit).into(),
+                ]),
+            ])
+            .wrap(Wrap { trim: false })
+            .render(area, buf),
+            None => {}
+        }
+    }
+
+    /// The last intervals between keys as bars, as high as they are long: those on the beat
+    /// reach the middle and are in the good color. Then the rhythm score so far.
+    fn cadence_bar(&self) -> Line<'static> {
+        let mut spans = vec![self.strings.rhythm.blue().bold()];
+        let Some(cadence) = self.cadence() else {
+            return Line::from(spans).centered();
+        };
+        let intervals = cadence.intervals();
+        for interval in &intervals[intervals.len().saturating_sub(CADENCE_BARS)..] {
+            let offset = cadence.offset(*interval);
+            // From -1 to 1 around the beat, anything past twice the beat being the top
+            let height = ((offset.clamp(-1., 1.) + 1.) / 2. * (BAR_HEIGHTS.len() - 1) as f32)
+                .round() as usize;
+            let bar = BAR_HEIGHTS[height].to_string();
+            spans.push(if offset.abs() <= rhythm::TOLERANCE {
+                bar.fg(self.good())
+            } else {
+                bar.yellow()
+            });
+        }
+        spans.push(format!(" {}", self.locale.percent(cadence.score(), 0)).white());
+        Line::from(spans).centered()
+    }
+
+    /// The typing screen for braille displays, which show one row at a time: the line being
+    /// typed as it was typed on the first row, and the line to type plainly on the last.
+    pub(super) fn render_linear(&self, area: Rect, buf: &mut Buffer) {
+        let line = &self.test.lines()[self.test.current_line()];
+        let mut spans = self.typed_line();
+        spans.push(
+            line[self.test.typing().len().min(line.len())..]
+                .concat()
+                .gray(),
+        );
+
+        let [active, _, mirror] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .areas(area);
+        Paragraph::new(Line::from(spans)).render(active, buf);
+        Paragraph::new(line.concat()).render(mirror, buf);
+    }
+
+    /// The characters typed so far on the current line, mistakes as they were typed.
+    pub(super) fn typed_line(&self) -> Vec<Span<'static>> {
+        let line = &self.test.lines()[self.test.current_line()];
+        let mut spans = Vec::with_capacity(self.test.typing().len() + 1);
+        let mut extras = self.test.extras().iter().peekable();
+        for (cid, typed) in self.test.typing().iter().enumerate() {
+            if let Some((_, extra)) = extras.next_if(|(pos, _)| *pos == cid) {
+                spans.push(self.extra(extra));
+            }
+            let expected = line.get(cid).map(String::as_str).unwrap_or(" ");
+            if self.test.matches(typed, expected) {
+                spans.push(typed.clone().into());
+            } else if typed == SKIPPED {
+                spans.push(self.mistake(expected.to_string().bg(self.bad())));
+            } else {
+                spans.push(self.mistake(typed.clone().bg(self.bad())));
+            }
+        }
+        if let Some((_, extra)) = extras.next() {
+            spans.push(self.extra(extra));
+        }
+        spans
+    }
+
+    /// Blank cells to put between characters, as many as asked unless the lines wouldn't fit.
+    fn letter_spacing(&self, width: u16) -> usize {
+        let longest = self.test.lines().iter().map(Vec::len).max().unwrap_or(0);
+        (self.config.letter_spacing as usize)
+            .min((width as usize / longest.max(1)).saturating_sub(1))
+    }
+
+    /// The text of a line left to type from `from` on, in chunks of alternating colors if asked.
+    fn upcoming(&self, line: &[String], from: usize, progress: f32) -> Vec<Span<'static>> {
+        let rest = &line[from..];
+        if self.config.chunks == Chunks::Off {
+            return vec![reveal(rest, progress).gray()];
+        }
+
+        let shown = shown(rest.len(), progress);
+        let graphemes: Vec<(&str, bool)> = rest
+            .iter()
+            .enumerate()
+            .zip(&chunk_parities(line, self.config.chunks)[from..])
+            .map(|((i, g), odd)| (if i < shown { g.as_str() } else { " " }, *odd))
+            .collect();
+        graphemes
+            .chunk_by(|a, b| a.1 == b.1)
+            .map(|chunk| {
+                let text: String = chunk.iter().map(|(g, _)| *g).collect();
+                if chunk[0].1 { text.cyan() } else { text.gray() }
+            })
+            .collect()
+    }
+
+    /// A line typed earlier, with its mistakes.
+    fn completed_line(&self, line: usize) -> Vec<Span<'static>> {
+        let mut spans: Vec<Span> = Vec::new();
+        let typed = &self.test.typed()[line];
+        let mut extras = self.test.typed_extras()[line].iter().peekable();
+
+        for (cid, c) in self.test.lines()[line].iter().enumerate() {
+            if let Some((_, extra)) = extras.next_if(|(pos, _)| *pos == cid) {
+                spans.push(self.extra(extra));
+            }
+            match typed.get(cid) {
+                Some(typed) if !self.test.matches(typed, c) => {
+                    spans.push(self.mistake(c.clone().fg(self.bad()).bold()));
+                    spans.extend(self.ghost(typed));
+                }
+                _ => spans.push(self.pause(c.clone().gray(), line, cid)),
+            }
+        }
+
+        spans
+    }
+
+    /// Marks a character of a replay typed after a long pause, to see where the typist froze.
+    fn pause<'a>(&self, span: Span<'a>, line: usize, cid: usize) -> Span<'a> {
+        if self.playback.is_some() && self.pauses.contains(&(line, cid)) {
+            span.yellow().underlined()
+        } else {
+            span
+        }
+    }
+
+    pub(super) fn render_info(&self, area: Rect, buf: &mut Buffer) {
+        let strings = self.strings;
+        let block = Block::bordered()
+            .title(strings.quote.bold())
+            .title_bottom(
+                Line::from(format!(" {} ", strings.quotes_credit).dark_gray()).right_aligned(),
+            )
+            .border_set(border::ROUNDED);
+
+        let mut lines = vec![
+            Line::from(vec![
+                strings.source.blue().bold(),
+                self.sentence_source.clone().italic(),
+            ]),
+            Line::from(vec![
+                strings.id.blue().bold(),
+                format!("#{}", self.sentence_id).into(),
+                "  |  ".into(),
+                strings.length.trim_start().blue().bold(),
+                fill(strings.chars, &[&self.sentence_length]).into(),
+                "  |  ".into(),
+                strings.difficulty.blue().bold(),
+                format!("{}/10", self.locale.number(self.difficulty, 1)).into(),
+            ]),
+        ];
+
+        let mut you = match history::quote_stats(&self.history, self.sentence_id) {
+            Some(stats) => vec![
+                strings.you.blue().bold(),
+                fill(strings.attempt_count, &[&stats.attempts]).into(),
+                "  |  ".into(),
+                strings.best.blue().bold(),
+                format!("{:.0} {}", stats.best_wpm, self.test.unit()).fg(self.good()),
+                "  |  ".into(),
+                strings.average.blue().bold(),
+                format!(
+                    "{:.0} {}, {}",
+                    stats.average_wpm,
+                    self.test.unit(),
+                    self.locale.percent(stats.average_accuracy, 1)
+                )
+                .into(),
+            ],
+            None => vec![strings.you.blue().bold(), strings.never_typed.dark_gray()],
+        };
+        let tags = self.tags();
+        if self.rateable() && !tags.is_empty() {
+            let tags: Vec<&str> = tags.iter().map(|tag| strings.tags[*tag as usize]).collect();
+            you.extend([
+                "  |  ".into(),
+                strings.rated.blue().bold(),
+                tags.join(", ").into(),
+            ]);
+        }
+        lines.push(Line::from(you));
+
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+
+    /// WPM of the line that was just completed, shown next to it for a moment.
+    /// Stays up for good when animations are disabled.
+    fn line_popup(&self) -> Vec<Span<'static>> {
+        let Some(line_wpm) = self.test.line_wpms().last() else {
+            return Vec::new();
+        };
+
+        let label = format!(" {:.0} {}", line_wpm, self.test.unit());
+        match self.animations.line_flash_progress() {
+            Some(progress) if progress < 0.2 => vec![label.black().bg(self.good()).bold()],
+            Some(_) => vec![label.fg(self.good()).bold()],
+            None if !self.animations.is_enabled() => vec![label.dark_gray()],
+            None => Vec::new(),
+        }
+    }
+
+    /// Characters typed past the end of a word, crossed out as they don't count.
+    fn extra(&self, extra: &[char]) -> Span<'static> {
+        self.mistake(String::from_iter(extra).fg(self.bad()).crossed_out())
+    }
+
+    /// The wrongly typed character, shown after the expected one if enabled.
+    fn ghost(&self, typed: &str) -> Option<Span<'static>> {
+        if typed == SKIPPED {
+            return None;
+        }
+
+        match self.config.ghost_errors {
+            Ghost::Off => None,
+            Ghost::Dim => Some(typed.to_string().fg(self.bad()).dim()),
+            Ghost::Strikethrough => Some(typed.to_string().fg(self.bad()).crossed_out()),
+            Ghost::Superscript => Some(
+                typed
+                    .chars()
+                    .map(superscript)
+                    .collect::<String>()
+                    .fg(self.bad()),
+            ),
+        }
+    }
+
+    /// Draws a window of the active line around the caret in big glyphs at the top of `area`,
+    /// returning what is left below it.
+    fn render_large_line(&self, area: Rect, buf: &mut Buffer) -> Rect {
+        let [big, _, rest] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(LARGE_CHAR_HEIGHT),
+                Constraint::Length(1),
+                Constraint::Min(0),
+            ])
+            .areas(area);
+
+        let line = &self.test.lines()[self.test.current_line()];
+        let visible = (big.width / LARGE_CHAR_WIDTH).max(1) as usize;
+        // Keep the caret around the first third of the window
+        let first = self
+            .test
+            .typing()
+            .len()
+            .saturating_sub(visible / 3)
+            .min(line.len().saturating_sub(visible));
+
+        let spans: Vec<Span> = line
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+            .map(|(cid, c)| match self.test.typing().get(cid) {
+                Some(typed) if self.test.matches(typed, c) => c.as_str().white(),
+                Some(_) => c.as_str().fg(self.bad()),
+                None => c.as_str().dark_gray(),
+            })
+            .collect();
+
+        BigText::builder()
+            .pixel_size(PixelSize::Quadrant)
+            .lines(vec![Line::from(spans)])
+            .centered()
+            .build()
+            .render(big, buf);
+
+        rest
+    }
+}
+
+/// Small raised version of a character when unicode has one, the character itself otherwise.
+fn superscript(c: char) -> char {
+    const LOWER: [char; 26] = [
+        'ᵃ', 'ᵇ', 'ᶜ', 'ᵈ', 'ᵉ', 'ᶠ', 'ᵍ', 'ʰ', 'ⁱ', 'ʲ', 'ᵏ', 'ˡ', 'ᵐ', 'ⁿ', 'ᵒ', 'ᵖ', '𐞥', 'ʳ',
+        'ˢ', 'ᵗ', 'ᵘ', 'ᵛ', 'ʷ', 'ˣ', 'ʸ', 'ᶻ',
+    ];
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+    if c.is_ascii_alphabetic() {
+        LOWER[(c.to_ascii_lowercase() as u8 - b'a') as usize]
+    } else if c.is_ascii_digit() {
+        DIGITS[(c as u8 - b'0') as usize]
+    } else {
+        match c {
+            '+' => '⁺',
+            '-' => '⁻',
+            '=' => '⁼',
+            '(' => '⁽',
+            ')' => '⁾',
+            _ => c,
+        }
+    }
+}
+
+/// Pads every character with `spacing` blank cells, left unstyled so highlights stay on the
+/// characters.
+fn spaced<'a>(spans: Vec<Span<'a>>, spacing: usize) -> Vec<Span<'a>> {
+    if spacing == 0 {
+        return spans;
+    }
+    let pad = " ".repeat(spacing);
+    spans
+        .iter()
+        .flat_map(|span| {
+            span.content.graphemes(true).flat_map(|g| {
+                [
+                    Span::styled(g.to_string(), span.style),
+                    Span::raw(pad.clone()),
+                ]
+            })
+        })
+        .collect()
+}
+
+/// Which of the two chunk colors each grapheme of a line takes, flipping at every chunk.
+fn chunk_parities(line: &[String], chunks: Chunks) -> Vec<bool> {
+    let mut parities = Vec::with_capacity(line.len());
+    let mut odd = false;
+    let mut words = line.split(|g| g == " ").peekable();
+    while let Some(word) = words.next() {
+        let text = word.concat();
+        let pieces = match chunks {
+            Chunks::Syllables => content::syllables(&text),
+            _ => vec![text.as_str()],
+        };
+        let start = parities.len();
+        for piece in pieces {
+            parities.extend(std::iter::repeat_n(odd, piece.graphemes(true).count()));
+            odd = !odd;
+        }
+        parities.resize(start + word.len(), odd);
+        if words.peek().is_some() {
+            parities.push(false);
+        }
+    }
+    parities
+}
+
+/// How many of `len` graphemes are visible at `progress` (0 to 1) of the intro.
+fn shown(len: usize, progress: f32) -> usize {
+    (len as f32 * progress.min(1.)).ceil() as usize
+}
+
+/// Blanks out the end of `graphemes` so only `progress` (0 to 1) of it is visible, keeping its width.
+fn reveal(graphemes: &[String], progress: f32) -> String {
+    if progress >= 1. {
+        return graphemes.concat();
+    }
+    let shown = shown(graphemes.len(), progress);
+
+    graphemes
+        .iter()
+        .enumerate()
+        .map(|(i, g)| if i < shown { g.as_str() } else { " " })
+        .collect()
+}
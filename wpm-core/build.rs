@@ -0,0 +1,51 @@
+//! Turns the quotes JSON into a compact postcard file, deflated, for the library to embed.
+
+use serde::{Deserialize, Serialize};
+use std::{env, fs, path::PathBuf};
+
+const QUOTES_JSON: &str = "src/content/english.json";
+// Deflate level, from 0 to 10
+const COMPRESSION: u8 = 9;
+
+// Postcard is not self-describing: these must keep the field order of `content::Library`
+#[derive(Deserialize, Serialize)]
+struct Quote {
+    text: String,
+    source: String,
+    length: u32,
+    id: u32,
+}
+
+#[derive(Deserialize, Serialize)]
+enum Metric {
+    #[serde(rename = "wpm")]
+    Wpm,
+    #[serde(rename = "cpm")]
+    Cpm,
+}
+
+#[derive(Deserialize, Serialize)]
+struct Library {
+    language: String,
+    #[serde(default)]
+    metric: Option<Metric>,
+    #[serde(default)]
+    category: Option<String>,
+    groups: Vec<[u32; 2]>,
+    quotes: Vec<Quote>,
+}
+
+fn main() {
+    println!("cargo::rerun-if-changed={QUOTES_JSON}");
+    if env::var_os("CARGO_FEATURE_EMBEDDED_QUOTES").is_none() {
+        return;
+    }
+
+    let json = fs::read_to_string(QUOTES_JSON).expect("Failed to read the quotes");
+    let library: Library = serde_json::from_str(&json).expect("Failed to parse the quotes");
+    let encoded = postcard::to_allocvec(&library).expect("Failed to encode the quotes");
+    let compressed = miniz_oxide::deflate::compress_to_vec(&encoded, COMPRESSION);
+
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("quotes.bin");
+    fs::write(out, compressed).expect("Failed to write the quotes");
+}
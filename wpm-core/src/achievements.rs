@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use crate::{history, stats::Metric};
+
+const ACHIEVEMENTS_FILE: &str = "achievements.json";
+pub const FAST_WPM: f32 = 100.;
+/// Days in a row with a test
+pub const STREAK_DAYS: usize = 7;
+pub const TESTS: usize = 100;
+/// Accuracy of `Achievement::Accurate`, in percent, on a text of `LONG_WORDS` or more
+pub const ACCURATE: f32 = 99.;
+pub const LONG_WORDS: u32 = 50;
+
+/// A milestone unlocked once, from the history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+    /// A test at `FAST_WPM` or more
+    Fast,
+    /// Tests on `STREAK_DAYS` days in a row
+    Streak,
+    /// `TESTS` tests done
+    Tests,
+    /// `ACCURATE` accuracy on a long text
+    Accurate,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 4] = [
+        Achievement::Fast,
+        Achievement::Streak,
+        Achievement::Tests,
+        Achievement::Accurate,
+    ];
+
+    /// Stable name it is stored under.
+    pub fn id(self) -> &'static str {
+        match self {
+            Achievement::Fast => "wpm-100",
+            Achievement::Streak => "streak-7",
+            Achievement::Tests => "tests-100",
+            Achievement::Accurate => "accurate-long",
+        }
+    }
+
+    /// Whether the results of `records` earn it.
+    pub fn earned(self, records: &[history::Record]) -> bool {
+        match self {
+            Achievement::Fast => records
+                .iter()
+                .any(|r| r.metric == Metric::Wpm && r.wpm >= FAST_WPM),
+            Achievement::Streak => longest_day_streak(records) >= STREAK_DAYS,
+            Achievement::Tests => records.len() >= TESTS,
+            Achievement::Accurate => records
+                .iter()
+                .any(|r| r.words >= LONG_WORDS && r.accuracy >= ACCURATE),
+        }
+    }
+}
+
+/// Most days in a row with at least one test, in the local time zone.
+pub fn longest_day_streak(records: &[history::Record]) -> usize {
+    let mut days: Vec<jiff::civil::Date> = records
+        .iter()
+        .filter_map(|r| jiff::Timestamp::from_second(r.timestamp as i64).ok())
+        .map(|time| time.to_zoned(jiff::tz::TimeZone::system()).date())
+        .collect();
+    days.sort();
+    days.dedup();
+
+    let (mut longest, mut streak) = (0, 0);
+    for (i, day) in days.iter().enumerate() {
+        let follows = i > 0 && days[i - 1].tomorrow().is_ok_and(|next| next == *day);
+        streak = if follows { streak + 1 } else { 1 };
+        longest = longest.max(streak);
+    }
+    longest
+}
+
+/// Achievements unlocked so far, and when, kept between runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Unlocked {
+    // seconds since the Unix epoch, by id
+    unlocked: BTreeMap<String, u64>,
+}
+
+impl Unlocked {
+    fn path() -> Option<PathBuf> {
+        history::data_dir().map(|dir| dir.join(ACHIEVEMENTS_FILE))
+    }
+
+    /// The achievements stored so far, none before the first one.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or(io::ErrorKind::NotFound)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    /// When it was unlocked, none if it wasn't.
+    pub fn get(&self, achievement: Achievement) -> Option<u64> {
+        self.unlocked.get(achievement.id()).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.unlocked.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.unlocked.is_empty()
+    }
+
+    /// Unlocks at `now` what `records` earn and wasn't yet, and returns those.
+    pub fn update(&mut self, records: &[history::Record], now: u64) -> Vec<Achievement> {
+        let new: Vec<Achievement> = (Achievement::ALL.into_iter())
+            .filter(|a| self.get(*a).is_none() && a.earned(records))
+            .collect();
+        for achievement in &new {
+            self.unlocked.insert(achievement.id().to_string(), now);
+        }
+        new
+    }
+}
@@ -0,0 +1,164 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpStream, ToSocketAddrs},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    history::{self, Record},
+    replay::Replay,
+    sink::StatsSink,
+    worker::{NETWORK_TIMEOUT, Worker},
+};
+
+/// A chat room personal bests are announced in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Room {
+    /// A Matrix room, by id or alias, posted to as the user of the access token
+    Matrix {
+        homeserver: String,
+        room: String,
+        token: String,
+    },
+    /// An IRC channel on a `host:port` server, joined over plain TCP for each message
+    Irc {
+        server: String,
+        channel: String,
+        nick: String,
+    },
+}
+
+impl Room {
+    fn send(&self, message: &str) -> io::Result<()> {
+        match self {
+            Room::Matrix {
+                homeserver,
+                room,
+                token,
+            } => send_matrix(homeserver, room, token, message),
+            Room::Irc {
+                server,
+                channel,
+                nick,
+            } => send_irc(server, channel, nick, message),
+        }
+    }
+}
+
+fn send_matrix(homeserver: &str, room: &str, token: &str, message: &str) -> io::Result<()> {
+    // Any id unique to the message, a retry with the same one is only posted once
+    let transaction = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let url = format!(
+        "{}/_matrix/client/v3/rooms/{}/send/m.room.message/wpm{transaction}",
+        homeserver.trim_end_matches('/'),
+        encode(room)
+    );
+    let body = serde_json::json!({ "msgtype": "m.text", "body": message });
+
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(NETWORK_TIMEOUT))
+        .build()
+        .into();
+    agent
+        .put(&url)
+        .header("Authorization", &format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .send(body.to_string())
+        .map_err(io::Error::other)?;
+    Ok(())
+}
+
+/// Percent-encodes a path segment, room ids and aliases start with `!` or `#`.
+fn encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+fn send_irc(server: &str, channel: &str, nick: &str, message: &str) -> io::Result<()> {
+    let addr = server
+        .to_socket_addrs()?
+        .next()
+        .ok_or(io::ErrorKind::NotFound)?;
+    let mut stream = TcpStream::connect_timeout(&addr, NETWORK_TIMEOUT)?;
+    stream.set_read_timeout(Some(NETWORK_TIMEOUT))?;
+    let mut lines = BufReader::new(stream.try_clone()?).lines();
+
+    let mut nick = nick.to_string();
+    write!(stream, "NICK {nick}\r\nUSER {nick} 0 * :wpm\r\n")?;
+    // Channels only take messages once the server welcomed the client
+    loop {
+        let line = lines.next().ok_or(io::ErrorKind::UnexpectedEof)??;
+        let mut words = line.split(' ');
+        match (words.next(), words.next()) {
+            (Some("PING"), token) => write!(stream, "PONG {}\r\n", token.unwrap_or_default())?,
+            (_, Some("001")) => break,
+            // Nickname in use
+            (_, Some("433")) => {
+                nick.push('_');
+                write!(stream, "NICK {nick}\r\n")?;
+            }
+            (Some("ERROR"), _) => return Err(io::Error::other(line)),
+            _ => {}
+        }
+    }
+
+    write!(
+        stream,
+        "JOIN {channel}\r\nPRIVMSG {channel} :{message}\r\nQUIT\r\n"
+    )?;
+    // Waits for the server to close, so the message isn't lost to a reset
+    for line in lines {
+        line?;
+    }
+    Ok(())
+}
+
+/// Posts to a chat room whenever a result beats the best so far in its unit, like "ann just hit
+/// 104 WPM (98.2% acc) on wpm-rust", from a [`Worker`] thread.
+#[derive(Debug)]
+pub struct Announcer {
+    player: String,
+    history: Vec<Record>,
+    messages: Worker<String>,
+}
+
+impl Announcer {
+    /// `history` holds the results to beat, the first result in a unit is a personal best.
+    pub fn spawn(room: Room, player: String, history: Vec<Record>) -> Self {
+        Announcer {
+            player,
+            history,
+            messages: Worker::spawn("announcer", move |message: String| {
+                let _ = room.send(&message);
+            }),
+        }
+    }
+}
+
+impl StatsSink for Announcer {
+    fn record(&mut self, record: &Record, _replay: Option<&Replay>) {
+        let best = history::personal_best_in(&self.history, record.metric);
+        self.history.push(record.clone());
+        if best.is_some_and(|best| record.wpm <= best) {
+            return;
+        }
+
+        let _ = self.messages.send(format!(
+            "{} just hit {:.0} {} ({:.1}% acc) on wpm-rust",
+            self.player,
+            record.wpm,
+            record.metric.unit().to_uppercase(),
+            record.accuracy
+        ));
+    }
+}
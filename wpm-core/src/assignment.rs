@@ -0,0 +1,252 @@
+use ring::{
+    rand::SystemRandom,
+    signature::{ED25519, Ed25519KeyPair, KeyPair, UnparsedPublicKey},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use crate::{content, engine::Rules, history, replay::Replay, sim::Simulation};
+
+const KEY_FILE: &str = "student.key";
+
+/// Texts a teacher sets for a class, each to type accurately enough in a few attempts.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Assignment {
+    pub title: String,
+    pub texts: Vec<String>,
+    /// The typing rules every text is typed with
+    #[serde(default)]
+    pub rules: Rules,
+    /// Accuracy a text has to be typed with to pass, in percent
+    #[serde(default)]
+    pub min_accuracy: f32,
+    /// Attempts allowed on each text
+    #[serde(default = "default_attempts")]
+    pub attempts: u32,
+    /// Public key of each student, by name, as `wpm assignment key` prints it
+    #[serde(default)]
+    pub students: BTreeMap<String, String>,
+}
+
+fn default_attempts() -> u32 {
+    3
+}
+
+/// A finished try at one of the texts.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Attempt {
+    /// Index of the text in the assignment
+    pub text: usize,
+    /// Why the app didn't trust the result, like pasted text
+    pub invalid: Option<String>,
+    pub replay: Replay,
+}
+
+/// The key a student signs their results with, made on first use and kept in the data
+/// directory. The teacher gets its public half ahead of time, so nobody else can send results
+/// in the student's name or edit those they sent.
+#[derive(Debug)]
+pub struct StudentKey(Ed25519KeyPair);
+
+impl StudentKey {
+    /// The key of this computer, made the first time it is needed.
+    pub fn load() -> io::Result<Self> {
+        let path = history::data_dir()
+            .ok_or(io::ErrorKind::NotFound)?
+            .join(KEY_FILE);
+        let pkcs8 = match fs::read(&path) {
+            Ok(pkcs8) => pkcs8,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                let pkcs8 = generate()?;
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                fs::write(&path, &pkcs8)?;
+                pkcs8
+            }
+            Err(error) => return Err(error),
+        };
+        Self::from_pkcs8(&pkcs8)
+    }
+
+    /// A new key, kept nowhere.
+    pub fn generate() -> io::Result<Self> {
+        Self::from_pkcs8(&generate()?)
+    }
+
+    fn from_pkcs8(pkcs8: &[u8]) -> io::Result<Self> {
+        Ed25519KeyPair::from_pkcs8(pkcs8)
+            .map(StudentKey)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "the student key is damaged"))
+    }
+
+    /// The half to give the teacher, in hex.
+    pub fn public(&self) -> String {
+        hex::encode(self.0.public_key())
+    }
+}
+
+fn generate() -> io::Result<Vec<u8>> {
+    Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+        .map(|pkcs8| pkcs8.as_ref().to_vec())
+        .map_err(|_| io::Error::other("no randomness to make a key with"))
+}
+
+/// What a student sends back: every attempt keystroke by keystroke, signed with their key. The
+/// signature shows who sent it and that it wasn't edited since, the keystrokes keep the results
+/// from being made up without typing them.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Submission {
+    pub student: String,
+    /// Title of the assignment it was typed for
+    pub title: String,
+    pub attempts: Vec<Attempt>,
+    /// Ed25519 signature of the rest, in hex
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl Submission {
+    pub fn new(student: String, assignment: &Assignment) -> Self {
+        Submission {
+            student,
+            title: assignment.title.clone(),
+            attempts: Vec::new(),
+            signature: String::new(),
+        }
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    pub fn sign(&mut self, key: &StudentKey) {
+        self.signature = hex::encode(key.0.sign(&self.signed()));
+    }
+
+    /// Whether the submission was signed with the key `public_key` is the public half of, and
+    /// not edited since.
+    pub fn verify(&self, public_key: &str) -> bool {
+        let (Ok(public_key), Ok(signature)) =
+            (hex::decode(public_key), hex::decode(&self.signature))
+        else {
+            return false;
+        };
+        UnparsedPublicKey::new(&ED25519, public_key)
+            .verify(&self.signed(), &signature)
+            .is_ok()
+    }
+
+    /// Everything the signature covers.
+    fn signed(&self) -> Vec<u8> {
+        serde_json::to_vec(&(&self.student, &self.title, &self.attempts)).unwrap_or_default()
+    }
+}
+
+/// The best attempt on a text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Score {
+    pub wpm: f32,
+    pub accuracy: f32,
+    pub passed: bool,
+}
+
+/// How a student did on each text, from their keystrokes played back.
+#[derive(Debug, Clone)]
+pub struct Grade {
+    pub student: String,
+    /// By text, `None` for texts never finished
+    pub scores: Vec<Option<Score>>,
+    /// By text, the ones counted
+    pub attempts: Vec<u32>,
+    /// Anything that makes the submission doubtful, like a bad signature
+    pub problems: Vec<String>,
+}
+
+impl Grade {
+    pub fn passed(&self) -> usize {
+        self.scores
+            .iter()
+            .filter(|score| score.is_some_and(|score| score.passed))
+            .count()
+    }
+}
+
+/// Plays back every attempt of the submission to score it against the assignment. Attempts on
+/// another text or with other rules, and those past the allowed number, don't count. Results
+/// not signed with the key of the student they name are flagged.
+pub fn grade(assignment: &Assignment, submission: &Submission) -> Grade {
+    let mut grade = Grade {
+        student: submission.student.clone(),
+        scores: vec![None; assignment.texts.len()],
+        attempts: vec![0; assignment.texts.len()],
+        problems: Vec::new(),
+    };
+    match assignment.students.get(&submission.student) {
+        None => grade
+            .problems
+            .push("the student has no key in the assignment".to_string()),
+        Some(key) if !submission.verify(key) => grade.problems.push(
+            "the signature doesn't match the student's key, the file was edited or sent by someone else"
+                .to_string(),
+        ),
+        Some(_) => {}
+    }
+    if submission.title != assignment.title {
+        grade
+            .problems
+            .push(format!("the results are for \"{}\"", submission.title));
+    }
+
+    for (i, attempt) in submission.attempts.iter().enumerate() {
+        let Some(text) = assignment.texts.get(attempt.text) else {
+            grade
+                .problems
+                .push(format!("attempt {} is on no text", i + 1));
+            continue;
+        };
+        let typed = attempt.replay.lines.join(" ");
+        let expected = content::prepare(text, assignment.rules.punctuation);
+        if !typed.split_whitespace().eq(expected.split_whitespace()) {
+            grade
+                .problems
+                .push(format!("attempt {} is on another text", i + 1));
+            continue;
+        }
+        if attempt.replay.rules != assignment.rules {
+            grade
+                .problems
+                .push(format!("attempt {} used other rules", i + 1));
+            continue;
+        }
+        if grade.attempts[attempt.text] == assignment.attempts {
+            grade
+                .problems
+                .push(format!("attempt {} is one too many on its text", i + 1));
+            continue;
+        }
+
+        grade.attempts[attempt.text] += 1;
+
+        let sim = Simulation::from_replay(&attempt.replay);
+        if !sim.test().is_done() {
+            continue;
+        }
+        let (wpm, accuracy) = (sim.speed(), sim.test().accuracy());
+        let score = Score {
+            wpm,
+            accuracy,
+            passed: attempt.invalid.is_none() && accuracy >= assignment.min_accuracy,
+        };
+        let best = &mut grade.scores[attempt.text];
+        if best.is_none_or(|best| (score.passed, score.wpm) > (best.passed, best.wpm)) {
+            *best = Some(score);
+        }
+    }
+
+    grade
+}
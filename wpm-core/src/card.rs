@@ -0,0 +1,153 @@
+use qrcodegen::{QrCode, QrCodeEcc};
+use std::fmt::Write;
+
+use crate::history::{self, Record};
+
+const WIDTH: f64 = 600.;
+const HEIGHT: f64 = 315.;
+// Area of the graph, under the numbers
+const GRAPH_TOP: f64 = 190.;
+const GRAPH_BOTTOM: f64 = 285.;
+const MARGIN: f64 = 32.;
+const BACKGROUND: &str = "#1e1e2e";
+const TEXT: &str = "#cdd6f4";
+const DIM: &str = "#7f849c";
+const ACCENT: &str = "#89b4fa";
+const SPEED: &str = "#a6e3a1";
+// Light modules around a QR code, half the usual to fit on a screen, which scanners still read
+const QUIET_ZONE: i32 = 2;
+
+/// A picture of a result to share, as an SVG document. `points` are drawn as a graph of the
+/// speed along `graph_label`, like the seconds of the test.
+pub fn svg(record: &Record, source: &str, points: &[(f64, f64)], graph_label: &str) -> String {
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="monospace">
+<rect width="100%" height="100%" rx="16" fill="{BACKGROUND}"/>
+<text x="{MARGIN}" y="48" font-size="20" font-weight="bold" fill="{ACCENT}">wpm</text>
+<text x="{right}" y="48" font-size="16" fill="{DIM}" text-anchor="end">{date}</text>
+<text x="{MARGIN}" y="128" font-size="72" font-weight="bold" fill="{SPEED}">{wpm:.0}<tspan font-size="24" fill="{DIM}"> {unit}</tspan></text>
+<text x="{right}" y="100" font-size="22" fill="{TEXT}" text-anchor="end">{accuracy:.1}% accuracy</text>
+<text x="{right}" y="128" font-size="22" fill="{TEXT}" text-anchor="end">{seconds:.1}s</text>
+<text x="{MARGIN}" y="165" font-size="16" fill="{DIM}">{source}</text>
+"#,
+        right = WIDTH - MARGIN,
+        date = history::date(record.timestamp),
+        wpm = record.wpm,
+        unit = record.unit(),
+        accuracy = record.accuracy,
+        seconds = record.seconds,
+        source = escape(source),
+    );
+
+    if let Some(graph) = polyline(points) {
+        let _ = write!(
+            svg,
+            r#"<polyline points="{graph}" fill="none" stroke="{SPEED}" stroke-width="3" stroke-linejoin="round"/>
+<text x="{right}" y="{label_y}" font-size="12" fill="{DIM}" text-anchor="end">{label}</text>
+"#,
+            right = WIDTH - MARGIN,
+            label_y = GRAPH_BOTTOM + 18.,
+            label = escape(graph_label),
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// A result as text in a box, to paste where pictures don't go. With a challenge code, the
+/// box also holds it as a QR code, for a friend to scan and type the same test.
+pub fn text(record: &Record, source: &str, challenge: Option<&str>) -> String {
+    let mut lines = vec![
+        format!("wpm · {}", history::date(record.timestamp)),
+        format!(
+            "{:.0} {} · {:.1}% accuracy · {:.1}s",
+            record.wpm,
+            record.unit(),
+            record.accuracy,
+            record.seconds
+        ),
+        source.to_string(),
+    ];
+    if let Some(code) = challenge {
+        lines.push(String::new());
+        lines.extend(qr(code).unwrap_or_default());
+        lines.push(String::new());
+        lines.push(format!("wpm challenge {code}"));
+    }
+
+    let width = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut card = format!("╭{}╮\n", "─".repeat(width + 2));
+    for line in &lines {
+        let _ = writeln!(
+            card,
+            "│ {line}{} │",
+            " ".repeat(width - line.chars().count())
+        );
+    }
+    card + &format!("╰{}╯", "─".repeat(width + 2))
+}
+
+/// A QR code of `text` in half blocks, two rows of modules to a line. Light modules are the
+/// blocks, so it reads right in light text on a dark background, the way terminals usually are.
+/// None when the text is too long for a QR code.
+pub fn qr(text: &str) -> Option<Vec<String>> {
+    let code = QrCode::encode_text(text, QrCodeEcc::Low).ok()?;
+    // Modules out of the code are light, which makes the quiet zone
+    let light = |x: i32, y: i32| !code.get_module(x, y);
+    let range = -QUIET_ZONE..code.size() + QUIET_ZONE;
+    let lines = range
+        .clone()
+        .step_by(2)
+        .map(|y| {
+            range
+                .clone()
+                .map(
+                    |x| match (light(x, y), light(x, y + 1) && y + 1 < range.end) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    },
+                )
+                .collect()
+        })
+        .collect();
+    Some(lines)
+}
+
+/// Points scaled to the graph area, or nothing for less than two of them.
+fn polyline(points: &[(f64, f64)]) -> Option<String> {
+    if points.len() < 2 {
+        return None;
+    }
+
+    let (min_x, max_x) = points.iter().fold((f64::MAX, f64::MIN), |(min, max), p| {
+        (min.min(p.0), max.max(p.0))
+    });
+    let max_y = points.iter().map(|p| p.1).fold(0., f64::max).max(1.);
+    let span_x = (max_x - min_x).max(f64::EPSILON);
+
+    let scaled: Vec<String> = points
+        .iter()
+        .map(|(x, y)| {
+            let x = MARGIN + (x - min_x) / span_x * (WIDTH - 2. * MARGIN);
+            let y = GRAPH_BOTTOM - y / max_y * (GRAPH_BOTTOM - GRAPH_TOP);
+            format!("{x:.1},{y:.1}")
+        })
+        .collect();
+    Some(scaled.join(" "))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,122 @@
+use crate::{engine::Rules, punctuation::Punctuation};
+
+/// Bumped whenever the layout of the code changes.
+const VERSION: u8 = 1;
+// Crockford's base 32: no I, L, O or U to mistake for other characters
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// A result someone got on a quote, with everything needed to type the exact same test, to
+/// send to a friend as a short code.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Challenge {
+    pub quote_id: u32,
+    pub rules: Rules,
+    /// Whether lines end with the space to the next one, it changes how the quote is split
+    pub trailing_space: bool,
+    pub wpm: f32,
+    pub accuracy: f32,
+    pub seconds: f32,
+}
+
+impl Challenge {
+    /// The challenge as base 32 text, with a checksum to catch typos.
+    pub fn encode(&self) -> String {
+        let flags = self.rules.strict_space as u8
+            | (self.rules.stop_on_error as u8) << 1
+            | (self.rules.overtype as u8) << 2
+            | (self.rules.dead_keys as u8) << 3
+            | (self.trailing_space as u8) << 4
+            | (match self.rules.punctuation {
+                Punctuation::Exact => 0,
+                Punctuation::Normalize => 1,
+                Punctuation::Accept => 2,
+            }) << 5;
+
+        let mut bytes = vec![VERSION];
+        bytes.extend(self.quote_id.to_be_bytes());
+        bytes.push(flags);
+        for value in [self.wpm, self.accuracy, self.seconds] {
+            bytes.extend(tenths(value).to_be_bytes());
+        }
+        bytes.push(checksum(&bytes));
+
+        to_base32(&bytes)
+    }
+
+    /// Reads a code made by [`Challenge::encode`]. Case, spaces and dashes don't matter.
+    pub fn decode(code: &str) -> Option<Self> {
+        let bytes = from_base32(code)?;
+        let (&sum, bytes) = bytes.split_last()?;
+        if bytes.len() != 12 || bytes[0] != VERSION || checksum(bytes) != sum {
+            return None;
+        }
+
+        let flags = bytes[5];
+        let value = |i: usize| u16::from_be_bytes([bytes[i], bytes[i + 1]]) as f32 / 10.;
+        Some(Challenge {
+            quote_id: u32::from_be_bytes(bytes[1..5].try_into().ok()?),
+            rules: Rules {
+                strict_space: flags & 1 != 0,
+                stop_on_error: flags & 1 << 1 != 0,
+                overtype: flags & 1 << 2 != 0,
+                dead_keys: flags & 1 << 3 != 0,
+                punctuation: match flags >> 5 {
+                    0 => Punctuation::Exact,
+                    1 => Punctuation::Normalize,
+                    2 => Punctuation::Accept,
+                    _ => return None,
+                },
+            },
+            trailing_space: flags & 1 << 4 != 0,
+            wpm: value(6),
+            accuracy: value(8),
+            seconds: value(10),
+        })
+    }
+}
+
+fn tenths(value: f32) -> u16 {
+    (value * 10.).round().clamp(0., u16::MAX as f32) as u16
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes
+        .iter()
+        .fold(0u8, |sum, &byte| sum.rotate_left(3) ^ byte)
+}
+
+fn to_base32(bytes: &[u8]) -> String {
+    let mut code = String::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    for &byte in bytes {
+        buffer = buffer << 8 | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            code.push(ALPHABET[(buffer >> bits & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        code.push(ALPHABET[(buffer << (5 - bits) & 31) as usize] as char);
+    }
+    code
+}
+
+fn from_base32(code: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in code.chars().filter(|c| !matches!(c, ' ' | '-')) {
+        let value = match c.to_ascii_uppercase() {
+            'O' => 0,
+            'I' | 'L' => 1,
+            c => ALPHABET.iter().position(|&a| a as char == c)?,
+        };
+        buffer = buffer << 5 | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Some(bytes)
+}
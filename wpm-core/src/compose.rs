@@ -0,0 +1,37 @@
+use unicode_normalization::UnicodeNormalization;
+
+/// Combining mark added by a dead key, for the spacing accents keyboards send for them. The
+/// ASCII look-alikes (' ` ^ ~ ") are keys of their own on most layouts, typed for themselves.
+fn mark(dead: char) -> Option<char> {
+    match dead {
+        '´' => Some('\u{301}'),
+        'ˋ' => Some('\u{300}'),
+        'ˆ' => Some('\u{302}'),
+        '˜' => Some('\u{303}'),
+        '¨' => Some('\u{308}'),
+        '¸' => Some('\u{327}'),
+        'ˇ' => Some('\u{30c}'),
+        '˘' => Some('\u{306}'),
+        '°' => Some('\u{30a}'),
+        _ => None,
+    }
+}
+
+pub fn is_dead_key(c: char) -> bool {
+    mark(c).is_some()
+}
+
+/// The accented letter a dead key followed by `base` makes, if there is a precomposed one.
+pub fn compose(dead: char, base: char) -> Option<char> {
+    let decomposed = format!("{base}{}", mark(dead)?);
+    let mut composed = decomposed.nfc();
+    match (composed.next(), composed.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+/// Canonical composed form, so accented letters compare equal however they were written.
+pub fn nfc(text: &str) -> String {
+    text.nfc().collect()
+}
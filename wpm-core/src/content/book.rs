@@ -0,0 +1,131 @@
+use std::io::{self, BufRead};
+
+use super::{ContentProvider, Quote, wrap};
+
+/// A long text typed a page at a time. It is read and wrapped only as far as the pages taken,
+/// so a whole novel never sits in memory.
+#[derive(Debug)]
+pub struct Book<R> {
+    title: String,
+    reader: R,
+    width: usize,
+    page_lines: usize,
+    // last line wrapped so far, more words may still fit on it
+    carry: String,
+    // lines wrapped but not handed out yet
+    pending: Vec<String>,
+    page: usize,
+    ended: bool,
+}
+
+impl<R: BufRead> Book<R> {
+    /// A book cut in pages of `page_lines` lines of at most `width` graphemes.
+    pub fn new(title: String, reader: R, width: usize, page_lines: usize) -> Self {
+        Book {
+            title,
+            reader,
+            width,
+            page_lines,
+            carry: String::new(),
+            pending: Vec::new(),
+            page: 0,
+            ended: false,
+        }
+    }
+
+    /// A book whose first page is read already, so a file that can't be read as text is
+    /// reported before typing starts rather than making a blank page.
+    pub fn open(title: String, reader: R, width: usize, page_lines: usize) -> io::Result<Self> {
+        let mut book = Self::new(title, reader, width, page_lines);
+        book.fill()?;
+        Ok(book)
+    }
+
+    /// The lines of the next page, fewer at the end of the text and none once it is all typed.
+    pub fn next_page(&mut self) -> io::Result<Vec<String>> {
+        self.fill()?;
+        let taken = self.page_lines.min(self.pending.len());
+        if taken > 0 {
+            self.page += 1;
+        }
+        Ok(self.pending.drain(..taken).collect())
+    }
+
+    /// Reads and wraps lines until a page is pending or the text is over.
+    fn fill(&mut self) -> io::Result<()> {
+        let mut buf = String::new();
+        while self.pending.len() < self.page_lines && !self.ended {
+            buf.clear();
+            let read = self
+                .reader
+                .read_line(&mut buf)
+                .map_err(|error| match error.kind() {
+                    io::ErrorKind::InvalidData => io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{} is not UTF-8 text", self.title),
+                    ),
+                    _ => error,
+                })?;
+            if read == 0 {
+                self.ended = true;
+                if !self.carry.is_empty() {
+                    self.pending.push(std::mem::take(&mut self.carry));
+                }
+                break;
+            }
+
+            // Line breaks and runs of spaces in the file are typed as a single space
+            let words: Vec<&str> = buf.split_whitespace().collect();
+            if words.is_empty() {
+                continue;
+            }
+            let text = words.join(" ");
+            let text = if self.carry.is_empty() {
+                text
+            } else {
+                format!("{} {text}", self.carry)
+            };
+
+            let mut wrapped = wrap(&text, self.width, false);
+            self.carry = wrapped.pop().unwrap_or_default();
+            self.pending.extend(wrapped);
+        }
+        Ok(())
+    }
+
+    /// Number of the page last handed out, from 1.
+    pub fn page(&self) -> usize {
+        self.page
+    }
+}
+
+impl<R: BufRead + std::fmt::Debug> ContentProvider for Book<R> {
+    fn name(&self) -> &str {
+        "book"
+    }
+
+    fn category(&self) -> String {
+        format!("book: {}", self.title)
+    }
+
+    // Books are not tagged with a language, they are assumed to be spaced out in words
+    fn language(&self) -> &str {
+        "english"
+    }
+
+    /// Pages in order, each its own quote numbered after it. Failing to read the file past the
+    /// first page ends the book there.
+    fn next_text(&mut self, _group: usize) -> Option<Quote> {
+        let text = self
+            .next_page()
+            .ok()
+            .filter(|page| !page.is_empty())?
+            .join(" ");
+        Some(Quote {
+            length: text.chars().count() as u32,
+            source: format!("{}, page {}", self.title, self.page),
+            id: self.page as u32,
+            text,
+        })
+    }
+}
@@ -0,0 +1,47 @@
+use rand::{rng, seq::SliceRandom};
+
+use super::{ContentProvider, Quote};
+use crate::custom::Version;
+
+// Words in a text of the drill
+const CUSTOM_WORDS: usize = 25;
+
+/// A drill built from the user's own weaknesses, its words in a new order each time.
+#[derive(Debug)]
+pub struct Custom {
+    name: String,
+    version: Version,
+}
+
+impl Custom {
+    pub fn new(name: String, version: Version) -> Self {
+        Custom { name, version }
+    }
+}
+
+impl ContentProvider for Custom {
+    fn name(&self) -> &str {
+        "custom"
+    }
+
+    fn language(&self) -> &str {
+        "english"
+    }
+
+    /// None when the drill has no word.
+    fn next_text(&mut self, _group: usize) -> Option<Quote> {
+        if self.version.is_empty() {
+            return None;
+        }
+
+        let mut words: Vec<&str> = self.version.texts().cycle().take(CUSTOM_WORDS).collect();
+        words.shuffle(&mut rng());
+        let text = words.join(" ");
+        Some(Quote {
+            length: text.chars().count() as u32,
+            source: format!("Drill: {} v{}", self.name, self.version.number),
+            id: 0,
+            text,
+        })
+    }
+}
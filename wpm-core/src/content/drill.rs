@@ -0,0 +1,135 @@
+use rand::{Rng, rng, seq::IndexedRandom};
+use std::collections::BTreeSet;
+
+use super::{ContentProvider, Flow, Quote};
+
+// Words in a drill
+const DRILL_WORDS: usize = 25;
+// Fewer real words than this and made up ones are typed instead, they would repeat too much
+const MIN_WORDS: usize = 30;
+const MADE_UP_LENGTH: std::ops::RangeInclusive<usize> = 2..=5;
+
+/// Words typed with only some of the keys, like the left hand or the home row. Real words are
+/// taken from other texts when there are enough of them, otherwise made up from the keys.
+#[derive(Debug)]
+pub struct Drill {
+    label: String,
+    letters: Vec<char>,
+    words: Vec<String>,
+    // new keys every other word is made of, none when all keys count the same
+    focus: Vec<char>,
+    // difficulty following the accuracy, none to keep it as it is
+    flow: Option<Flow>,
+}
+
+impl Drill {
+    /// A drill on `letters`, with the words of `texts` that are made of them only.
+    pub fn new<'a>(
+        label: String,
+        letters: Vec<char>,
+        texts: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        let words = words(texts, |word| word.chars().all(|c| letters.contains(&c)));
+        Drill {
+            label,
+            letters,
+            words,
+            focus: Vec::new(),
+            flow: None,
+        }
+    }
+
+    /// Makes every other word of the keys in `focus` only, like the new keys of a lesson.
+    pub fn focused(mut self, focus: Vec<char>) -> Self {
+        self.focus = focus;
+        self
+    }
+
+    /// Scales the difficulty with the accuracy, from the level of `flow`.
+    pub fn adaptive(mut self, flow: Flow) -> Self {
+        self.flow = Some(flow);
+        self
+    }
+
+    fn level(&self) -> u32 {
+        self.flow.as_ref().map_or(0, Flow::level)
+    }
+
+    /// A made up word of `keys`, longer at higher levels.
+    fn made_up_word(&self, keys: &[char]) -> String {
+        let mut rng = rng();
+        let longer = self.level() as usize / 2;
+        let length = rng.random_range(MADE_UP_LENGTH) + longer;
+        (0..length).filter_map(|_| keys.choose(&mut rng)).collect()
+    }
+}
+
+/// The distinct words of `texts` that `keep` accepts, lowercased and without punctuation around
+/// them. Single letters are left out.
+pub(crate) fn words<'a>(
+    texts: impl IntoIterator<Item = &'a str>,
+    keep: impl Fn(&str) -> bool,
+) -> Vec<String> {
+    let words: BTreeSet<String> = texts
+        .into_iter()
+        .flat_map(str::split_whitespace)
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphabetic())
+                .to_lowercase()
+        })
+        .filter(|word| word.chars().count() >= 2 && keep(word))
+        .collect();
+    words.into_iter().collect()
+}
+
+impl ContentProvider for Drill {
+    fn name(&self) -> &str {
+        "drill"
+    }
+
+    fn language(&self) -> &str {
+        "english"
+    }
+
+    /// A new draw of words each time, none when there is no key to type.
+    fn next_text(&mut self, _group: usize) -> Option<Quote> {
+        if self.letters.is_empty() {
+            return None;
+        }
+        let words: Vec<String> = (0..DRILL_WORDS)
+            .map(|i| match self.words.len() {
+                _ if i % 2 == 1 && !self.focus.is_empty() => self.made_up_word(&self.focus),
+                ..MIN_WORDS => self.made_up_word(&self.letters),
+                _ => match &self.flow {
+                    Some(flow) => self
+                        .words
+                        .choose_weighted(&mut rng(), |w| flow.weight(w))
+                        .ok(),
+                    None => self.words.choose(&mut rng()),
+                }
+                .cloned()
+                .unwrap_or_default(),
+            })
+            .collect();
+        let (text, source) = match &self.flow {
+            Some(flow) => (
+                flow.punctuate(&words, |c| self.letters.contains(&c)),
+                format!("Drill: {}, level {}", self.label, flow.level() + 1),
+            ),
+            None => (words.join(" "), format!("Drill: {}", self.label)),
+        };
+
+        Some(Quote {
+            length: text.chars().count() as u32,
+            source,
+            id: 0,
+            text,
+        })
+    }
+
+    fn adapt(&mut self, accuracy: f32) {
+        if let Some(flow) = &mut self.flow {
+            flow.record(accuracy);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use rand::{rng, seq::SliceRandom};
+
+use super::{ContentProvider, Quote};
+
+// Words per minute the text is long enough for, faster than almost anyone keeps up
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Texts put end to end into one long enough to type for a number of minutes, for endurance
+/// runs.
+#[derive(Debug)]
+pub struct Endurance {
+    texts: Vec<String>,
+    minutes: u32,
+}
+
+impl Endurance {
+    pub fn new<'a>(texts: impl IntoIterator<Item = &'a str>, minutes: u32) -> Self {
+        Endurance {
+            texts: texts
+                .into_iter()
+                .filter(|text| !text.trim().is_empty())
+                .map(str::to_string)
+                .collect(),
+            minutes,
+        }
+    }
+}
+
+impl ContentProvider for Endurance {
+    fn name(&self) -> &str {
+        "endurance"
+    }
+
+    fn language(&self) -> &str {
+        "english"
+    }
+
+    /// The texts in a new order each time, as many times over as it takes.
+    fn next_text(&mut self, _group: usize) -> Option<Quote> {
+        let words = self.minutes as usize * WORDS_PER_MINUTE;
+        let mut texts: Vec<&str> = self.texts.iter().map(String::as_str).collect();
+        texts.shuffle(&mut rng());
+
+        if texts.is_empty() {
+            return None;
+        }
+        let mut picked = Vec::new();
+        let mut count = 0;
+        for text in texts.iter().cycle() {
+            if count >= words {
+                break;
+            }
+            count += text.split_whitespace().count();
+            picked.push(*text);
+        }
+
+        let text = picked.join(" ");
+        Some(Quote {
+            length: text.chars().count() as u32,
+            source: format!("Endurance: {} minutes", self.minutes),
+            id: 0,
+            text,
+        })
+    }
+}
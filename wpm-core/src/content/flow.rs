@@ -0,0 +1,94 @@
+use rand::{Rng, rng, seq::IndexedRandom};
+
+// Tests the accuracy is averaged over before the texts get harder
+const WINDOW: usize = 3;
+/// Average accuracy, in percent, that makes the texts harder once kept for `WINDOW` tests.
+pub const RAISE_AT: f32 = 97.;
+/// Average accuracy, in percent, under which the texts get easier right away.
+pub const EASE_AT: f32 = 92.;
+pub const MAX_LEVEL: u32 = 5;
+// Letters from the most to the least common in English, the later the rarer
+const FREQUENCY: &str = "etaoinshrdlcumwfgypbvkjxqz";
+// Marks added after words, more kinds at each level
+const MARKS: [char; MAX_LEVEL as usize] = [',', '.', ';', '?', '!'];
+// Chance of a mark after a word, at each level
+const MARK_CHANCE: f64 = 0.06;
+
+/// Difficulty of generated texts that follows how accurately they are typed: longer words,
+/// rarer letters and more punctuation while the accuracy stays high, simpler texts as soon as
+/// it drops.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Flow {
+    level: u32,
+    // accuracy of the tests since the level last changed, the last `WINDOW` of them
+    recent: Vec<f32>,
+}
+
+impl Flow {
+    /// The level reached by results of these accuracies in order, to pick up where the last
+    /// run left off.
+    pub fn replay(accuracies: impl IntoIterator<Item = f32>) -> Self {
+        let mut flow = Flow::default();
+        for accuracy in accuracies {
+            flow.record(accuracy);
+        }
+        flow
+    }
+
+    /// From 0, the easiest, to `MAX_LEVEL`.
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    /// Takes the accuracy of a finished test, in percent, and moves the level if it calls for
+    /// it.
+    pub fn record(&mut self, accuracy: f32) {
+        self.recent.push(accuracy);
+        if self.recent.len() > WINDOW {
+            self.recent.remove(0);
+        }
+        let average = self.recent.iter().sum::<f32>() / self.recent.len() as f32;
+        if average < EASE_AT && self.level > 0 {
+            self.level -= 1;
+            self.recent.clear();
+        } else if self.recent.len() == WINDOW && average >= RAISE_AT && self.level < MAX_LEVEL {
+            self.level += 1;
+            self.recent.clear();
+        }
+    }
+
+    /// How often to pick `word` at this level, relative to the others: short and common words
+    /// first, then longer ones with rare letters.
+    pub fn weight(&self, word: &str) -> f32 {
+        let length = word.chars().count() as f32;
+        let rarity = word
+            .chars()
+            .filter_map(|c| FREQUENCY.find(c.to_ascii_lowercase()))
+            .map(|rank| rank as f32 / FREQUENCY.len() as f32)
+            .sum::<f32>()
+            / length.max(1.);
+        (length / 5.).powi(self.level as i32 - 2) * (1. + rarity).powi(self.level as i32)
+    }
+
+    /// Joins `words` into a text, with a mark after some of them at higher levels. Only marks
+    /// `allowed` are used, like the keys of a drill.
+    pub fn punctuate(&self, words: &[String], allowed: impl Fn(char) -> bool) -> String {
+        let marks: Vec<char> = (MARKS[..self.level as usize].iter())
+            .copied()
+            .filter(|mark| allowed(*mark))
+            .collect();
+        let chance = MARK_CHANCE * self.level as f64;
+        let mut rng = rng();
+        let words: Vec<String> = words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| match marks.choose(&mut rng) {
+                Some(mark) if i + 1 < words.len() && rng.random_bool(chance) => {
+                    format!("{word}{mark}")
+                }
+                _ => word.clone(),
+            })
+            .collect();
+        words.join(" ")
+    }
+}
@@ -0,0 +1,137 @@
+use rand::{rng, seq::IndexedRandom};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "embedded-quotes")]
+use std::sync::OnceLock;
+
+use super::ContentProvider;
+use crate::stats::Metric;
+
+// english.json as postcard, deflated by the build script
+#[cfg(feature = "embedded-quotes")]
+const ENGLISH: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/quotes.bin"));
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Quote {
+    pub text: String,
+    pub source: String,
+    pub length: u32,
+    pub id: u32,
+}
+
+/// A set of quotes in one language, with the length groups they are picked from.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Library {
+    pub language: String,
+    /// What speed is counted in, guessed from the language when not given
+    #[serde(default)]
+    pub metric: Option<Metric>,
+    /// What results are grouped under in the stats, like "code" or "prose", the language when
+    /// not given
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Bounds (exclusive) of the length of the quotes in each group
+    pub groups: Vec<[u32; 2]>,
+    pub quotes: Vec<Quote>,
+    // positions in `quotes` of the quotes of each group, filled in by `index`
+    #[serde(skip)]
+    by_group: Vec<Vec<usize>>,
+}
+
+impl Library {
+    /// The english quotes built into the binary, decompressed on first use only.
+    #[cfg(feature = "embedded-quotes")]
+    pub fn embedded() -> &'static Self {
+        static EMBEDDED: OnceLock<Library> = OnceLock::new();
+        EMBEDDED.get_or_init(|| {
+            let bytes = miniz_oxide::inflate::decompress_to_vec(ENGLISH)
+                .expect("Failed to decompress the embedded quotes");
+            postcard::from_bytes::<Library>(&bytes)
+                .expect("Failed to decode the embedded quotes")
+                .index()
+        })
+    }
+
+    /// Quotes in the same JSON format as the embedded ones.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json).map(Self::index)
+    }
+
+    /// Sorts the quotes into their groups once, so picking one doesn't go through them all.
+    fn index(mut self) -> Self {
+        self.by_group = self
+            .groups
+            .iter()
+            .map(|group| {
+                self.quotes
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, q)| group[0] < q.length && q.length < group[1])
+                    .map(|(i, _)| i)
+                    .collect()
+            })
+            .collect();
+        self
+    }
+
+    /// A random quote of the given length group.
+    pub fn pick(&self, group: usize) -> Option<&Quote> {
+        let index = self.by_group.get(group)?.choose(&mut rng())?;
+        self.quotes.get(*index)
+    }
+
+    /// A random quote of the given length group among those `keep` lets through.
+    pub fn pick_where(&self, group: usize, keep: impl Fn(&Quote) -> bool) -> Option<&Quote> {
+        let kept: Vec<usize> = self
+            .by_group
+            .get(group)?
+            .iter()
+            .copied()
+            .filter(|i| keep(&self.quotes[*i]))
+            .collect();
+        self.quotes.get(*kept.choose(&mut rng())?)
+    }
+
+    pub fn get(&self, id: u32) -> Option<&Quote> {
+        self.quotes.iter().find(|q| q.id == id)
+    }
+
+    /// What speed is counted in, as the quotes say or else as their language needs.
+    pub fn metric(&self) -> Metric {
+        self.metric.unwrap_or_else(|| super::metric(&self.language))
+    }
+
+    /// What results on these quotes are grouped under, as they say or else their language.
+    pub fn category(&self) -> &str {
+        self.category.as_deref().unwrap_or(&self.language)
+    }
+}
+
+impl ContentProvider for &'static Library {
+    fn name(&self) -> &str {
+        "quotes"
+    }
+
+    fn language(&self) -> &str {
+        &self.language
+    }
+
+    fn metric(&self) -> Metric {
+        Library::metric(self)
+    }
+
+    fn category(&self) -> String {
+        Library::category(self).to_string()
+    }
+
+    fn groups(&self) -> &[[u32; 2]] {
+        &self.groups
+    }
+
+    fn next_text(&mut self, group: usize) -> Option<Quote> {
+        self.pick(group).cloned()
+    }
+
+    fn next_text_where(&mut self, group: usize, keep: &dyn Fn(&Quote) -> bool) -> Option<Quote> {
+        self.pick_where(group, keep).cloned()
+    }
+}
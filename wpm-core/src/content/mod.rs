@@ -0,0 +1,170 @@
+use rand::{Rng, rng};
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    compose,
+    punctuation::{self, Punctuation},
+    stats::Metric,
+};
+
+pub use book::Book;
+pub use custom::Custom;
+pub use drill::Drill;
+pub(crate) use drill::words;
+pub use endurance::Endurance;
+pub use flow::Flow;
+pub use library::{Library, Quote};
+pub use practice::Practice;
+pub use provider::{ContentProvider, Registry};
+pub use remote::Remote;
+pub use review::Review;
+
+mod book;
+mod custom;
+mod drill;
+mod endurance;
+pub mod flow;
+mod library;
+mod practice;
+mod provider;
+pub mod remote;
+mod review;
+
+/// Languages written without spaces between words are scored in characters per minute, for
+/// quotes that don't say which metric they use.
+pub fn metric(language: &str) -> Metric {
+    let per_character = [
+        "chinese", "japanese", "korean", "thai", "lao", "khmer", "burmese",
+    ]
+    .iter()
+    .any(|prefix| language.starts_with(prefix));
+    if per_character {
+        Metric::Cpm
+    } else {
+        Metric::Wpm
+    }
+}
+
+// Chance of a word starting with a capital with random casing
+const RANDOM_CAPITALS: f64 = 0.3;
+
+/// Case of the letters of the texts typed, to practice Shift or leave it out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Casing {
+    /// As written
+    #[default]
+    Exact,
+    /// No capitals, Shift is never needed for letters
+    Lower,
+    Upper,
+    /// Lowercase with random words capitalized, so Shift comes where it isn't expected
+    Random,
+}
+
+/// Changes the case of the letters of a text.
+pub fn recase(text: &str, casing: Casing) -> String {
+    match casing {
+        Casing::Exact => text.to_string(),
+        Casing::Lower => text.to_lowercase(),
+        Casing::Upper => text.to_uppercase(),
+        Casing::Random => {
+            let mut rng = rng();
+            let mut recased = String::with_capacity(text.len());
+            for word in text.to_lowercase().split_word_bounds() {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) if first.is_alphabetic() && rng.random_bool(RANDOM_CAPITALS) => {
+                        recased.extend(first.to_uppercase());
+                        recased.push_str(chars.as_str());
+                    }
+                    _ => recased.push_str(word),
+                }
+            }
+            recased
+        }
+    }
+}
+
+/// Puts a quote in the form it is typed in: composed accents, and punctuation handled as asked.
+pub fn prepare(text: &str, punctuation: Punctuation) -> String {
+    let text = compose::nfc(text);
+    match punctuation {
+        Punctuation::Exact => text,
+        Punctuation::Normalize => punctuation::normalize(&text),
+        // No single key matches an ellipsis, so it is always spelled out
+        Punctuation::Accept => text.replace('…', "..."),
+    }
+}
+
+/// Splits a word into syllables by a rule of thumb: a single consonant between two vowels
+/// starts the next syllable, of two or more the first ends the previous one. Good enough to
+/// read a word in chunks, not to hyphenate it.
+pub fn syllables(word: &str) -> Vec<&str> {
+    let is_vowel = |c: char| "aeiouyàâäéèêëîïôöûüù".contains(c.to_lowercase().next().unwrap_or(c));
+    let chars: Vec<(usize, char)> = word.char_indices().collect();
+    let mut cuts = Vec::new();
+
+    let mut i = chars
+        .iter()
+        .position(|(_, c)| is_vowel(*c))
+        .unwrap_or(chars.len());
+    while i < chars.len() {
+        while i < chars.len() && is_vowel(chars[i].1) {
+            i += 1;
+        }
+        let consonants = i;
+        while i < chars.len() && !is_vowel(chars[i].1) {
+            i += 1;
+        }
+        // A silent final e doesn't make a syllable of its own, like in "make"
+        if i >= chars.len() - 1 && (i == chars.len() || chars[i].1 == 'e') {
+            break;
+        }
+
+        let cluster: String = chars[consonants..i].iter().map(|(_, c)| *c).collect();
+        let keep = match cluster.to_lowercase().as_str() {
+            "ch" | "sh" | "th" | "ph" | "wh" => 0,
+            cluster if cluster.chars().count() >= 2 => 1,
+            _ => 0,
+        };
+        cuts.push(chars[consonants + keep].0);
+    }
+
+    let mut syllables = Vec::with_capacity(cuts.len() + 1);
+    let mut start = 0;
+    for cut in cuts {
+        syllables.push(&word[start..cut]);
+        start = cut;
+    }
+    syllables.push(&word[start..]);
+    syllables
+}
+
+/// Splits a text into lines of at most `width` graphemes, breaking between words. Every line
+/// but the last ends with the space separating it from the next when `trailing_space` is set.
+pub fn wrap(text: &str, width: usize, trailing_space: bool) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for word in text.split(" ") {
+        if let Some(l) = lines.last_mut() {
+            if l.graphemes(true).count() + 1 + word.graphemes(true).count() > width {
+                lines.push(word.to_string());
+            } else {
+                l.push(' ');
+                l.push_str(word);
+            }
+        } else {
+            lines.push(word.to_string());
+        }
+    }
+
+    if trailing_space {
+        for i in 0..lines.len().saturating_sub(1) {
+            lines[i].push(' ');
+        }
+    }
+
+    lines
+}
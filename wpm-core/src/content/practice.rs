@@ -0,0 +1,150 @@
+use rand::{rng, seq::IndexedRandom};
+
+use super::{ContentProvider, Flow, Quote, drill};
+use crate::letters::Letters;
+
+// Words in a practice text
+const PRACTICE_WORDS: usize = 25;
+// Weakest letters and pairs named in the source
+const FOCUS: usize = 3;
+
+/// Words picked for the letters and pairs of letters that need the most practice, weighed
+/// again after every text like keybr.com does.
+#[derive(Debug)]
+pub struct Practice {
+    letters: Letters,
+    target_wpm: f32,
+    words: Vec<String>,
+    // how often each word is picked, from the needs of its letters
+    weights: Vec<f32>,
+    // difficulty following the accuracy, none to keep it as it is
+    flow: Option<Flow>,
+}
+
+impl Practice {
+    /// Practice on the words of `texts`, toward typing every letter at `target_wpm`.
+    pub fn new<'a>(
+        letters: Letters,
+        target_wpm: f32,
+        texts: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        let words = drill::words(texts, |word| word.chars().all(char::is_alphabetic));
+        let mut practice = Practice {
+            letters,
+            target_wpm,
+            weights: Vec::new(),
+            words,
+            flow: None,
+        };
+        practice.weigh();
+        practice
+    }
+
+    /// Scales the difficulty with the accuracy, from the level of `flow`.
+    pub fn adaptive(mut self, flow: Flow) -> Self {
+        self.flow = Some(flow);
+        self.weigh();
+        self
+    }
+
+    /// Weighs each word by the average need of its letters and of the pairs in it already
+    /// typed, squared so that weak words come up much more often. Then by the level of the
+    /// flow, if any.
+    fn weigh(&mut self) {
+        let (letters, target, flow) = (&self.letters, self.target_wpm, &self.flow);
+        self.weights = self
+            .words
+            .iter()
+            .map(|word| {
+                let chars: Vec<char> = word.chars().collect();
+                let needs: Vec<f32> = chars
+                    .iter()
+                    .map(|c| letters.need(*c, target))
+                    .chain(
+                        chars
+                            .windows(2)
+                            .filter_map(|pair| letters.bigram_need(pair[0], pair[1], target)),
+                    )
+                    .collect();
+                let need = needs.iter().sum::<f32>() / needs.len() as f32;
+                (1. + 9. * need).powi(2) * flow.as_ref().map_or(1., |flow| flow.weight(word))
+            })
+            .collect();
+    }
+
+    /// The letters and pairs needing the most practice, like "q, z, th".
+    fn focus(&self) -> String {
+        let mut letters: Vec<(char, f32)> = self
+            .letters
+            .iter()
+            .map(|(c, _)| (c, self.letters.need(c, self.target_wpm)))
+            .collect();
+        letters.sort_by(|a, b| b.1.total_cmp(&a.1));
+        letters
+            .iter()
+            .take(FOCUS)
+            .map(|(c, _)| c.to_string())
+            .chain(
+                self.letters
+                    .weakest_bigrams(self.target_wpm)
+                    .into_iter()
+                    .take(FOCUS)
+                    .map(str::to_string),
+            )
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl ContentProvider for Practice {
+    fn name(&self) -> &str {
+        "practice"
+    }
+
+    fn language(&self) -> &str {
+        "english"
+    }
+
+    fn next_text(&mut self, _group: usize) -> Option<Quote> {
+        let indices: Vec<usize> = (0..self.words.len()).collect();
+        let mut rng = rng();
+        let words: Vec<String> = (0..PRACTICE_WORDS)
+            .filter_map(|_| indices.choose_weighted(&mut rng, |i| self.weights[*i]).ok())
+            .map(|i| self.words[*i].clone())
+            .collect();
+        if words.is_empty() {
+            return None;
+        }
+        let text = match &self.flow {
+            Some(flow) => flow.punctuate(&words, |_| true),
+            None => words.join(" "),
+        };
+
+        let focus = self.focus();
+        let mut source = match focus.as_str() {
+            "" => "Practice".to_string(),
+            _ => format!("Practice: {focus}"),
+        };
+        if let Some(flow) = &self.flow {
+            source += &format!(", level {}", flow.level() + 1);
+        }
+        Some(Quote {
+            length: text.chars().count() as u32,
+            source,
+            id: 0,
+            text,
+        })
+    }
+
+    fn learn(&mut self, letters: &Letters) {
+        self.letters.merge(letters);
+        self.weigh();
+    }
+
+    fn adapt(&mut self, accuracy: f32) {
+        if let Some(flow) = &mut self.flow {
+            flow.record(accuracy);
+            self.weigh();
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::fmt;
+
+use super::Quote;
+use crate::{letters::Letters, review::Session, stats::Metric};
+
+/// A source of texts to type. Texts come as quotes, with whatever the source knows about them.
+pub trait ContentProvider: fmt::Debug {
+    /// Short name the source is picked by.
+    fn name(&self) -> &str;
+
+    fn language(&self) -> &str;
+
+    /// What speed is counted in, characters for the languages that need it.
+    fn metric(&self) -> Metric {
+        super::metric(self.language())
+    }
+
+    /// What results on this source are grouped under in the stats, like a language, a pack or
+    /// a book.
+    fn category(&self) -> String {
+        self.name().to_string()
+    }
+
+    /// Length groups texts can be picked from, none for sources read in a set order.
+    fn groups(&self) -> &[[u32; 2]] {
+        &[]
+    }
+
+    /// The next text, from the given group if there are any. `None` once the source ran out.
+    fn next_text(&mut self, group: usize) -> Option<Quote>;
+
+    /// The next text of the given group that `keep` lets through, `None` when none does.
+    /// Sources read in a set order have nothing to pick from and ignore it.
+    fn next_text_where(&mut self, group: usize, _keep: &dyn Fn(&Quote) -> bool) -> Option<Quote> {
+        self.next_text(group)
+    }
+
+    /// Takes how the letters of a finished text were typed, for sources that adapt to it.
+    fn learn(&mut self, _letters: &Letters) {}
+
+    /// Takes the accuracy of a finished text, in percent, for sources that scale their
+    /// difficulty with it.
+    fn adapt(&mut self, _accuracy: f32) {}
+
+    /// Takes which words of a finished text were missed, for sources made of missed words.
+    fn words(&mut self, _session: &Session) {}
+}
+
+/// Every source registered, one of them being typed from.
+#[derive(Debug)]
+pub struct Registry {
+    providers: Vec<Box<dyn ContentProvider>>,
+    active: usize,
+}
+
+impl Registry {
+    pub fn new(provider: impl ContentProvider + 'static) -> Self {
+        Registry {
+            providers: vec![Box::new(provider)],
+            active: 0,
+        }
+    }
+
+    /// Adds a source, replacing any other one of the same name.
+    pub fn register(&mut self, provider: impl ContentProvider + 'static) {
+        match self
+            .providers
+            .iter()
+            .position(|p| p.name() == provider.name())
+        {
+            Some(i) => self.providers[i] = Box::new(provider),
+            None => self.providers.push(Box::new(provider)),
+        }
+    }
+
+    /// Switches to the source of the given name, if there is one.
+    pub fn select(&mut self, name: &str) -> bool {
+        let found = self.providers.iter().position(|p| p.name() == name);
+        if let Some(i) = found {
+            self.active = i;
+        }
+        found.is_some()
+    }
+
+    pub fn active(&self) -> &dyn ContentProvider {
+        self.providers[self.active].as_ref()
+    }
+
+    pub fn active_mut(&mut self) -> &mut dyn ContentProvider {
+        self.providers[self.active].as_mut()
+    }
+}
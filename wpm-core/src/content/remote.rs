@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use super::{ContentProvider, Library, Quote};
+use crate::{history, stats::Metric};
+
+const CACHE_DIR: &str = "quote-packs";
+// Copies younger than this are used without asking the server
+pub const MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+// Short, so a network that is down never holds up the start
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What the server said about the copy on disk, to ask it whether it changed since.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Meta {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// When the server last confirmed the copy, in seconds since the Unix epoch
+    checked: u64,
+}
+
+/// Quotes downloaded from a URL, in the JSON format of the embedded ones.
+#[derive(Debug)]
+pub struct Remote {
+    name: String,
+    library: Library,
+}
+
+impl Remote {
+    /// Loads the pack called `name` from `url`, see [`fetch`]. Cached in the data directory.
+    pub fn load(name: &str, url: &str, offline: bool) -> io::Result<Self> {
+        let dir = history::data_dir()
+            .ok_or(io::ErrorKind::NotFound)?
+            .join(CACHE_DIR);
+        let json = fetch(&dir, name, url, offline, MAX_AGE)?;
+        Ok(Remote {
+            name: name.to_string(),
+            library: Library::from_json(&json)?,
+        })
+    }
+}
+
+impl ContentProvider for Remote {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn language(&self) -> &str {
+        &self.library.language
+    }
+
+    fn metric(&self) -> Metric {
+        self.library.metric()
+    }
+
+    fn category(&self) -> String {
+        self.library
+            .category
+            .clone()
+            .unwrap_or_else(|| self.name.clone())
+    }
+
+    fn groups(&self) -> &[[u32; 2]] {
+        &self.library.groups
+    }
+
+    fn next_text(&mut self, group: usize) -> Option<Quote> {
+        self.library.pick(group).cloned()
+    }
+
+    fn next_text_where(&mut self, group: usize, keep: &dyn Fn(&Quote) -> bool) -> Option<Quote> {
+        self.library.pick_where(group, keep).cloned()
+    }
+}
+
+/// The body at `url`, kept in `dir` under `name`. A copy younger than `max_age` is used as is,
+/// an older one is revalidated with its ETag or date. Offline, or when the server can't be
+/// reached, any copy on disk is used.
+pub fn fetch(
+    dir: &Path,
+    name: &str,
+    url: &str,
+    offline: bool,
+    max_age: Duration,
+) -> io::Result<String> {
+    let (body_path, meta_path) = cache_paths(dir, name);
+    let old: Option<Meta> = fs::read_to_string(&meta_path)
+        .ok()
+        .and_then(|meta| serde_json::from_str(&meta).ok())
+        .filter(|meta: &Meta| meta.url == url);
+    // Only trusted along with what is known about it
+    let cached = old
+        .as_ref()
+        .and_then(|_| fs::read_to_string(&body_path).ok());
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    if let (Some(old), Some(cached)) = (&old, &cached) {
+        let fresh = now.saturating_sub(old.checked) < max_age.as_secs();
+        if offline || fresh {
+            return Ok(cached.clone());
+        }
+    }
+    if offline {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("quote pack {name} was never downloaded, run once without --offline"),
+        ));
+    }
+
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_connect(Some(CONNECT_TIMEOUT))
+        .timeout_global(Some(TIMEOUT))
+        .build()
+        .into();
+    let mut request = agent.get(url);
+    if let Some(old) = old.as_ref().filter(|_| cached.is_some()) {
+        if let Some(etag) = &old.etag {
+            request = request.header("If-None-Match", etag);
+        }
+        if let Some(date) = &old.last_modified {
+            request = request.header("If-Modified-Since", date);
+        }
+    }
+
+    let response = match request.call() {
+        Ok(response) => response,
+        Err(error) => return cached.ok_or(io::Error::other(error)),
+    };
+    let header = |name: &str| {
+        let value = response.headers().get(name)?.to_str().ok()?;
+        Some(value.to_string())
+    };
+    let mut meta = Meta {
+        url: url.to_string(),
+        etag: header("etag"),
+        last_modified: header("last-modified"),
+        checked: now,
+    };
+
+    let body = match (response.status().as_u16(), cached) {
+        // Not modified, servers may leave the validators out of the answer
+        (304, Some(cached)) => {
+            if let Some(old) = old {
+                meta.etag = meta.etag.or(old.etag);
+                meta.last_modified = meta.last_modified.or(old.last_modified);
+            }
+            cached
+        }
+        _ => {
+            let body = response
+                .into_body()
+                .read_to_string()
+                .map_err(io::Error::other)?;
+            fs::create_dir_all(dir)?;
+            fs::write(&body_path, &body)?;
+            body
+        }
+    };
+    fs::write(&meta_path, serde_json::to_string(&meta)?)?;
+    Ok(body)
+}
+
+/// Where the body and what is known about it are kept, the name made safe for a file name.
+fn cache_paths(dir: &Path, name: &str) -> (PathBuf, PathBuf) {
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+            _ => '_',
+        })
+        .collect();
+    (
+        dir.join(format!("{name}.json")),
+        dir.join(format!("{name}.meta.json")),
+    )
+}
@@ -0,0 +1,58 @@
+use rand::{rng, seq::SliceRandom};
+
+use super::{ContentProvider, Quote};
+use crate::review::{self, Queue, Session};
+
+// Most words in a review, the others wait for the next one
+const REVIEW_WORDS: usize = 25;
+// Fewer words due than this and they are typed more than once
+const MIN_WORDS: usize = 10;
+
+/// The words of the review queue that are due, in a new order each time.
+#[derive(Debug)]
+pub struct Review {
+    queue: Queue,
+}
+
+impl Review {
+    pub fn new(queue: Queue) -> Self {
+        Review { queue }
+    }
+}
+
+impl ContentProvider for Review {
+    fn name(&self) -> &str {
+        "review"
+    }
+
+    fn language(&self) -> &str {
+        "english"
+    }
+
+    /// None once no word is due.
+    fn next_text(&mut self, _group: usize) -> Option<Quote> {
+        let due = self.queue.due(review::now());
+        if due.is_empty() {
+            return None;
+        }
+
+        let mut words: Vec<&str> = due
+            .iter()
+            .copied()
+            .cycle()
+            .take(due.len().clamp(MIN_WORDS, REVIEW_WORDS))
+            .collect();
+        words.shuffle(&mut rng());
+        let text = words.join(" ");
+        Some(Quote {
+            length: text.chars().count() as u32,
+            source: format!("Review: {} words due", due.len()),
+            id: 0,
+            text,
+        })
+    }
+
+    fn words(&mut self, session: &Session) {
+        self.queue.add(session);
+    }
+}
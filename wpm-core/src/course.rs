@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use crate::{content::Drill, history, keyboard::Layout};
+
+const COURSE_FILE: &str = "course.json";
+
+/// A step of the touch typing course: a few new keys, typed with all those of the lessons
+/// before, and what it takes to pass.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lesson {
+    /// Stable name the progress is stored under
+    pub id: &'static str,
+    pub title: &'static str,
+    /// The new keys, where they are on a QWERTY keyboard
+    pub keys: &'static str,
+    pub wpm: f32,
+    /// In percent
+    pub accuracy: f32,
+}
+
+const fn lesson(id: &'static str, title: &'static str, keys: &'static str, wpm: f32) -> Lesson {
+    Lesson {
+        id,
+        title,
+        keys,
+        wpm,
+        accuracy: 95.,
+    }
+}
+
+/// From the home row to every letter, then capitals, numbers and symbols, a little faster each
+/// time.
+pub const LESSONS: [Lesson; 17] = [
+    lesson("home-fj", "Home row: f and j", "fj", 10.),
+    lesson("home-dk", "Home row: d and k", "dk", 10.),
+    lesson("home-sl", "Home row: s and l", "sl", 12.),
+    lesson("home-a", "Home row: a and ;", "a;", 12.),
+    lesson("home-gh", "Home row: g and h", "gh", 14.),
+    lesson("top-ei", "Top row: e and i", "ei", 14.),
+    lesson("top-ru", "Top row: r and u", "ru", 16.),
+    lesson("top-ty", "Top row: t and y", "ty", 16.),
+    lesson("top-wo", "Top row: w, o, q and p", "woqp", 18.),
+    lesson("bottom-vm", "Bottom row: v, b, n and m", "vbnm", 18.),
+    lesson("bottom-cx", "Bottom row: c, x and z", "cxz", 20.),
+    lesson(
+        "bottom-comma",
+        "Bottom row: comma, period and slash",
+        ",./",
+        20.,
+    ),
+    lesson(
+        "capitals",
+        "Capitals: both shift keys",
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+        20.,
+    ),
+    lesson("numbers-left", "Numbers: 1 to 5", "12345", 20.),
+    lesson("numbers-right", "Numbers: 6 to 0", "67890", 20.),
+    lesson(
+        "symbols-quote",
+        "Symbols: quotes, dash and question mark",
+        "'\"-?",
+        22.,
+    ),
+    lesson(
+        "symbols-shift",
+        "Symbols: the shifted numbers",
+        "!@#$%^&*()",
+        22.,
+    ),
+];
+
+impl Lesson {
+    /// The new keys on `layout`, found where they are on QWERTY.
+    pub fn new_keys(&self, layout: Layout) -> Vec<char> {
+        self.keys
+            .chars()
+            .map(|c| Layout::Qwerty.emulate(layout, c))
+            .collect()
+    }
+
+    /// Whether a result is good enough to move on.
+    pub fn passes(&self, wpm: f32, accuracy: f32) -> bool {
+        wpm >= self.wpm && accuracy >= self.accuracy
+    }
+}
+
+/// Texts of the lesson at `index` on `layout`: words of `texts` typed with the keys learned so
+/// far, every other one made of the new keys.
+pub fn drill<'a>(index: usize, layout: Layout, texts: impl IntoIterator<Item = &'a str>) -> Drill {
+    let lesson = LESSONS[index];
+    let keys: Vec<char> = (LESSONS[..=index].iter())
+        .flat_map(|lesson| lesson.new_keys(layout))
+        .collect();
+    Drill::new(
+        format!("lesson {}, {}", index + 1, lesson.title),
+        keys,
+        texts,
+    )
+    .focused(lesson.new_keys(layout))
+}
+
+/// How a lesson went so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub struct LessonProgress {
+    pub attempts: u32,
+    pub best_wpm: f32,
+    pub best_accuracy: f32,
+    pub passed: bool,
+}
+
+/// Progress through the course, kept between runs.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Progress {
+    lessons: BTreeMap<String, LessonProgress>,
+}
+
+impl Progress {
+    fn path() -> Option<PathBuf> {
+        history::data_dir().map(|dir| dir.join(COURSE_FILE))
+    }
+
+    /// The progress stored so far, none before the first lesson.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or(io::ErrorKind::NotFound)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    pub fn get(&self, index: usize) -> LessonProgress {
+        self.lessons
+            .get(LESSONS[index].id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Counts an attempt at the lesson at `index`, and whether it passed.
+    pub fn attempt(&mut self, index: usize, wpm: f32, accuracy: f32) -> bool {
+        let lesson = LESSONS[index];
+        let progress = self.lessons.entry(lesson.id.to_string()).or_default();
+        let passed = lesson.passes(wpm, accuracy);
+        progress.attempts += 1;
+        progress.best_wpm = progress.best_wpm.max(wpm);
+        progress.best_accuracy = progress.best_accuracy.max(accuracy);
+        progress.passed |= passed;
+        passed
+    }
+
+    /// Whether the lesson at `index` can be taken, all those before it being passed.
+    pub fn unlocked(&self, index: usize) -> bool {
+        index < LESSONS.len() && (0..index).all(|i| self.get(i).passed)
+    }
+
+    /// The first lesson not passed yet, none once the course is over.
+    pub fn next(&self) -> Option<usize> {
+        (0..LESSONS.len()).find(|i| !self.get(*i).passed)
+    }
+}
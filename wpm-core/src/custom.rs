@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use crate::{content, history, letters::Letters, review::Queue};
+
+const CUSTOM_FILE: &str = "drills.json";
+/// Missed words a drill is built on, those missed the most.
+pub const WORST_WORDS: usize = 15;
+/// Slowest or most missed pairs of letters a drill is built on.
+pub const WORST_BIGRAMS: usize = 6;
+// Words typed for each pair, the shortest ones with it
+const WORDS_PER_BIGRAM: usize = 3;
+
+/// A drill as it was built at one time, from the weaknesses then.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Version {
+    /// From 1, one more each time the drill changed
+    pub number: u32,
+    /// When it was built, in seconds since the Unix epoch
+    pub created: u64,
+    /// Missed words, the most missed first
+    pub words: Vec<String>,
+    pub bigrams: Vec<String>,
+    /// Words with each of `bigrams` in it
+    pub bigram_words: Vec<String>,
+}
+
+impl Version {
+    /// Everything the drill types, missed words first.
+    pub fn texts(&self) -> impl Iterator<Item = &str> + Clone {
+        self.words
+            .iter()
+            .chain(&self.bigram_words)
+            .map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.texts().next().is_none()
+    }
+
+    fn same_as(&self, other: &Version) -> bool {
+        (self.words == other.words)
+            && (self.bigrams == other.bigrams)
+            && (self.bigram_words == other.bigram_words)
+    }
+}
+
+/// Drills built from the words missed the most and the weakest pairs of letters, kept by name.
+/// Building a drill again adds a version when the weaknesses changed, the older ones can still
+/// be typed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Drills {
+    drills: BTreeMap<String, Vec<Version>>,
+}
+
+impl Drills {
+    fn path() -> Option<PathBuf> {
+        history::data_dir().map(|dir| dir.join(CUSTOM_FILE))
+    }
+
+    /// The drills built so far, none before the first one.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or(io::ErrorKind::NotFound)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    /// Every drill and its versions, by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[Version])> {
+        self.drills
+            .iter()
+            .map(|(name, versions)| (name.as_str(), versions.as_slice()))
+    }
+
+    /// A version of the drill called `name`, the latest without one.
+    pub fn get(&self, name: &str, version: Option<u32>) -> Option<&Version> {
+        let versions = self.drills.get(name)?;
+        match version {
+            Some(number) => versions.iter().find(|v| v.number == number),
+            None => versions.last(),
+        }
+    }
+
+    /// Builds the drill called `name` from the words missed in `queue` and the pairs of
+    /// `letters` furthest from `target_wpm`, taking words with those pairs from `texts`. A new
+    /// version is only added when it differs from the latest one.
+    pub fn build<'a>(
+        &mut self,
+        name: &str,
+        queue: &Queue,
+        letters: &Letters,
+        target_wpm: f32,
+        texts: impl IntoIterator<Item = &'a str>,
+        now: u64,
+    ) -> &Version {
+        let texts: Vec<&str> = texts.into_iter().collect();
+        let bigrams: Vec<String> = letters
+            .weakest_bigrams(target_wpm)
+            .into_iter()
+            .take(WORST_BIGRAMS)
+            .map(str::to_string)
+            .collect();
+        let bigram_words = bigrams
+            .iter()
+            .flat_map(|bigram| {
+                let mut words =
+                    content::words(texts.iter().copied(), |word| word.contains(bigram.as_str()));
+                words.sort_by_key(|word| word.chars().count());
+                words.into_iter().take(WORDS_PER_BIGRAM)
+            })
+            .collect();
+        let mut version = Version {
+            number: 1,
+            created: now,
+            words: queue
+                .worst()
+                .into_iter()
+                .take(WORST_WORDS)
+                .map(str::to_string)
+                .collect(),
+            bigrams,
+            bigram_words,
+        };
+
+        let versions = self.drills.entry(name.to_string()).or_default();
+        match versions.last() {
+            Some(latest) if latest.same_as(&version) => {}
+            latest => {
+                version.number = latest.map_or(1, |latest| latest.number + 1);
+                versions.push(version);
+            }
+        }
+        versions.last().expect("a version was just added or found")
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use crate::{
+    engine::SKIPPED,
+    replay::{self, Key, Replay},
+    sim::Simulation,
+};
+
+/// What became of a grapheme of the text, or of keys typed past the end of a word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mark {
+    /// Typed right the first time
+    Right,
+    /// Typed right in the end, after the wrong graphemes typed there first and deleted
+    Fixed(Vec<String>),
+    /// Left wrong, with what was typed instead
+    Wrong(String),
+    /// Jumped over with a space
+    Skipped,
+    /// Not reached before the test ended
+    Untyped,
+    /// Typed past the end of a word, with no grapheme of the text to match
+    Extra(String),
+}
+
+/// A grapheme of the text and what became of it, empty for extra keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    pub expected: String,
+    pub mark: Mark,
+}
+
+/// The text of a replay line by line, each grapheme marked with how it was typed, the mistakes
+/// fixed along the way included.
+pub fn of(replay: &Replay) -> Vec<Vec<Cell>> {
+    let mut sim = Simulation::with_lines(replay.lines.clone(), replay.rules);
+    // Wrong graphemes deleted at each line and position, in the order they were typed
+    let mut fixes: HashMap<(usize, usize), Vec<String>> = HashMap::new();
+    for &Key(_, key) in &replay.keys {
+        if key != replay::BACKSPACE && key != replay::DELETE_WORD {
+            sim.press(key, 0);
+            continue;
+        }
+        let (line, before) = (sim.test().current_line(), sim.test().typing().to_vec());
+        sim.press(key, 0);
+        let test = sim.test();
+        if test.current_line() != line {
+            continue;
+        }
+        for (pos, typed) in before.iter().enumerate().skip(test.typing().len()) {
+            let expected = test.lines()[line].get(pos);
+            if typed != SKIPPED && expected.is_none_or(|expected| !test.matches(typed, expected)) {
+                fixes.entry((line, pos)).or_default().push(typed.clone());
+            }
+        }
+    }
+
+    let test = sim.test();
+    let mut lines = Vec::new();
+    for (l, line) in test.lines().iter().enumerate() {
+        let (typed, extras): (&[String], &[(usize, Vec<char>)]) = match l {
+            l if l < test.typed().len() => (&test.typed()[l], &test.typed_extras()[l]),
+            l if l == test.current_line() => (test.typing(), test.extras()),
+            _ => (&[], &[]),
+        };
+        let mut cells = Vec::new();
+        for pos in 0..=line.len() {
+            for (_, chars) in extras.iter().filter(|(at, _)| *at == pos) {
+                cells.push(Cell {
+                    expected: String::new(),
+                    mark: Mark::Extra(chars.iter().collect()),
+                });
+            }
+            let Some(expected) = line.get(pos) else {
+                break;
+            };
+            let mark = match typed.get(pos) {
+                None => Mark::Untyped,
+                Some(typed) if typed == SKIPPED => Mark::Skipped,
+                Some(typed) if !test.matches(typed, expected) => Mark::Wrong(typed.clone()),
+                Some(_) => match fixes.remove(&(l, pos)) {
+                    Some(attempts) => Mark::Fixed(attempts),
+                    None => Mark::Right,
+                },
+            };
+            cells.push(Cell {
+                expected: expected.clone(),
+                mark,
+            });
+        }
+        lines.push(cells);
+    }
+    lines
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    ops::RangeInclusive,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{engine::TypingTest, history};
+
+const ENDURANCE_FILE: &str = "endurance.jsonl";
+/// Length of a run without one, in minutes.
+pub const DEFAULT_MINUTES: u32 = 15;
+/// Lengths a run can take, in minutes.
+pub const MINUTES: RangeInclusive<u32> = 10..=30;
+
+/// A long test of continuous text, kept apart from the sprints of the history.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Run {
+    pub timestamp: u64,
+    /// How long the run was meant to last
+    pub minutes: u32,
+    pub seconds: f32,
+    pub wpm: f32,
+    pub accuracy: f32,
+    /// Speed over each full minute, the fatigue curve
+    pub per_minute: Vec<f32>,
+}
+
+impl Run {
+    /// The run of a test meant to last `minutes`, now.
+    pub fn of(test: &TypingTest, minutes: u32) -> Self {
+        let duration = test.elapsed();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Run {
+            timestamp,
+            minutes,
+            seconds: duration.as_secs_f32(),
+            wpm: test.speed(duration),
+            accuracy: test.accuracy(),
+            per_minute: test.minute_speeds(),
+        }
+    }
+
+    /// Change of speed from the first third of the minutes to the last one, in percent: below
+    /// zero when tiring. None under three minutes.
+    pub fn fatigue(&self) -> Option<f32> {
+        let third = self.per_minute.len() / 3;
+        if third == 0 {
+            return None;
+        }
+        let average = |speeds: &[f32]| speeds.iter().sum::<f32>() / speeds.len() as f32;
+        let (first, last) = (
+            average(&self.per_minute[..third]),
+            average(&self.per_minute[self.per_minute.len() - third..]),
+        );
+        (first > 0.).then(|| (last - first) / first * 100.)
+    }
+}
+
+/// Every run so far, skipping lines that fail to parse.
+pub fn load() -> Vec<Run> {
+    let Some(path) = history::data_dir().map(|dir| dir.join(ENDURANCE_FILE)) else {
+        return Vec::new();
+    };
+
+    fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn append(run: &Run) -> io::Result<()> {
+    let dir = history::data_dir().ok_or(io::ErrorKind::NotFound)?;
+    fs::create_dir_all(&dir)?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(ENDURANCE_FILE))?;
+    writeln!(file, "{}", serde_json::to_string(run)?)
+}
+
+/// Best speed of the runs meant to last `minutes`, none before the first one.
+pub fn best(runs: &[Run], minutes: u32) -> Option<f32> {
+    runs.iter()
+        .filter(|run| run.minutes == minutes)
+        .map(|run| run.wpm)
+        .reduce(f32::max)
+}
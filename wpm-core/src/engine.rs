@@ -0,0 +1,935 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    clock::{Clock, SystemClock},
+    compose, history, latency,
+    letters::Letters,
+    punctuation::{self, Punctuation},
+    replay,
+    stats::{self, Metric},
+};
+
+// Extra characters accepted at the end of a word before input is blocked
+const MAX_EXTRAS: usize = 10;
+// Span of the fastest stretch of a test, in milliseconds
+const BURST_WINDOW: u32 = 5000;
+// Shortest span a burst is measured over, so text committed at once by an input method doesn't
+// make one of a few keys in no time
+const MIN_BURST_WINDOW: u32 = 1000;
+/// Stands for the letters jumped over when space is pressed mid-word.
+pub const SKIPPED: &str = "\0";
+
+/// The typing rules that change what a key does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Only accept space where the quote has one, otherwise space jumps to the next word
+    pub strict_space: bool,
+    /// Refuse to move past a wrong character, it is only counted as an error
+    pub stop_on_error: bool,
+    /// Accept extra characters at the end of a word as errors instead of ignoring them
+    pub overtype: bool,
+    pub punctuation: Punctuation,
+    /// Combine accent keys with the next letter typed
+    pub dead_keys: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            strict_space: true,
+            stop_on_error: false,
+            overtype: true,
+            punctuation: Punctuation::Exact,
+            dead_keys: true,
+        }
+    }
+}
+
+/// Progress worth reacting to, returned by the methods feeding keys to a test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Milestone {
+    /// A line was completed and the next one is up
+    LineCompleted,
+    /// The last line was completed, the test is over
+    Finished,
+}
+
+/// Characters typed past the end of a word, keyed by the position they were typed at, in order.
+pub type Extras = Vec<(usize, Vec<char>)>;
+
+/// The state of one attempt at a quote, fed one key at a time.
+#[derive(Debug)]
+pub struct TypingTest {
+    clock: Box<dyn Clock>,
+    rules: Rules,
+    metric: Metric,
+
+    // every line of the quote, split into graphemes
+    lines: Vec<Vec<String>>,
+    current_line: usize,
+    typing: Vec<String>,
+    typed: Vec<Vec<String>>,
+    extras: Extras,
+    typed_extras: Vec<Extras>,
+
+    correct: u32,
+    incorrect: u32,
+    // incorrect characters that only differ by case
+    case_errors: u32,
+    // mistakes that were deleted or blocked, they no longer show but still lower the accuracy
+    corrected: u32,
+    words: u32,
+
+    // set by the first keystroke, so the time spent reading the quote doesn't count
+    start: Option<Instant>,
+    done: Option<Instant>,
+
+    // (seconds elapsed, wpm) taken at every word boundary
+    samples: Vec<(f64, f64)>,
+    // time spent on each completed line
+    splits: Vec<Duration>,
+    line_wpms: Vec<f32>,
+    word_errors: HashMap<String, u32>,
+    // hits, misses and time of each letter of the quote
+    letters: Letters,
+    // when each right key was typed, in milliseconds from the first key
+    hits: Vec<u32>,
+    // the word being typed: when it started and right keys since, none before the first key
+    word_from: Option<u32>,
+    word_keys: u32,
+    // whether it was typed without a mistake or a correction so far
+    word_clean: bool,
+    word_burst: f32,
+    // where keys came after a long pause, as (line, grapheme)
+    pauses: Vec<(usize, usize)>,
+    // indices of the keys typed with Shift held, when the terminal tells
+    shifted: Vec<usize>,
+    shift_held: bool,
+    // right keys in a row since the last wrong one, and the most of the test
+    streak: u32,
+    best_streak: u32,
+
+    // dead key waiting for the letter it accents
+    dead_key: Option<char>,
+    // why the result can't be trusted
+    invalid: Option<&'static str>,
+    // every key that changed the text, timed from the first one
+    keys: Vec<replay::Key>,
+    keys_start: Option<Instant>,
+}
+
+impl TypingTest {
+    /// Starts a test on the given lines, its speed counted in `metric`.
+    pub fn new(lines: Vec<String>, rules: Rules, metric: Metric) -> Self {
+        TypingTest {
+            clock: Box::new(SystemClock),
+            rules,
+            metric,
+
+            lines: lines.iter().map(|line| graphemes(line)).collect(),
+            current_line: 0,
+            typing: Vec::new(),
+            typed: Vec::new(),
+            extras: Vec::new(),
+            typed_extras: Vec::new(),
+
+            correct: 0,
+            incorrect: 0,
+            case_errors: 0,
+            corrected: 0,
+            words: 0,
+
+            start: None,
+            done: None,
+
+            samples: Vec::new(),
+            splits: Vec::new(),
+            line_wpms: Vec::new(),
+            word_errors: HashMap::new(),
+            letters: Letters::default(),
+            hits: Vec::new(),
+            word_from: None,
+            word_keys: 0,
+            word_clean: true,
+            word_burst: 0.,
+            pauses: Vec::new(),
+            shifted: Vec::new(),
+            shift_held: false,
+            streak: 0,
+            best_streak: 0,
+
+            dead_key: None,
+            invalid: None,
+            keys: Vec::new(),
+            keys_start: None,
+        }
+    }
+
+    /// Takes the time from `clock` instead of the system.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.set_clock(clock);
+        self
+    }
+
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) {
+        self.clock = Box::new(clock);
+    }
+
+    /// Starts over on the same lines.
+    pub fn restart(&mut self) {
+        let lines = self.lines.iter().map(|line| line.concat()).collect();
+        let clock = std::mem::replace(&mut self.clock, Box::new(SystemClock));
+        *self = TypingTest::new(lines, self.rules, self.metric);
+        self.clock = clock;
+    }
+
+    /// Handles a key as typed on the keyboard, holding dead keys back until the letter they
+    /// accent.
+    pub fn type_key(&mut self, char: char) -> Option<Milestone> {
+        let mut milestone = None;
+        for char in self.compose(char) {
+            milestone = self.type_char(char).or(milestone);
+        }
+
+        milestone
+    }
+
+    /// Types a whole composed string at once, like an IME commit.
+    pub fn type_text(&mut self, text: &str) -> Option<Milestone> {
+        let mut milestone = None;
+        for char in text.chars() {
+            milestone = self.type_char(char).or(milestone);
+        }
+
+        milestone
+    }
+
+    /// Types a single character, with no dead key handling.
+    pub fn type_char(&mut self, char: char) -> Option<Milestone> {
+        self.type_char_at(char, self.clock.now())
+    }
+
+    fn type_char_at(&mut self, char: char, now: Instant) -> Option<Milestone> {
+        if self.done.is_some() {
+            return None;
+        }
+        let previous = self.keys.last().map(|key| key.0);
+        self.record_key(char, now);
+
+        // A combining mark typed on its own belongs to the character before it
+        if let Some(last) = self.typing.last_mut()
+            && !is_blank(last)
+            && last != SKIPPED
+            && is_combining(last, char)
+        {
+            last.push(char);
+            *last = compose::nfc(last);
+            return None;
+        }
+
+        let part = &self.lines[self.current_line];
+        let expected = part.get(self.typing.len()).map(String::as_str);
+
+        // Space at the start of a line is the habit of ending the previous one with it
+        if char.is_whitespace() && self.typing.is_empty() && expected != Some(" ") {
+            return None;
+        }
+        // The clock starts on the first key that counts, not a stray one before it
+        if self.start.is_none() {
+            self.start = Some(now);
+        }
+        let since = previous.map(|previous| self.keys[self.keys.len() - 1].0 - previous);
+        let position = (self.current_line, self.typing.len());
+        if expected.is_some()
+            && since.is_some_and(|since| since > latency::HESITATION_MS)
+            && self.pauses.last() != Some(&position)
+        {
+            self.pauses.push(position);
+        }
+        if let Some(expected) = expected
+            && let Some((letter, hit, ms)) = self.letter_typed(char, expected, since)
+        {
+            self.letters.add(letter, hit as u32, !hit as u32, ms);
+            let previous = self
+                .typing
+                .len()
+                .checked_sub(1)
+                .and_then(|i| single_letter(&part[i]));
+            if let Some(previous) = previous {
+                self.letters
+                    .add_bigram(previous, letter, hit as u32, !hit as u32, ms);
+            }
+        }
+
+        let right = expected.is_some_and(|expected| self.matches(&char.to_string(), expected));
+        self.streak = if right { self.streak + 1 } else { 0 };
+        self.best_streak = self.best_streak.max(self.streak);
+
+        // The mistake is counted but the caret stays put until the right key is pressed
+        if self.rules.stop_on_error
+            && let Some(expected) = expected
+            && !right
+        {
+            self.word_clean = false;
+            if is_case_error(&char.to_string(), expected) {
+                self.case_errors += 1;
+            }
+            self.corrected += 1;
+            return None;
+        }
+
+        if char.is_whitespace() {
+            if expected != Some(" ") {
+                if self.rules.strict_space || self.typing.last().is_none_or(|g| is_blank(g)) {
+                    return None;
+                }
+
+                let rest = part[self.typing.len()..]
+                    .iter()
+                    .take_while(|g| *g != " ")
+                    .count();
+                self.typing
+                    .extend(std::iter::repeat_n(SKIPPED.to_string(), rest));
+                self.word_clean = false;
+                // Skipping the last word ends the quote
+                if part.len() == self.typing.len() {
+                    return Some(self.complete_line(now));
+                }
+            }
+
+            self.words += 1;
+        }
+        if !char.is_whitespace() && expected == Some(" ") {
+            self.word_clean = false;
+            self.push_extra(char);
+            return None;
+        }
+
+        self.typing.push(char.to_string());
+        let ms = self.keys.last().map_or(0, |key| key.0);
+        if right {
+            self.hits.push(ms);
+        }
+        let line_over = part.len() == self.typing.len();
+        self.time_word(ms, right, line_over || char.is_whitespace());
+
+        if line_over {
+            return Some(self.complete_line(now));
+        } else if char.is_whitespace() {
+            self.record_sample(now);
+        }
+
+        None
+    }
+
+    pub fn backspace(&mut self) {
+        self.backspace_at(self.clock.now())
+    }
+
+    fn backspace_at(&mut self, now: Instant) {
+        if self.done.is_some() || self.dead_key.take().is_some() {
+            return;
+        }
+        self.record_key(replay::BACKSPACE, now);
+        self.word_clean = false;
+
+        if let Some((pos, extra)) = self.extras.last_mut()
+            && *pos == self.typing.len()
+        {
+            extra.pop();
+            if extra.is_empty() {
+                self.extras.pop();
+            }
+            self.corrected += 1;
+            return;
+        }
+
+        self.pop_typed();
+    }
+
+    /// Deletes back to the start of the word being typed, or of the previous one when
+    /// right after a space.
+    pub fn delete_word(&mut self) {
+        self.delete_word_at(self.clock.now())
+    }
+
+    fn delete_word_at(&mut self, now: Instant) {
+        if self.done.is_some() {
+            return;
+        }
+        self.record_key(replay::DELETE_WORD, now);
+        self.word_clean = false;
+
+        while self.typing.last().is_some_and(|g| is_blank(g)) {
+            self.pop_typed();
+        }
+        while self.typing.last().is_some_and(|g| !is_blank(g)) {
+            self.pop_typed();
+        }
+
+        let len = self.typing.len();
+        for (pos, extra) in &self.extras {
+            if *pos > len {
+                self.corrected += extra.len() as u32;
+            }
+        }
+        self.extras.retain(|(pos, _)| *pos <= len);
+    }
+
+    /// Feeds a key recorded in a replay.
+    pub fn play_key(&mut self, key: char) -> Option<Milestone> {
+        let at = self.clock.now();
+        match key {
+            replay::BACKSPACE => {
+                self.backspace_at(at);
+                None
+            }
+            replay::DELETE_WORD => {
+                self.delete_word_at(at);
+                None
+            }
+            char => self.type_char_at(char, at),
+        }
+    }
+
+    /// Ends a running test where it is, like when its time is up.
+    pub fn stop(&mut self) -> Option<Milestone> {
+        if !self.is_running() {
+            return None;
+        }
+
+        let now = self.clock.now();
+        self.done = Some(now);
+        self.record_sample(now);
+        Some(Milestone::Finished)
+    }
+
+    /// Marks the result as untrustworthy, it should then be kept out of the history.
+    pub fn invalidate(&mut self, reason: &'static str) {
+        self.invalid = Some(reason);
+    }
+
+    /// Holds back dead keys until the next key, and turns the two into an accented letter
+    /// when they make one.
+    fn compose(&mut self, char: char) -> Vec<char> {
+        if let Some(dead) = self.dead_key.take() {
+            return match compose::compose(dead, char) {
+                Some(composed) => vec![composed],
+                // Dead key then space types the accent itself
+                None if char == ' ' => vec![dead],
+                None => vec![dead, char],
+            };
+        }
+
+        let wanted = self.expected().is_some_and(|g| g.starts_with(char));
+        if self.rules.dead_keys && compose::is_dead_key(char) && !wanted {
+            self.dead_key = Some(char);
+            return Vec::new();
+        }
+
+        vec![char]
+    }
+
+    /// The letter expected, whether it was hit and in how long since the key before, for the
+    /// letter stats. The first key of a test has no time, it only counts when it is a miss.
+    fn letter_typed(
+        &self,
+        char: char,
+        expected: &str,
+        since_ms: Option<u32>,
+    ) -> Option<(char, bool, f32)> {
+        let letter = single_letter(expected)?;
+        match (self.matches(&char.to_string(), expected), since_ms) {
+            (true, Some(ms)) => Some((letter, true, ms as f32)),
+            (true, None) => None,
+            (false, _) => Some((letter, false, 0.)),
+        }
+    }
+
+    /// Counts a key toward the speed of the word being typed, and keeps that speed once the word
+    /// is over if it had no mistake. The first key of the test only starts the clock.
+    fn time_word(&mut self, ms: u32, right: bool, word_over: bool) {
+        self.word_clean &= right;
+        match self.word_from {
+            None => self.word_from = Some(ms),
+            Some(_) => self.word_keys += 1,
+        }
+        if !word_over {
+            return;
+        }
+
+        if let Some(from) = self.word_from
+            && self.word_clean
+            && self.word_keys > 0
+        {
+            let duration = Duration::from_millis((ms - from) as u64);
+            let speed = self.metric.speed(self.word_keys, duration);
+            self.word_burst = self.word_burst.max(speed);
+        }
+        self.word_from = Some(ms);
+        self.word_keys = 0;
+        self.word_clean = true;
+    }
+
+    /// Notes a key for the replay.
+    fn record_key(&mut self, key: char, now: Instant) {
+        let start = *self.keys_start.get_or_insert(now);
+        let elapsed = now.duration_since(start);
+        if self.shift_held {
+            self.shifted.push(self.keys.len());
+        }
+        self.keys.push(replay::Key(elapsed.as_millis() as u32, key));
+    }
+
+    /// Removes the last typed grapheme, remembering if it was a mistake.
+    fn pop_typed(&mut self) -> Option<String> {
+        let grapheme = self.typing.pop()?;
+        let expected = &self.lines[self.current_line][self.typing.len()];
+
+        if !self.matches(&grapheme, expected) {
+            self.corrected += 1;
+        }
+        if is_blank(&grapheme) {
+            self.words = self.words.saturating_sub(1);
+        }
+
+        Some(grapheme)
+    }
+
+    /// Keeps a character typed where a space was expected, as an error shown after the word.
+    fn push_extra(&mut self, char: char) {
+        if !self.rules.overtype {
+            return;
+        }
+
+        let pos = self.typing.len();
+        match self.extras.last_mut() {
+            Some((last, extra)) if *last == pos => {
+                if extra.len() < MAX_EXTRAS {
+                    extra.push(char);
+                }
+            }
+            _ => self.extras.push((pos, vec![char])),
+        }
+    }
+
+    fn count_mistakes(&mut self) {
+        let line = &self.lines[self.current_line];
+        let (correct, incorrect) = self.line_counts(line, &self.typing, &self.extras);
+        self.correct += correct;
+        self.incorrect += incorrect;
+
+        let mut word = String::new();
+        let mut word_incorrect = 0;
+        // Extras are kept in typing order, so they are walked along with the line
+        let mut extras = self.extras.iter().peekable();
+
+        for (i, typed) in self.typing.iter().enumerate() {
+            let expected = line.get(i).map(String::as_str).unwrap_or(" ");
+
+            if !self.matches(typed, expected) {
+                word_incorrect += 1;
+
+                if is_case_error(typed, expected) {
+                    self.case_errors += 1;
+                }
+            }
+
+            if expected != " " {
+                word.push_str(expected);
+            }
+            if let Some((_, extra)) = extras.next_if(|(pos, _)| *pos <= i + 1) {
+                word_incorrect += extra.len() as u32;
+            }
+            if expected == " " || i + 1 == self.typing.len() {
+                if word_incorrect > 0 && !word.is_empty() {
+                    *self.word_errors.entry(word.clone()).or_insert(0) += word_incorrect;
+                }
+                word.clear();
+                word_incorrect = 0;
+            }
+        }
+    }
+
+    fn record_sample(&mut self, now: Instant) {
+        let elapsed = self.elapsed_at(now);
+        self.samples
+            .push((elapsed.as_secs_f64(), self.speed(elapsed) as f64));
+    }
+
+    fn complete_line(&mut self, now: Instant) -> Milestone {
+        let correct_before = self.correct;
+        self.count_mistakes();
+
+        self.typed.push(std::mem::take(&mut self.typing));
+        self.typed_extras.push(std::mem::take(&mut self.extras));
+
+        let elapsed = self.elapsed_at(now);
+        let split = elapsed.saturating_sub(self.splits.iter().sum());
+        let line_correct = self.correct - correct_before;
+        // Without a trailing space, no space press counts the last word of the line
+        if self.lines[self.current_line]
+            .last()
+            .is_some_and(|g| !is_blank(g))
+            && self.current_line + 1 < self.lines.len()
+        {
+            self.words += 1;
+        }
+        self.splits.push(split);
+        self.line_wpms.push(self.metric.speed(line_correct, split));
+        self.current_line += 1;
+
+        if self.current_line + 1 > self.lines.len() {
+            self.done = Some(now);
+            // The last word has no space after it to count it
+            self.words += 1;
+            self.record_sample(now);
+            Milestone::Finished
+        } else {
+            self.record_sample(now);
+            Milestone::LineCompleted
+        }
+    }
+
+    /// Whether a typed grapheme counts as the expected one.
+    pub fn matches(&self, typed: &str, expected: &str) -> bool {
+        typed == expected
+            || (self.rules.punctuation == Punctuation::Accept
+                && punctuation::is_plain_equivalent(typed, expected))
+    }
+
+    /// Correct and incorrect graphemes of a line as typed, extra characters counting as
+    /// errors. Both the live stats and `count_mistakes` go through it so they always agree.
+    fn line_counts(&self, line: &[String], typed: &[String], extras: &Extras) -> (u32, u32) {
+        let mut correct = 0;
+        let mut incorrect = 0;
+
+        for (typed, expected) in typed.iter().zip(line) {
+            if self.matches(typed, expected) {
+                correct += 1;
+            } else {
+                incorrect += 1;
+            }
+        }
+        for (_, extra) in extras {
+            incorrect += extra.len() as u32;
+        }
+
+        (correct, incorrect)
+    }
+
+    /// Correct and incorrect characters so far, including the line being typed.
+    pub fn live_counts(&self) -> (u32, u32) {
+        let (correct, incorrect) = match self.lines.get(self.current_line) {
+            Some(line) => self.line_counts(line, &self.typing, &self.extras),
+            None => (0, 0),
+        };
+
+        (self.correct + correct, self.incorrect + incorrect)
+    }
+
+    /// Share of keystrokes that were right, mistakes fixed since then included.
+    pub fn accuracy(&self) -> f32 {
+        let (correct, incorrect) = self.live_counts();
+        stats::accuracy(correct, incorrect + self.corrected)
+    }
+
+    /// Words per minute from the correct characters, or characters per minute for languages
+    /// scored that way.
+    pub fn speed(&self, duration: Duration) -> f32 {
+        let (correct, _) = self.live_counts();
+        self.metric.speed(correct, duration)
+    }
+
+    /// Speed over each full minute of the test, from the right keys typed in it.
+    pub fn minute_speeds(&self) -> Vec<f32> {
+        let minutes = self.elapsed().as_secs() / 60;
+        (0..minutes as u32)
+            .map(|minute| {
+                let (from, to) = (minute * 60_000, (minute + 1) * 60_000);
+                let keys = self
+                    .hits
+                    .iter()
+                    .filter(|ms| (from..to).contains(*ms))
+                    .count();
+                self.metric.speed(keys as u32, Duration::from_secs(60))
+            })
+            .collect()
+    }
+
+    /// Most right keys typed in a row, without a wrong one between them.
+    pub fn clean_streak(&self) -> u32 {
+        self.best_streak
+    }
+
+    /// Best speed over any `BURST_WINDOW` of the test, or over the whole of a shorter one but
+    /// never less than `MIN_BURST_WINDOW`.
+    pub fn burst(&self) -> f32 {
+        let elapsed = self.hits.last().copied().unwrap_or(0);
+        let window = elapsed.clamp(MIN_BURST_WINDOW, BURST_WINDOW);
+        let mut from = 0;
+        let mut best = 0;
+        for (to, hit) in self.hits.iter().enumerate() {
+            while hit - self.hits[from] >= window {
+                from += 1;
+            }
+            best = best.max(to - from + 1);
+        }
+        self.metric
+            .speed(best as u32, Duration::from_millis(window as u64))
+    }
+
+    /// Best speed over a word typed without a mistake, from the end of the word before.
+    pub fn word_burst(&self) -> f32 {
+        self.word_burst
+    }
+
+    /// How each letter was typed in this test.
+    pub fn letters(&self) -> &Letters {
+        &self.letters
+    }
+
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    pub fn unit(&self) -> &'static str {
+        self.metric.unit()
+    }
+
+    /// Time since the first keystroke, up to the end of the test once it is done.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed_at(self.clock.now())
+    }
+
+    fn elapsed_at(&self, now: Instant) -> Duration {
+        let Some(start) = self.start else {
+            return Duration::from_secs(0);
+        };
+
+        self.done.unwrap_or(now).duration_since(start)
+    }
+
+    /// Whether the last `streak` letters were all typed in the wrong case.
+    pub fn wrong_case_streak(&self, streak: usize) -> bool {
+        let Some(line) = self.lines.get(self.current_line) else {
+            return false;
+        };
+        let letters = self
+            .typing
+            .iter()
+            .zip(line)
+            .rev()
+            .filter(|(_, expected)| expected.chars().all(char::is_alphabetic))
+            .take(streak);
+
+        let mut wrong = 0;
+        for (typed, expected) in letters {
+            if !is_case_error(typed, expected) {
+                return false;
+            }
+            wrong += 1;
+        }
+
+        wrong == streak
+    }
+
+    /// The history entry for this attempt.
+    pub fn record(&self, quote_id: u32, group: usize) -> history::Record {
+        let duration = self.elapsed();
+        history::Record {
+            metric: self.metric,
+            burst: self.burst(),
+            word_burst: self.word_burst,
+            clean_streak: self.best_streak,
+            ..history::Record::now(
+                quote_id,
+                group,
+                self.speed(duration),
+                self.accuracy(),
+                self.words,
+                duration.as_secs_f32(),
+            )
+        }
+    }
+
+    /// Every key typed so far, to play this attempt back later.
+    pub fn replay(&self, quote_id: u32) -> replay::Replay {
+        replay::Replay::now(
+            quote_id,
+            self.lines.iter().map(|line| line.concat()).collect(),
+            self.rules,
+            self.keys.clone(),
+        )
+    }
+
+    /// Every key typed so far, timed from the first one.
+    pub fn keys(&self) -> &[replay::Key] {
+        &self.keys
+    }
+
+    /// Where keys were typed after a pause longer than `latency::HESITATION_MS`, as the line
+    /// and grapheme they landed on, in typing order.
+    pub fn pauses(&self) -> &[(usize, usize)] {
+        &self.pauses
+    }
+
+    /// Whether Shift is held for the keys typed next, as the terminal reports it.
+    pub fn hold_shift(&mut self, held: bool) {
+        self.shift_held = held;
+    }
+
+    /// Indices in `keys` of those typed with Shift held, as far as the terminal told.
+    pub fn shifted(&self) -> &[usize] {
+        &self.shifted
+    }
+
+    /// The next grapheme to type.
+    pub fn expected(&self) -> Option<&str> {
+        self.lines
+            .get(self.current_line)?
+            .get(self.typing.len())
+            .map(String::as_str)
+    }
+
+    /// Share of the text typed so far, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        let total: usize = self.lines.iter().map(Vec::len).sum();
+        if total == 0 {
+            return 0.;
+        }
+
+        let typed: usize = self
+            .lines
+            .iter()
+            .take(self.current_line)
+            .map(Vec::len)
+            .sum();
+        ((typed + self.typing.len()) as f32 / total as f32).min(1.)
+    }
+
+    /// Every line of the quote, split into graphemes.
+    pub fn lines(&self) -> &[Vec<String>] {
+        &self.lines
+    }
+
+    pub fn current_line(&self) -> usize {
+        self.current_line
+    }
+
+    /// What has been typed on the current line.
+    pub fn typing(&self) -> &[String] {
+        &self.typing
+    }
+
+    /// What was typed on each completed line.
+    pub fn typed(&self) -> &[Vec<String>] {
+        &self.typed
+    }
+
+    pub fn extras(&self) -> &Extras {
+        &self.extras
+    }
+
+    /// Extra characters of each completed line.
+    pub fn typed_extras(&self) -> &[Extras] {
+        &self.typed_extras
+    }
+
+    /// Whether nothing was typed yet, or all of it was deleted on the first line.
+    pub fn is_untouched(&self) -> bool {
+        self.typing.is_empty() && self.current_line == 0
+    }
+
+    /// Whether the timer is running: the first key was typed and the test isn't over yet.
+    pub fn is_running(&self) -> bool {
+        self.start.is_some() && self.done.is_none()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done.is_some()
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// Correct characters of the completed lines.
+    pub fn correct(&self) -> u32 {
+        self.correct
+    }
+
+    /// Incorrect characters of the completed lines.
+    pub fn incorrect(&self) -> u32 {
+        self.incorrect
+    }
+
+    pub fn case_errors(&self) -> u32 {
+        self.case_errors
+    }
+
+    pub fn corrected(&self) -> u32 {
+        self.corrected
+    }
+
+    /// Actual words typed, counted at every space.
+    pub fn words(&self) -> u32 {
+        self.words
+    }
+
+    /// Seconds elapsed and speed, taken at every word boundary.
+    pub fn samples(&self) -> &[(f64, f64)] {
+        &self.samples
+    }
+
+    /// Time spent on each completed line.
+    pub fn splits(&self) -> &[Duration] {
+        &self.splits
+    }
+
+    /// Speed on each completed line.
+    pub fn line_wpms(&self) -> &[f32] {
+        &self.line_wpms
+    }
+
+    /// Mistakes made in each word of the completed lines.
+    pub fn word_errors(&self) -> &HashMap<String, u32> {
+        &self.word_errors
+    }
+
+    pub fn invalid(&self) -> Option<&'static str> {
+        self.invalid
+    }
+}
+
+pub fn is_case_error(typed: &str, expected: &str) -> bool {
+    typed != expected && typed.to_lowercase() == expected.to_lowercase()
+}
+
+fn graphemes(text: &str) -> Vec<String> {
+    text.graphemes(true).map(str::to_string).collect()
+}
+
+pub fn is_blank(grapheme: &str) -> bool {
+    grapheme.chars().all(char::is_whitespace)
+}
+
+/// Whether `c` would merge into `grapheme` instead of starting a new one, like a combining accent.
+fn is_combining(grapheme: &str, c: char) -> bool {
+    format!("{grapheme}{c}").graphemes(true).count() == 1
+}
+
+/// The letter a grapheme is made of, none for anything else.
+fn single_letter(grapheme: &str) -> Option<char> {
+    let mut chars = grapheme.chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), None) if letter.is_alphabetic() => Some(letter),
+        _ => None,
+    }
+}
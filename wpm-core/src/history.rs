@@ -0,0 +1,301 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{mistakes::Mistakes, stats::Metric};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const HISTORY_FILE: &str = "history.jsonl";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Record {
+    pub timestamp: u64,
+    pub quote_id: u32,
+    pub group: usize,
+    pub wpm: f32,
+    pub accuracy: f32,
+    pub words: u32,
+    pub seconds: f32,
+    /// What `wpm` is counted in, words unless the language is scored in characters
+    #[serde(default, skip_serializing_if = "Metric::is_wpm")]
+    pub metric: Metric,
+    /// Best speed over 5 seconds, 0 for results older than bursts
+    #[serde(default)]
+    pub burst: f32,
+    /// Best speed over a word typed without a mistake
+    #[serde(default)]
+    pub word_burst: f32,
+    /// What the text was, like a language, a quote pack or a book, none for older results
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub category: String,
+    /// Most right keys in a row, 0 for results older than streaks
+    #[serde(default)]
+    pub clean_streak: u32,
+    /// Kinds of the mistakes left in the text
+    #[serde(default, skip_serializing_if = "Mistakes::is_empty")]
+    pub mistakes: Mistakes,
+    /// How hard the text was, from 0 to 10, 0 for results older than difficulty
+    #[serde(default)]
+    pub difficulty: f32,
+}
+
+impl Record {
+    pub fn now(
+        quote_id: u32,
+        group: usize,
+        wpm: f32,
+        accuracy: f32,
+        words: u32,
+        seconds: f32,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        Record {
+            timestamp,
+            quote_id,
+            group,
+            wpm,
+            accuracy,
+            words,
+            seconds,
+            metric: Metric::Wpm,
+            burst: 0.,
+            word_burst: 0.,
+            category: String::new(),
+            clean_streak: 0,
+            mistakes: Mistakes::default(),
+            difficulty: 0.,
+        }
+    }
+
+    pub fn unit(&self) -> &'static str {
+        self.metric.unit()
+    }
+}
+
+/// Directory holding everything the app persists between runs.
+pub fn data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("wpm"))
+}
+
+/// Loads every stored record, skipping lines that fail to parse.
+pub fn load() -> Vec<Record> {
+    let Some(path) = data_dir().map(|dir| dir.join(HISTORY_FILE)) else {
+        return Vec::new();
+    };
+
+    fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn append(record: &Record) -> io::Result<()> {
+    let dir = data_dir().ok_or(io::ErrorKind::NotFound)?;
+    fs::create_dir_all(&dir)?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(HISTORY_FILE))?;
+    writeln!(file, "{}", serde_json::to_string(record)?)
+}
+
+/// Timestamp of the last midnight, in the local time zone.
+pub fn start_of_today() -> u64 {
+    jiff::Zoned::now()
+        .start_of_day()
+        .map(|midnight| midnight.timestamp().as_second().max(0) as u64)
+        .unwrap_or(0)
+}
+
+/// Day of a timestamp in the local time zone, like `2024-05-17`.
+pub fn date(timestamp: u64) -> String {
+    jiff::Timestamp::from_second(timestamp as i64)
+        .map(|time| {
+            time.to_zoned(jiff::tz::TimeZone::system())
+                .date()
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+/// Records from `timestamp` on, the last ones since the history is kept in order.
+pub fn since(records: &[Record], timestamp: u64) -> &[Record] {
+    &records[records.partition_point(|r| r.timestamp < timestamp)..]
+}
+
+/// Best speed in words per minute, results counted in characters left out.
+pub fn personal_best(records: &[Record]) -> Option<f32> {
+    personal_best_in(records, Metric::Wpm)
+}
+
+/// Best speed of the results counted in `metric`.
+pub fn personal_best_in(records: &[Record], metric: Metric) -> Option<f32> {
+    records
+        .iter()
+        .filter(|r| r.metric == metric)
+        .map(|r| r.wpm)
+        .reduce(f32::max)
+}
+
+/// Best burst of the results counted in `metric`, none before the first one with bursts.
+pub fn peak_burst_in(records: &[Record], metric: Metric) -> Option<f32> {
+    records
+        .iter()
+        .filter(|r| r.metric == metric && r.burst > 0.)
+        .map(|r| r.burst)
+        .reduce(f32::max)
+}
+
+/// Most right keys in a row of all the results, 0 before the first one with streaks.
+pub fn longest_clean_streak(records: &[Record]) -> u32 {
+    records.iter().map(|r| r.clean_streak).max().unwrap_or(0)
+}
+
+/// Average speed over the last `count` records.
+pub fn recent_average(records: &[Record], count: usize) -> Option<f32> {
+    let recent = &records[records.len().saturating_sub(count)..];
+    if recent.is_empty() {
+        return None;
+    }
+
+    Some(recent.iter().map(|r| r.wpm).sum::<f32>() / recent.len() as f32)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct QuoteStats {
+    pub attempts: usize,
+    pub best_wpm: f32,
+    pub average_wpm: f32,
+    pub average_accuracy: f32,
+}
+
+/// Aggregates every previous attempt at the given quote.
+pub fn quote_stats(records: &[Record], quote_id: u32) -> Option<QuoteStats> {
+    let attempts: Vec<&Record> = records.iter().filter(|r| r.quote_id == quote_id).collect();
+    if attempts.is_empty() {
+        return None;
+    }
+
+    let count = attempts.len() as f32;
+    Some(QuoteStats {
+        attempts: attempts.len(),
+        best_wpm: attempts.iter().map(|r| r.wpm).fold(0., f32::max),
+        average_wpm: attempts.iter().map(|r| r.wpm).sum::<f32>() / count,
+        average_accuracy: attempts.iter().map(|r| r.accuracy).sum::<f32>() / count,
+    })
+}
+
+/// Results of a stretch of days.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Period {
+    pub tests: usize,
+    /// Average speed of the results in words per minute, none without any
+    pub wpm: Option<f32>,
+    pub accuracy: Option<f32>,
+    /// Time spent typing
+    pub minutes: f32,
+    /// Most right keys in a row in one of the results
+    pub clean_streak: u32,
+}
+
+impl Period {
+    pub fn of<'a>(records: impl IntoIterator<Item = &'a Record>) -> Self {
+        let all: Vec<&Record> = records.into_iter().collect();
+        let average = |records: &[&Record], value: fn(&Record) -> f32| {
+            (!records.is_empty())
+                .then(|| records.iter().map(|r| value(r)).sum::<f32>() / records.len() as f32)
+        };
+        let in_wpm: Vec<&Record> = all
+            .iter()
+            .copied()
+            .filter(|r| r.metric == Metric::Wpm)
+            .collect();
+        Period {
+            tests: all.len(),
+            wpm: average(&in_wpm, |r| r.wpm),
+            accuracy: average(&all, |r| r.accuracy),
+            minutes: all.iter().map(|r| r.seconds).sum::<f32>() / 60.,
+            clean_streak: all.iter().map(|r| r.clean_streak).max().unwrap_or(0),
+        }
+    }
+}
+
+/// Results grouped by category, the one typed the most first. Results older than categories
+/// are left out.
+pub fn categories(records: &[Record]) -> Vec<(&str, Period)> {
+    let mut categories: Vec<&str> = records
+        .iter()
+        .map(|r| r.category.as_str())
+        .filter(|category| !category.is_empty())
+        .collect();
+    categories.sort_unstable();
+    categories.dedup();
+
+    let mut periods: Vec<(&str, Period)> = categories
+        .into_iter()
+        .map(|category| {
+            let records = records.iter().filter(|r| r.category == category);
+            (category, Period::of(records))
+        })
+        .collect();
+    periods.sort_by_key(|(_, period)| std::cmp::Reverse(period.tests));
+    periods
+}
+
+/// The last `days` days before `now` and the as many days before them, to see how practice
+/// is going.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Comparison {
+    pub current: Period,
+    pub previous: Period,
+}
+
+impl Comparison {
+    /// Change in average speed, none unless both stretches have results in words per minute.
+    pub fn wpm(&self) -> Option<f32> {
+        Some(self.current.wpm? - self.previous.wpm?)
+    }
+
+    pub fn accuracy(&self) -> Option<f32> {
+        Some(self.current.accuracy? - self.previous.accuracy?)
+    }
+
+    pub fn tests(&self) -> i64 {
+        self.current.tests as i64 - self.previous.tests as i64
+    }
+
+    pub fn minutes(&self) -> f32 {
+        self.current.minutes - self.previous.minutes
+    }
+
+    pub fn clean_streak(&self) -> i64 {
+        self.current.clean_streak as i64 - self.previous.clean_streak as i64
+    }
+}
+
+/// Compares the rolling stretch of `days` days up to `now`, a timestamp, to the one before.
+pub fn compare(records: &[Record], now: u64, days: u64) -> Comparison {
+    let span = days * 24 * 60 * 60;
+    let start = now.saturating_sub(span);
+    let before = start.saturating_sub(span);
+    let between = |from: u64, to: u64| {
+        let records = since(records, from);
+        &records[..records.partition_point(|r| r.timestamp < to)]
+    };
+    Comparison {
+        current: Period::of(between(start, now.saturating_add(1))),
+        previous: Period::of(between(before, start)),
+    }
+}
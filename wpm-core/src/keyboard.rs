@@ -0,0 +1,342 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
+
+use crate::letters::Letters;
+
+/// Physical arrangement of the letters on the keyboard.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Layout {
+    #[default]
+    Qwerty,
+    Qwertz,
+    Azerty,
+    Dvorak,
+    Colemak,
+    Workman,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Row {
+    Top,
+    Home,
+    Bottom,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Finger {
+    Pinky,
+    Ring,
+    Middle,
+    Index,
+}
+
+/// Letters typed with another finger than the one of their column, like `b` with the right
+/// index.
+pub type Fingering = BTreeMap<char, (Hand, Finger)>;
+
+/// A letter key, and the finger touch typists press it with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub letter: char,
+    pub row: Row,
+    pub hand: Hand,
+    pub finger: Finger,
+}
+
+impl Layout {
+    /// Top, home and bottom rows from the leftmost key.
+    fn rows(self) -> [&'static str; 3] {
+        match self {
+            Layout::Qwerty => ["qwertyuiop[]", "asdfghjkl;'", "zxcvbnm,./"],
+            Layout::Qwertz => ["qwertzuiopü+", "asdfghjklöä#", "yxcvbnm,.-"],
+            Layout::Azerty => ["azertyuiop^$", "qsdfghjklmù*", "wxcvbn,;:!"],
+            Layout::Dvorak => ["',.pyfgcrl/=", "aoeuidhtns-", ";qjkxbmwvz"],
+            Layout::Colemak => ["qwfpgjluy;[]", "arstdhneio'", "zxcvbkm,./"],
+            Layout::Workman => ["qdrwbjfup;[]", "ashtgyneoi'", "zxmcvkl,./"],
+        }
+    }
+
+    /// The rows with Shift held.
+    fn shifted_rows(self) -> [&'static str; 3] {
+        match self {
+            Layout::Qwerty => ["QWERTYUIOP{}", "ASDFGHJKL:\"", "ZXCVBNM<>?"],
+            Layout::Qwertz => ["QWERTZUIOPÜ*", "ASDFGHJKLÖÄ'", "YXCVBNM;:_"],
+            Layout::Azerty => ["AZERTYUIOP¨£", "QSDFGHJKLM%µ", "WXCVBN?./§"],
+            Layout::Dvorak => ["\"<>PYFGCRL?+", "AOEUIDHTNS_", ":QJKXBMWVZ"],
+            Layout::Colemak => ["QWFPGJLUY:{}", "ARSTDHNEIO\"", "ZXCVBKM<>?"],
+            Layout::Workman => ["QDRWBJFUP:{}", "ASHTGYNEOI\"", "ZXMCVKL<>?"],
+        }
+    }
+
+    /// The number row with Shift held, from the key left of 1.
+    fn shifted_numbers(self) -> &'static str {
+        match self {
+            Layout::Qwerty | Layout::Colemak | Layout::Workman => "~!@#$%^&*()_+",
+            Layout::Qwertz => "°!\"§$%&/()=?`",
+            Layout::Azerty => "1234567890°+",
+            Layout::Dvorak => "~!@#$%^&*(){}",
+        }
+    }
+
+    /// Whether typing `c` takes Shift, like capitals and most symbols.
+    pub fn needs_shift(self, c: char) -> bool {
+        c.is_uppercase()
+            || self.shifted_numbers().contains(c)
+            || self.shifted_rows().iter().any(|row| row.contains(c))
+    }
+
+    /// What the key typing `c` on this layout types on `target`, to learn a layout without
+    /// switching the one of the system. Keys outside of the three letter rows stay as they are.
+    pub fn emulate(self, target: Layout, c: char) -> char {
+        [
+            (self.rows(), target.rows()),
+            (self.shifted_rows(), target.shifted_rows()),
+        ]
+        .into_iter()
+        .flat_map(|(rows, target_rows)| rows.into_iter().zip(target_rows))
+        .find_map(|(row, target_row)| {
+            let column = row.chars().position(|key| key == c)?;
+            target_row.chars().nth(column)
+        })
+        .unwrap_or(c)
+    }
+
+    /// Where the key of `c` is: its row from the top, and how far it is from the left edge in
+    /// key widths, rows being staggered.
+    fn position(self, c: char) -> Option<(usize, f32)> {
+        // How far each row is shifted right of the top one
+        const STAGGER: [f32; 3] = [0., 0.25, 0.75];
+        [self.rows(), self.shifted_rows()]
+            .into_iter()
+            .find_map(|rows| {
+                rows.iter().enumerate().find_map(|(row, keys)| {
+                    let column = keys.chars().position(|key| key == c)?;
+                    Some((row, column as f32 + STAGGER[row]))
+                })
+            })
+    }
+
+    /// Whether the keys of two characters touch, like `s` and `e` on QWERTY. The same key
+    /// doesn't touch itself.
+    pub fn adjacent(self, a: char, b: char) -> bool {
+        match (self.position(a), self.position(b)) {
+            (Some((row_a, x_a)), Some((row_b, x_b))) => {
+                row_a.abs_diff(row_b) <= 1
+                    && (x_a - x_b).abs() <= 1.
+                    && (row_a, x_a) != (row_b, x_b)
+            }
+            _ => false,
+        }
+    }
+
+    /// Every letter key of the layout, on the fingers of `fingering` for those it has.
+    pub fn keys(self, fingering: &Fingering) -> Vec<Key> {
+        let rows = [Row::Top, Row::Home, Row::Bottom];
+        rows.into_iter()
+            .zip(self.rows())
+            .flat_map(|(row, letters)| {
+                letters
+                    .chars()
+                    .enumerate()
+                    .filter_map(move |(column, letter)| {
+                        let (hand, finger) = fingering
+                            .get(&letter)
+                            .copied()
+                            .unwrap_or_else(|| finger(column));
+                        letter.is_alphabetic().then_some(Key {
+                            letter,
+                            row,
+                            hand,
+                            finger,
+                        })
+                    })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", lowercase(self))
+    }
+}
+
+/// Hand and finger of a column of keys, the index fingers taking two columns each and the
+/// right pinky whatever is past the tenth.
+fn finger(column: usize) -> (Hand, Finger) {
+    match column {
+        0 => (Hand::Left, Finger::Pinky),
+        1 => (Hand::Left, Finger::Ring),
+        2 => (Hand::Left, Finger::Middle),
+        3 | 4 => (Hand::Left, Finger::Index),
+        5 | 6 => (Hand::Right, Finger::Index),
+        7 => (Hand::Right, Finger::Middle),
+        8 => (Hand::Right, Finger::Ring),
+        _ => (Hand::Right, Finger::Pinky),
+    }
+}
+
+/// Keys a drill keeps to. Each part left empty allows any key.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyFilter {
+    pub hand: Option<Hand>,
+    pub rows: Vec<Row>,
+    pub fingers: Vec<Finger>,
+}
+
+impl KeyFilter {
+    /// Narrows the filter by a word like `left`, `home` or `index`, false for unknown ones.
+    pub fn add(&mut self, word: &str) -> bool {
+        match word {
+            "left" => self.hand = Some(Hand::Left),
+            "right" => self.hand = Some(Hand::Right),
+            "top" => self.rows.push(Row::Top),
+            "home" => self.rows.push(Row::Home),
+            "bottom" => self.rows.push(Row::Bottom),
+            "pinky" => self.fingers.push(Finger::Pinky),
+            "ring" => self.fingers.push(Finger::Ring),
+            "middle" => self.fingers.push(Finger::Middle),
+            "index" => self.fingers.push(Finger::Index),
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn allows(&self, key: &Key) -> bool {
+        self.hand.is_none_or(|hand| key.hand == hand)
+            && (self.rows.is_empty() || self.rows.contains(&key.row))
+            && (self.fingers.is_empty() || self.fingers.contains(&key.finger))
+    }
+
+    /// The letters of the layout allowed.
+    pub fn letters(&self, layout: Layout, fingering: &Fingering) -> Vec<char> {
+        layout
+            .keys(fingering)
+            .iter()
+            .filter(|key| self.allows(key))
+            .map(|key| key.letter)
+            .collect()
+    }
+}
+
+impl fmt::Display for KeyFilter {
+    /// Like "left hand, home and top rows".
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(hand) = self.hand {
+            parts.push(format!("{} hand", lowercase(hand)));
+        }
+        for (names, one, many) in [
+            (
+                self.rows.iter().map(lowercase).collect::<Vec<_>>(),
+                "row",
+                "rows",
+            ),
+            (
+                self.fingers.iter().map(lowercase).collect(),
+                "finger",
+                "fingers",
+            ),
+        ] {
+            match names.as_slice() {
+                [] => {}
+                [name] => parts.push(format!("{name} {one}")),
+                [first @ .., last] => parts.push(format!("{} and {last} {many}", first.join(", "))),
+            }
+        }
+        if parts.is_empty() {
+            write!(f, "all keys")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
+/// How much and how well a finger types, from the letters it has.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FingerStats {
+    pub hand: Hand,
+    pub finger: Finger,
+    pub keystrokes: u32,
+    pub misses: u32,
+    /// Share of all the keystrokes, in percent
+    pub load: f32,
+    /// Average time to type one of its letters, in milliseconds
+    pub time_ms: f32,
+}
+
+impl FingerStats {
+    /// Share of its keystrokes that were misses, in percent.
+    pub fn error_rate(&self) -> f32 {
+        if self.keystrokes == 0 {
+            return 0.;
+        }
+        self.misses as f32 / self.keystrokes as f32 * 100.
+    }
+}
+
+impl fmt::Display for FingerStats {
+    /// Like "left pinky".
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", lowercase(self.hand), lowercase(self.finger))
+    }
+}
+
+/// Stats of the eight fingers from the left pinky to the right one, for letters typed on
+/// `layout`. Letters of no key of the layout are left out.
+pub fn finger_stats(letters: &Letters, layout: Layout, fingering: &Fingering) -> Vec<FingerStats> {
+    let keys = layout.keys(fingering);
+    let fingers = [Finger::Pinky, Finger::Ring, Finger::Middle, Finger::Index];
+    let mut stats: Vec<FingerStats> = fingers
+        .iter()
+        .map(|finger| (Hand::Left, *finger))
+        .chain(fingers.iter().rev().map(|finger| (Hand::Right, *finger)))
+        .map(|(hand, finger)| FingerStats {
+            hand,
+            finger,
+            keystrokes: 0,
+            misses: 0,
+            load: 0.,
+            time_ms: 0.,
+        })
+        .collect();
+
+    for (c, letter) in letters.iter() {
+        let Some(key) = keys.iter().find(|key| key.letter == c) else {
+            continue;
+        };
+        let Some(stats) = stats
+            .iter_mut()
+            .find(|s| s.hand == key.hand && s.finger == key.finger)
+        else {
+            continue;
+        };
+        // Averaged over the hits, which are the only keys timed
+        let hits = stats.keystrokes - stats.misses;
+        if letter.hits > 0 {
+            stats.time_ms = (stats.time_ms * hits as f32 + letter.time_ms * letter.hits as f32)
+                / (hits + letter.hits) as f32;
+        }
+        stats.keystrokes += letter.hits + letter.misses;
+        stats.misses += letter.misses;
+    }
+
+    let total: u32 = stats.iter().map(|s| s.keystrokes).sum();
+    for stats in &mut stats {
+        stats.load = stats.keystrokes as f32 / total.max(1) as f32 * 100.;
+    }
+    stats
+}
+
+fn lowercase(value: impl fmt::Debug) -> String {
+    format!("{value:?}").to_lowercase()
+}
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+use crate::{history, replay};
+
+const LATENCY_FILE: &str = "latency.json";
+/// Width of a bar of the histogram, in milliseconds.
+pub const BUCKET_MS: u32 = 50;
+/// Bars of the histogram, the last one holding every interval of a second or more.
+pub const BUCKETS: usize = 21;
+/// Intervals longer than this are hesitations rather than finger speed, in milliseconds.
+pub const HESITATION_MS: u32 = 500;
+
+/// How long the keys of one or more tests took after the key before them, in bars of
+/// `BUCKET_MS`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Histogram {
+    counts: Vec<u32>,
+    total_ms: u64,
+    // time spent past `HESITATION_MS` in the hesitations
+    lost_ms: u64,
+}
+
+impl Histogram {
+    /// The intervals between the keys of a test, corrections included.
+    pub fn of(keys: &[replay::Key]) -> Self {
+        let mut histogram = Histogram::default();
+        for pair in keys.windows(2) {
+            histogram.add(pair[1].0.saturating_sub(pair[0].0));
+        }
+        histogram
+    }
+
+    fn path() -> Option<PathBuf> {
+        history::data_dir().map(|dir| dir.join(LATENCY_FILE))
+    }
+
+    /// The intervals of every test so far, none before the first one.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or(io::ErrorKind::NotFound)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    pub fn add(&mut self, interval_ms: u32) {
+        self.counts.resize(BUCKETS, 0);
+        let bucket = (interval_ms / BUCKET_MS) as usize;
+        self.counts[bucket.min(BUCKETS - 1)] += 1;
+        self.total_ms += interval_ms as u64;
+        self.lost_ms += interval_ms.saturating_sub(HESITATION_MS) as u64;
+    }
+
+    /// Adds the intervals of another histogram, like the one of a test.
+    pub fn merge(&mut self, other: &Histogram) {
+        self.counts.resize(BUCKETS, 0);
+        for (count, other) in self.counts.iter_mut().zip(&other.counts) {
+            *count += other;
+        }
+        self.total_ms += other.total_ms;
+        self.lost_ms += other.lost_ms;
+    }
+
+    /// Intervals in each bar, from the shortest.
+    pub fn counts(&self) -> [u32; BUCKETS] {
+        let mut counts = [0; BUCKETS];
+        for (count, stored) in counts.iter_mut().zip(&self.counts) {
+            *count = *stored;
+        }
+        counts
+    }
+
+    pub fn total(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    /// Share of the intervals in each bar, in percent.
+    pub fn shares(&self) -> [f32; BUCKETS] {
+        let total = self.total().max(1) as f32;
+        self.counts().map(|count| count as f32 / total * 100.)
+    }
+
+    /// The interval under which half of them are, to the bar, in milliseconds.
+    pub fn median(&self) -> u32 {
+        let half = self.total().div_ceil(2);
+        let mut seen = 0;
+        for (bucket, count) in self.counts().iter().enumerate() {
+            seen += count;
+            if seen >= half && seen > 0 {
+                return bucket as u32 * BUCKET_MS + BUCKET_MS / 2;
+            }
+        }
+        0
+    }
+
+    /// Intervals longer than `HESITATION_MS`.
+    pub fn hesitations(&self) -> u32 {
+        let first = (HESITATION_MS / BUCKET_MS) as usize;
+        self.counts()[first..].iter().sum()
+    }
+
+    /// Time the hesitations took past `HESITATION_MS`, in milliseconds: what typing without
+    /// them would save.
+    pub fn lost_ms(&self) -> u64 {
+        self.lost_ms
+    }
+
+    /// Share of the time lost to hesitations, in percent.
+    pub fn lost_share(&self) -> f32 {
+        match self.total_ms {
+            0 => 0.,
+            total => self.lost_ms as f32 / total as f32 * 100.,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+use crate::{
+    history::{self, Record},
+    worker::NETWORK_TIMEOUT,
+};
+
+const CACHE_FILE: &str = "leaderboard.json";
+
+/// A player of the group, as the server ranks them.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Standing {
+    pub name: String,
+    pub best_wpm: f32,
+    pub average_wpm: f32,
+    pub tests: usize,
+}
+
+/// What was synced last, kept between runs so results are pushed once and the standings can be
+/// shown offline.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Cache {
+    /// Records of the history already pushed, the history is only ever appended to
+    pub pushed: usize,
+    /// Standings as of the last pull, best first
+    pub standings: Vec<Standing>,
+}
+
+impl Cache {
+    fn path() -> Option<PathBuf> {
+        history::data_dir().map(|dir| dir.join(CACHE_FILE))
+    }
+
+    /// The last synced state, empty if never synced.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or(io::ErrorKind::NotFound)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+    }
+}
+
+/// Client of a self-hosted leaderboard shared by a group of friends. The server is reached with
+/// a bearer token, and only has to answer two requests:
+/// - `POST <url>/results` with `{"name": ..., "records": [...]}`, the new results of a player
+/// - `GET <url>/leaderboard`, answered with everyone's standings as a JSON array of
+///   `{"name": ..., "best_wpm": ..., "average_wpm": ..., "tests": ...}`
+#[derive(Debug)]
+pub struct Client {
+    url: String,
+    token: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    /// A client of the server at `url`, like `https://wpm.example.com`.
+    pub fn new(url: &str, token: String) -> Self {
+        Client {
+            url: url.trim_end_matches('/').to_string(),
+            token,
+            agent: ureq::Agent::config_builder()
+                .timeout_global(Some(NETWORK_TIMEOUT))
+                .build()
+                .into(),
+        }
+    }
+
+    fn authorization(&self) -> String {
+        format!("Bearer {}", self.token)
+    }
+
+    /// Sends results of the player called `name`.
+    pub fn push(&self, name: &str, records: &[Record]) -> io::Result<()> {
+        #[derive(Serialize)]
+        struct Push<'a> {
+            name: &'a str,
+            records: &'a [Record],
+        }
+
+        let body = serde_json::to_string(&Push { name, records })?;
+        self.agent
+            .post(format!("{}/results", self.url))
+            .header("Authorization", self.authorization())
+            .header("Content-Type", "application/json")
+            .send(body)
+            .map_err(io::Error::other)?;
+        Ok(())
+    }
+
+    /// Standings of the whole group, best first.
+    pub fn pull(&self) -> io::Result<Vec<Standing>> {
+        let body = self
+            .agent
+            .get(format!("{}/leaderboard", self.url))
+            .header("Authorization", self.authorization())
+            .call()
+            .and_then(|response| response.into_body().read_to_string())
+            .map_err(io::Error::other)?;
+
+        let mut standings: Vec<Standing> = serde_json::from_str(&body)?;
+        standings.sort_by(|a, b| b.best_wpm.total_cmp(&a.best_wpm));
+        Ok(standings)
+    }
+
+    /// Pushes the records not pushed yet and notes them in `cache`. Returns how many records
+    /// were pushed. Save the cache before pulling, or a failed pull gets them pushed again.
+    pub fn push_new(&self, name: &str, records: &[Record], cache: &mut Cache) -> io::Result<usize> {
+        // A history cleared since the last sync starts over
+        let new = records.get(cache.pushed..).unwrap_or(records);
+        if !new.is_empty() {
+            self.push(name, new)?;
+        }
+        cache.pushed = records.len();
+        Ok(new.len())
+    }
+}
@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use crate::{history, stats::CHARS_PER_WORD};
+
+const LETTERS_FILE: &str = "letters.json";
+// Practice needed by letters never typed, between known weak and strong ones
+const UNKNOWN_NEED: f32 = 0.5;
+
+/// How a letter was typed so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub struct Letter {
+    pub hits: u32,
+    pub misses: u32,
+    /// Average time to type it, in milliseconds
+    pub time_ms: f32,
+}
+
+impl Letter {
+    /// Counts keystrokes typed in `time_ms` on average, only hits being timed.
+    fn add(&mut self, hits: u32, misses: u32, time_ms: f32) {
+        if hits > 0 {
+            let total = self.hits + hits;
+            self.time_ms = (self.time_ms * self.hits as f32 + time_ms * hits as f32) / total as f32;
+        }
+        self.hits += hits;
+        self.misses += misses;
+    }
+
+    /// From 0 to 1, how much practice it needs: 0 when always right at `target_wpm` or
+    /// faster, more the slower or the more often missed it is.
+    fn need(&self, target_wpm: f32) -> f32 {
+        let keystrokes = self.hits + self.misses;
+        if keystrokes == 0 {
+            return UNKNOWN_NEED;
+        }
+        let accuracy = self.hits as f32 / keystrokes as f32;
+        let confidence = match self.hits {
+            0 => 0.,
+            _ => confidence(self.time_ms, target_wpm),
+        };
+        1. - confidence * accuracy
+    }
+}
+
+/// How well each letter is known, tracked like keybr.com does it: by the time each one takes
+/// to type, against a target speed. Pairs of letters typed in a row are tracked the same way,
+/// timed on their second letter.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Letters {
+    letters: BTreeMap<char, Letter>,
+    #[serde(default)]
+    bigrams: BTreeMap<String, Letter>,
+}
+
+// A result of a keybr.com data export, only what is read of it
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeybrResult {
+    histogram: Vec<KeybrKey>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeybrKey {
+    code_point: u32,
+    hit_count: u32,
+    miss_count: u32,
+    /// Average milliseconds to type the key in that result
+    time_to_type: f32,
+}
+
+impl Letters {
+    fn path() -> Option<PathBuf> {
+        history::data_dir().map(|dir| dir.join(LETTERS_FILE))
+    }
+
+    /// The letters stored so far, none if there are no stats yet.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or(io::ErrorKind::NotFound)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    pub fn get(&self, letter: char) -> Option<&Letter> {
+        self.letters.get(&fold(letter))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (char, &Letter)> {
+        self.letters.iter().map(|(c, letter)| (*c, letter))
+    }
+
+    /// Adds the stats of other letters, like those of a test.
+    pub fn merge(&mut self, other: &Letters) {
+        for (c, letter) in other.iter() {
+            self.add(c, letter.hits, letter.misses, letter.time_ms);
+        }
+        for (bigram, letter) in &other.bigrams {
+            self.bigrams.entry(bigram.clone()).or_default().add(
+                letter.hits,
+                letter.misses,
+                letter.time_ms,
+            );
+        }
+    }
+
+    /// Counts keystrokes of `second` right after `first`, like `add` does for letters.
+    pub fn add_bigram(&mut self, first: char, second: char, hits: u32, misses: u32, time_ms: f32) {
+        let bigram = String::from_iter([fold(first), fold(second)]);
+        self.bigrams
+            .entry(bigram)
+            .or_default()
+            .add(hits, misses, time_ms);
+    }
+
+    pub fn bigram(&self, first: char, second: char) -> Option<&Letter> {
+        self.bigrams
+            .get(&String::from_iter([fold(first), fold(second)]))
+    }
+
+    /// From 0 to 1, how much practice a letter needs at `target_wpm`, from its speed and misses.
+    /// Letters never typed are in the middle.
+    pub fn need(&self, letter: char, target_wpm: f32) -> f32 {
+        self.get(letter)
+            .map_or(UNKNOWN_NEED, |letter| letter.need(target_wpm))
+    }
+
+    /// Like `need`, for a pair of letters. Unknown for pairs never typed.
+    pub fn bigram_need(&self, first: char, second: char, target_wpm: f32) -> Option<f32> {
+        Some(self.bigram(first, second)?.need(target_wpm))
+    }
+
+    /// Pairs of letters typed so far, those needing the most practice first.
+    pub fn weakest_bigrams(&self, target_wpm: f32) -> Vec<&str> {
+        let mut bigrams: Vec<(&str, f32)> = self
+            .bigrams
+            .iter()
+            .map(|(bigram, letter)| (bigram.as_str(), letter.need(target_wpm)))
+            .collect();
+        bigrams.sort_by(|a, b| b.1.total_cmp(&a.1));
+        bigrams.into_iter().map(|(bigram, _)| bigram).collect()
+    }
+
+    /// Counts keystrokes of `letter` typed in `time_ms` on average, letters being told apart
+    /// regardless of case.
+    pub fn add(&mut self, letter: char, hits: u32, misses: u32, time_ms: f32) {
+        if hits == 0 && misses == 0 {
+            return;
+        }
+
+        self.letters
+            .entry(fold(letter))
+            .or_default()
+            .add(hits, misses, time_ms);
+    }
+
+    /// From 0 to 1, how close the letter is to being typed at `target_wpm`. Unknown for letters
+    /// never typed.
+    pub fn confidence(&self, letter: char, target_wpm: f32) -> Option<f32> {
+        let letter = self.get(letter).filter(|letter| letter.hits > 0)?;
+        Some(confidence(letter.time_ms, target_wpm))
+    }
+
+    /// Letters typed so far, least confident first.
+    pub fn weakest(&self, target_wpm: f32) -> Vec<char> {
+        let mut letters: Vec<(char, f32)> = self
+            .letters
+            .keys()
+            .filter_map(|&c| Some((c, self.confidence(c, target_wpm)?)))
+            .collect();
+        letters.sort_by(|a, b| a.1.total_cmp(&b.1));
+        letters.into_iter().map(|(c, _)| c).collect()
+    }
+
+    /// Adds the per-key stats of every result of a keybr.com data export, the JSON file its
+    /// profile page downloads. Returns how many results were read.
+    pub fn import_keybr(&mut self, json: &str) -> serde_json::Result<usize> {
+        let results: Vec<KeybrResult> = serde_json::from_str(json)?;
+        for key in results.iter().flat_map(|result| &result.histogram) {
+            if let Some(letter) = char::from_u32(key.code_point).filter(|c| c.is_alphabetic()) {
+                self.add(letter, key.hit_count, key.miss_count, key.time_to_type);
+            }
+        }
+        Ok(results.len())
+    }
+}
+
+/// From 0 to 1, how close typing in `time_ms` is to `target_wpm`.
+fn confidence(time_ms: f32, target_wpm: f32) -> f32 {
+    let target_ms = 60_000. / (target_wpm * CHARS_PER_WORD);
+    (target_ms / time_ms.max(1.)).min(1.)
+}
+
+fn fold(letter: char) -> char {
+    letter.to_lowercase().next().unwrap_or(letter)
+}
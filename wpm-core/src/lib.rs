@@ -0,0 +1,87 @@
+//! Typing test engine behind the `wpm` terminal app, free of any UI.
+//!
+//! - [`content`] holds the quotes and other sources of text, and prepares them for typing
+//! - [`engine`] is the typing state machine, fed one key at a time, timed by a [`clock`]
+//! - [`stats`] turns what was typed into speed and accuracy
+//! - [`mistakes`] sorts mistakes into slips like adjacent keys or swapped ones
+//! - [`diff`] marks the mistakes of a replay on its text, those fixed along the way included
+//! - [`latency`] sorts the time between keys into a histogram, to tell pauses from finger speed
+//! - [`rhythm`] measures how evenly keys come, against a steady beat
+//! - [`shift`] tells how much time the keys typed with Shift cost
+//! - [`letters`] tracks how well each letter is known, imported from keybr.com for now
+//! - [`endurance`] keeps long runs apart from the history, with how speed drops over them
+//! - [`course`] takes a touch typist from the home row to every key, lesson by lesson
+//! - [`custom`] builds drills out of the words and pairs of letters missed the most
+//! - [`review`] brings missed words back for review on a spaced repetition schedule
+//! - [`keyboard`] knows which hand, row and finger type each letter, for drills and to
+//!   emulate a layout on another
+//! - [`challenge`] packs a result into a code for a friend to beat
+//! - [`card`] draws a result as a picture to share
+//! - [`assignment`] sets texts for a class and grades what students send back
+//! - [`history`] and [`replay`] persist results and keystrokes between runs, written in the
+//!   background by [`storage`]
+//! - [`sink`] passes finished results on, to the history or anywhere else, like the chat
+//!   rooms of [`announce`]
+//! - [`achievements`] unlocks milestones like a first 100 WPM test or a week in a row
+//! - [`rating`] keeps how quotes felt to type, to see it again and pick them by it
+//! - [`xp`] awards points for each result, and levels for them
+//! - [`reminder`] tells when to remind of the daily practice, and how much was done
+//! - [`status`] sums the history up in a line, for status bars
+//! - [`metrics`] serves the history to Prometheus
+//! - [`race`] races other players over the local network
+//! - [`telemetry`] streams the test live to browser overlays
+//! - [`leaderboard`] syncs results with a leaderboard shared by friends
+//! - [`worker`] does writes and requests on a thread of their own, so nothing waits on them
+//! - [`sim`] plays scripted keys on a fake clock, for tests
+//!
+//! ```no_run
+//! use wpm_core::{content::{self, Library}, engine::{Rules, TypingTest}, stats::Metric};
+//!
+//! let library = Library::embedded();
+//! let quote = library.pick(0).unwrap();
+//! let rules = Rules::default();
+//! let lines = content::wrap(&content::prepare(&quote.text, rules.punctuation), 50, true);
+//!
+//! let mut test = TypingTest::new(lines, rules, Metric::Wpm);
+//! for c in quote.text.chars() {
+//!     test.type_char(c);
+//! }
+//! assert!(test.is_done());
+//! ```
+
+pub mod achievements;
+pub mod announce;
+pub mod assignment;
+pub mod card;
+pub mod challenge;
+pub mod clock;
+pub mod compose;
+pub mod content;
+pub mod course;
+pub mod custom;
+pub mod diff;
+pub mod endurance;
+pub mod engine;
+pub mod history;
+pub mod keyboard;
+pub mod latency;
+pub mod leaderboard;
+pub mod letters;
+pub mod metrics;
+pub mod mistakes;
+pub mod punctuation;
+pub mod race;
+pub mod rating;
+pub mod reminder;
+pub mod replay;
+pub mod review;
+pub mod rhythm;
+pub mod shift;
+pub mod sim;
+pub mod sink;
+pub mod stats;
+pub mod status;
+pub mod storage;
+pub mod telemetry;
+pub mod worker;
+pub mod xp;
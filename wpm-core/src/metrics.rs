@@ -0,0 +1,111 @@
+use std::{
+    fmt::Write as _,
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use crate::history::{self, Record};
+
+// A scraper this slow to send its request is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The history as Prometheus metrics, in its text format.
+pub fn render(records: &[Record]) -> String {
+    let last = records.last();
+    let average = (!records.is_empty())
+        .then(|| records.iter().map(|r| r.wpm).sum::<f32>() / records.len() as f32);
+
+    let mut text = String::new();
+    let mut metric = |name: &str, kind: &str, help: &str, value: Option<f64>| {
+        let _ = writeln!(text, "# HELP wpm_{name} {help}");
+        let _ = writeln!(text, "# TYPE wpm_{name} {kind}");
+        // Left out when there is nothing to measure yet, rather than a made up zero
+        if let Some(value) = value {
+            let _ = writeln!(text, "wpm_{name} {value}");
+        }
+    };
+    metric(
+        "tests_total",
+        "counter",
+        "Tests completed.",
+        Some(records.len() as f64),
+    );
+    metric(
+        "average_wpm",
+        "gauge",
+        "Average speed over all tests.",
+        average.map(f64::from),
+    );
+    metric(
+        "best_wpm",
+        "gauge",
+        "Fastest test.",
+        history::personal_best(records).map(f64::from),
+    );
+    metric(
+        "last_wpm",
+        "gauge",
+        "Speed of the latest test.",
+        last.map(|r| f64::from(r.wpm)),
+    );
+    metric(
+        "last_accuracy_percent",
+        "gauge",
+        "Accuracy of the latest test.",
+        last.map(|r| f64::from(r.accuracy)),
+    );
+    metric(
+        "last_test_timestamp_seconds",
+        "gauge",
+        "When the latest test was completed.",
+        last.map(|r| r.timestamp as f64),
+    );
+    text
+}
+
+/// Answers `GET /metrics` on the given port of the local machine in the background, any free
+/// port for 0. Each scrape renders the records `records` gives then, so results saved by
+/// another process show up too.
+pub fn serve(
+    port: u16,
+    records: impl Fn() -> Vec<Record> + Send + Sync + 'static,
+) -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    let addr = listener.local_addr()?;
+    let records = Arc::new(records);
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            // Each scrape on a thread of its own, so a silent client holds up no other
+            let records = records.clone();
+            thread::spawn(move || answer(stream, &*records));
+        }
+    });
+    Ok(addr)
+}
+
+fn answer(stream: TcpStream, records: &impl Fn() -> Vec<Record>) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut lines = BufReader::new(stream.try_clone()?).lines();
+    let request = lines.next().transpose()?.unwrap_or_default();
+    // The headers don't matter, but are read so the client isn't cut off mid-request
+    for line in lines {
+        if line?.is_empty() {
+            break;
+        }
+    }
+
+    let path = request.split_whitespace().nth(1).unwrap_or_default();
+    let (status, body) = match request.split_whitespace().next() {
+        Some("GET") if path == "/metrics" => ("200 OK", render(&records())),
+        Some("GET") => ("404 Not Found", "Metrics are at /metrics\n".to_string()),
+        _ => ("405 Method Not Allowed", String::new()),
+    };
+    write!(
+        &stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    engine::{SKIPPED, TypingTest},
+    keyboard::Layout,
+};
+
+/// What kind of slip a mistake was, from what was typed instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// A key next to the right one, a finger off its mark
+    Adjacent,
+    /// Two keys in the wrong order, one finger ahead of the other
+    Transposition,
+    /// A key left out
+    Omission,
+    /// A key too many
+    Insertion,
+    /// Anything else, like a wrong key far from the right one
+    Other,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 5] = [
+        Kind::Adjacent,
+        Kind::Transposition,
+        Kind::Omission,
+        Kind::Insertion,
+        Kind::Other,
+    ];
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", format!("{self:?}").to_lowercase())
+    }
+}
+
+/// How many mistakes of each kind were left in a text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Mistakes {
+    pub adjacent: u32,
+    pub transposition: u32,
+    pub omission: u32,
+    pub insertion: u32,
+    pub other: u32,
+}
+
+impl Mistakes {
+    pub fn get(&self, kind: Kind) -> u32 {
+        match kind {
+            Kind::Adjacent => self.adjacent,
+            Kind::Transposition => self.transposition,
+            Kind::Omission => self.omission,
+            Kind::Insertion => self.insertion,
+            Kind::Other => self.other,
+        }
+    }
+
+    pub fn add(&mut self, kind: Kind, count: u32) {
+        match kind {
+            Kind::Adjacent => self.adjacent += count,
+            Kind::Transposition => self.transposition += count,
+            Kind::Omission => self.omission += count,
+            Kind::Insertion => self.insertion += count,
+            Kind::Other => self.other += count,
+        }
+    }
+
+    pub fn merge(&mut self, other: &Mistakes) {
+        for kind in Kind::ALL {
+            self.add(kind, other.get(kind));
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        Kind::ALL.iter().map(|kind| self.get(*kind)).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    /// Share of the mistakes of a kind, in percent.
+    pub fn share(&self, kind: Kind) -> f32 {
+        match self.total() {
+            0 => 0.,
+            total => self.get(kind) as f32 / total as f32 * 100.,
+        }
+    }
+
+    /// The kinds there were and how many of each, most common first.
+    pub fn kinds(&self) -> Vec<(Kind, u32)> {
+        let mut kinds: Vec<(Kind, u32)> = (Kind::ALL.into_iter())
+            .map(|kind| (kind, self.get(kind)))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        kinds.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        kinds
+    }
+}
+
+/// Sorts the mistakes left in the completed lines of a test, lining each word typed up with
+/// the one expected. Keys are told apart on `layout`.
+pub fn classify(test: &TypingTest, layout: Layout) -> Mistakes {
+    let mut mistakes = Mistakes::default();
+    for ((line, typed), extras) in test
+        .lines()
+        .iter()
+        .zip(test.typed())
+        .zip(test.typed_extras())
+    {
+        let mut start = 0;
+        for end in (0..=line.len()).filter(|i| line.get(*i).is_none_or(|g| g == " ")) {
+            let expected = &line[start..end];
+            let mut attempt: Vec<&str> = typed
+                .get(start..end.min(typed.len()))
+                .unwrap_or_default()
+                .iter()
+                .map(String::as_str)
+                .filter(|g| *g != SKIPPED)
+                .collect();
+            let extra: Vec<String> = extras
+                .iter()
+                .filter(|(pos, _)| *pos == end)
+                .flat_map(|(_, chars)| chars.iter().map(char::to_string))
+                .collect();
+            attempt.extend(extra.iter().map(String::as_str));
+
+            let expected: Vec<&str> = expected.iter().map(String::as_str).collect();
+            for kind in align(&expected, &attempt, |a, b| test.matches(a, b), layout) {
+                mistakes.add(kind, 1);
+            }
+            start = end + 1;
+        }
+    }
+    mistakes
+}
+
+/// The mistakes of the cheapest way to turn `expected` into `typed`, one edit per key, two keys
+/// swapped counting as one.
+pub fn align(
+    expected: &[&str],
+    typed: &[&str],
+    matches: impl Fn(&str, &str) -> bool,
+    layout: Layout,
+) -> Vec<Kind> {
+    let (n, m) = (expected.len(), typed.len());
+    // Edits to turn the first i expected graphemes into the first j typed ones
+    let mut cost = vec![vec![0u32; m + 1]; n + 1];
+    for i in 0..=n {
+        for j in 0..=m {
+            cost[i][j] = match (i, j) {
+                (0, _) => j as u32,
+                (_, 0) => i as u32,
+                _ => {
+                    let same = matches(typed[j - 1], expected[i - 1]);
+                    let mut best = (cost[i - 1][j - 1] + !same as u32)
+                        .min(cost[i - 1][j] + 1)
+                        .min(cost[i][j - 1] + 1);
+                    if swapped(expected, typed, i, j, &matches) {
+                        best = best.min(cost[i - 2][j - 2] + 1);
+                    }
+                    best
+                }
+            };
+        }
+    }
+
+    let mut kinds = Vec::new();
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        if i > 0
+            && j > 0
+            && matches(typed[j - 1], expected[i - 1])
+            && cost[i][j] == cost[i - 1][j - 1]
+        {
+            (i, j) = (i - 1, j - 1);
+        } else if swapped(expected, typed, i, j, &matches) && cost[i][j] == cost[i - 2][j - 2] + 1 {
+            kinds.push(Kind::Transposition);
+            (i, j) = (i - 2, j - 2);
+        } else if i > 0 && j > 0 && cost[i][j] == cost[i - 1][j - 1] + 1 {
+            let adjacent = match (single(expected[i - 1]), single(typed[j - 1])) {
+                (Some(expected), Some(typed)) => layout.adjacent(expected, typed),
+                _ => false,
+            };
+            kinds.push(if adjacent {
+                Kind::Adjacent
+            } else {
+                Kind::Other
+            });
+            (i, j) = (i - 1, j - 1);
+        } else if i > 0 && cost[i][j] == cost[i - 1][j] + 1 {
+            kinds.push(Kind::Omission);
+            i -= 1;
+        } else {
+            kinds.push(Kind::Insertion);
+            j -= 1;
+        }
+    }
+    kinds.reverse();
+    kinds
+}
+
+/// Whether the last two typed graphemes are the last two expected ones, the other way around.
+fn swapped(
+    expected: &[&str],
+    typed: &[&str],
+    i: usize,
+    j: usize,
+    matches: impl Fn(&str, &str) -> bool,
+) -> bool {
+    i > 1
+        && j > 1
+        && matches(typed[j - 1], expected[i - 2])
+        && matches(typed[j - 2], expected[i - 1])
+        && !matches(typed[j - 1], expected[i - 1])
+}
+
+fn single(grapheme: &str) -> Option<char> {
+    let mut chars = grapheme.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// How typographic punctuation, which most keyboards can't easily type, is handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Punctuation {
+    /// Type it exactly as written in the quote
+    #[default]
+    Exact,
+    /// Replace it with its plain equivalent in the quote
+    Normalize,
+    /// Keep showing it, but also accept its plain equivalent
+    Accept,
+}
+
+/// Plain keyboard equivalent of a typographic character.
+fn plain(c: char) -> Option<&'static str> {
+    match c {
+        '‘' | '’' | '‚' | '‛' | '′' => Some("'"),
+        '“' | '”' | '„' | '‟' | '″' => Some("\""),
+        '‒' | '–' | '—' | '―' | '−' => Some("-"),
+        '…' => Some("..."),
+        _ => None,
+    }
+}
+
+/// Replaces every typographic character of `text` with its plain equivalent.
+pub fn normalize(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len());
+    for c in text.chars() {
+        match plain(c) {
+            Some(replacement) => normalized.push_str(replacement),
+            None => normalized.push(c),
+        }
+    }
+
+    normalized
+}
+
+/// Whether `typed` is the plain equivalent of the typographic grapheme `expected`.
+pub fn is_plain_equivalent(typed: &str, expected: &str) -> bool {
+    let mut chars = expected.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => plain(c) == Some(typed),
+        _ => false,
+    }
+}
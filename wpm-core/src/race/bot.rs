@@ -0,0 +1,144 @@
+use rand::{Rng, rng};
+use std::time::Duration;
+
+use super::Player;
+use crate::{
+    replay::{Key, Replay},
+    sim::Simulation,
+    stats,
+};
+
+// How much the speed can drift from the target each second, and at most
+const DRIFT: f32 = 0.04;
+const MAX_DRIFT: f32 = 0.12;
+// Chance to stumble on a second, typing it at a fraction of the speed
+const STUMBLE_CHANCE: f64 = 0.08;
+const STUMBLE_SPEED: f32 = 0.3;
+
+/// An opponent typing on its own at about the given speed, to race offline. Its pace wanders
+/// a little around the target, with a stumble now and then.
+#[derive(Debug, Clone)]
+pub struct Bot {
+    pub name: String,
+    wpm: f32,
+    chars: usize,
+    // characters typed by the end of each second
+    typed: Vec<f32>,
+    // share of the text typed by the end of each second, for a ghost following a replay
+    recorded: Option<Vec<f32>>,
+}
+
+impl Bot {
+    pub fn new(name: String, wpm: f32) -> Self {
+        Bot {
+            name,
+            wpm: wpm.max(1.),
+            chars: 0,
+            typed: vec![0.],
+            recorded: None,
+        }
+    }
+
+    /// A ghost typing exactly like the replay did, at its speed and with its mistakes. `None`
+    /// when the replay doesn't finish its text.
+    pub fn from_replay(name: String, replay: &Replay) -> Option<Self> {
+        let mut sim = Simulation::with_lines(replay.lines.clone(), replay.rules);
+        let mut progress = vec![0.];
+        let mut last = 0;
+        for &Key(ms, key) in &replay.keys {
+            // Seconds over before this key, typed as far as the one before
+            while (progress.len() as u32) * 1000 < ms {
+                progress.push(sim.test().progress());
+            }
+            sim.press(key, u64::from(ms.saturating_sub(last)));
+            last = ms;
+            if sim.test().is_done() {
+                break;
+            }
+        }
+        if !sim.test().is_done() {
+            return None;
+        }
+
+        // Reaches the end of the text right when the replay did, within its last second
+        let from = progress[progress.len() - 1];
+        let fraction = last as f32 / 1000. - (progress.len() - 1) as f32;
+        progress.push(from + (1. - from) / fraction.max(f32::EPSILON));
+
+        let mut bot = Self::new(name, sim.test().speed(sim.test().elapsed()));
+        bot.recorded = Some(progress);
+        Some(bot)
+    }
+
+    pub fn is_ghost(&self) -> bool {
+        self.recorded.is_some()
+    }
+
+    /// Lines up for a text of `chars` characters, with a pace of its own or the one of its replay.
+    pub fn start(&mut self, chars: usize) {
+        self.chars = chars;
+        if let Some(recorded) = &self.recorded {
+            self.typed = recorded.iter().map(|share| share * chars as f32).collect();
+            return;
+        }
+
+        let mut rng = rng();
+        let mut drift: f32 = 0.;
+        let mut typed = vec![0.];
+        let mut last = 0.;
+
+        while last < chars as f32 {
+            drift = (drift + rng.random_range(-DRIFT..DRIFT)).clamp(-MAX_DRIFT, MAX_DRIFT);
+            let mut per_second = self.wpm * stats::CHARS_PER_WORD / 60. * (1. + drift);
+            if rng.random_bool(STUMBLE_CHANCE) {
+                per_second *= STUMBLE_SPEED;
+            }
+            last += per_second;
+            typed.push(last);
+        }
+
+        self.typed = typed;
+    }
+
+    /// Characters typed `elapsed` after the start.
+    fn typed_at(&self, elapsed: Duration) -> f32 {
+        let seconds = elapsed.as_secs_f32();
+        let second = seconds as usize;
+        let (Some(from), Some(to)) = (self.typed.get(second), self.typed.get(second + 1)) else {
+            return self.chars as f32;
+        };
+
+        (from + (to - from) * seconds.fract()).min(self.chars as f32)
+    }
+
+    /// When the bot types its last character.
+    pub fn finish_time(&self) -> Duration {
+        let second = self
+            .typed
+            .iter()
+            .position(|&typed| typed >= self.chars as f32)
+            .unwrap_or(0);
+        let Some(&from) = second.checked_sub(1).and_then(|i| self.typed.get(i)) else {
+            return Duration::ZERO;
+        };
+
+        let fraction = (self.chars as f32 - from) / (self.typed[second] - from);
+        Duration::from_secs_f32((second - 1) as f32 + fraction)
+    }
+
+    /// Where the bot is `elapsed` after the start, without a place.
+    pub fn player(&self, elapsed: Duration) -> Player {
+        let typed = self.typed_at(elapsed);
+        Player {
+            name: self.name.clone(),
+            progress: if self.chars == 0 {
+                0.
+            } else {
+                typed / self.chars as f32
+            },
+            wpm: stats::wpm(typed as u32, elapsed.min(self.finish_time())),
+            place: None,
+            wins: 0,
+        }
+    }
+}
@@ -0,0 +1,400 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, Sender},
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::{engine::Rules, worker::lock};
+
+pub use bot::Bot;
+pub use server::serve;
+
+mod bot;
+mod server;
+
+pub const DEFAULT_PORT: u16 = 4000;
+// A player this slow to take a message is let go, rather than its messages piling up
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A player of the race, as the host last heard of it.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Player {
+    pub name: String,
+    /// Share of the text typed, from 0 to 1
+    pub progress: f32,
+    pub wpm: f32,
+    /// Place at the finish, from 1, once done
+    pub place: Option<usize>,
+    /// Races won in the room so far, only counted by a race server
+    #[serde(default)]
+    pub wins: u32,
+}
+
+impl Player {
+    fn new(name: String) -> Self {
+        Player {
+            name,
+            progress: 0.,
+            wpm: 0.,
+            place: None,
+            wins: 0,
+        }
+    }
+
+    /// Back at the start line for the next race.
+    fn reset(&mut self) {
+        self.progress = 0.;
+        self.wpm = 0.;
+        self.place = None;
+    }
+}
+
+/// A text everyone types at once, exactly as the host split it into lines.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Start {
+    pub quote_id: u32,
+    pub source: String,
+    pub lines: Vec<String>,
+    pub rules: Rules,
+}
+
+// Sent as a line of JSON each
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Message {
+    /// Sent first by a player joining, a race server puts it in the given room or a new one
+    Join {
+        name: String,
+        #[serde(default)]
+        room: Option<String>,
+    },
+    /// Room a race server put the player in
+    Joined {
+        room: String,
+    },
+    /// A player asks a race server for the next race
+    Ready,
+    /// Seconds left before a race server starts the race
+    Countdown {
+        seconds: u32,
+    },
+    Start(Start),
+    Progress {
+        progress: f32,
+        wpm: f32,
+        done: bool,
+    },
+    /// Everyone's progress, sent by the host whenever it changes
+    Standings {
+        players: Vec<Player>,
+    },
+}
+
+impl Message {
+    fn standings(players: &[Player]) -> Self {
+        Message::Standings {
+            players: players.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    players: Vec<Player>,
+    // race started by the host, kept there for players joining late, and waiting for the
+    // game to pick it up on guests
+    start: Option<Start>,
+    room: Option<String>,
+    countdown: Option<u32>,
+    // standings changed since they were last read
+    changed: bool,
+}
+
+/// The writing end of a connection to another player, on a thread of its own: sending only
+/// queues the message, so a player that stops reading never holds up the game or the others.
+#[derive(Debug, Clone)]
+struct Peer(Sender<String>);
+
+impl Peer {
+    fn spawn(mut stream: TcpStream) -> Self {
+        let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+        let (lines, queue) = mpsc::channel::<String>();
+        thread::spawn(move || {
+            for line in queue {
+                // Gone for good after a failed write, sends fail from then on
+                if writeln!(stream, "{line}").is_err() {
+                    return;
+                }
+            }
+        });
+        Peer(lines)
+    }
+
+    /// Queues a message, false once the player is gone.
+    fn send(&self, message: &Message) -> bool {
+        serde_json::to_string(message).is_ok_and(|line| self.0.send(line).is_ok())
+    }
+}
+
+#[derive(Debug)]
+enum Role {
+    // port players join on, and connections to every guest
+    Host(u16, Arc<Mutex<Vec<Peer>>>),
+    Guest(Peer),
+}
+
+/// This player's end of a race over the local network, hosting it or joined to someone else's
+/// over TCP. The host picks the text, and places the players as they finish.
+#[derive(Debug)]
+pub struct Race {
+    name: String,
+    state: Arc<Mutex<State>>,
+    role: Role,
+}
+
+impl Race {
+    /// Waits for players on the given port in the background, any free one for 0.
+    pub fn host(name: String, port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        let port = listener.local_addr()?.port();
+        let state = Arc::new(Mutex::new(State {
+            players: vec![Player::new(name.clone())],
+            ..State::default()
+        }));
+        let guests = Arc::new(Mutex::new(Vec::new()));
+
+        let (accept_state, accept_guests) = (state.clone(), guests.clone());
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let (state, guests) = (accept_state.clone(), accept_guests.clone());
+                thread::spawn(move || follow_guest(stream, &state, &guests));
+            }
+        });
+
+        Ok(Race {
+            name,
+            state,
+            role: Role::Host(port, guests),
+        })
+    }
+
+    /// Joins the race hosted at `addr`, like `192.168.1.20:4000`. On a race server, `room` is
+    /// the code of the room to join, a new one is opened without it.
+    pub fn join(name: String, addr: impl ToSocketAddrs, room: Option<String>) -> io::Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        let host = Peer::spawn(stream.try_clone()?);
+        host.send(&Message::Join {
+            name: name.clone(),
+            room,
+        });
+
+        let state = Arc::new(Mutex::new(State::default()));
+        let reader = BufReader::new(stream);
+        let listen_state = state.clone();
+        thread::spawn(move || {
+            for message in reader.lines().map_while(Result::ok) {
+                let mut state = lock(&listen_state);
+                match serde_json::from_str(&message) {
+                    Ok(Message::Joined { room }) => state.room = Some(room),
+                    Ok(Message::Countdown { seconds }) => state.countdown = Some(seconds),
+                    Ok(Message::Start(start)) => {
+                        state.countdown = None;
+                        state.start = Some(start);
+                    }
+                    Ok(Message::Standings { players }) => state.players = players,
+                    _ => continue,
+                }
+                state.changed = true;
+            }
+        });
+
+        Ok(Race {
+            name,
+            state,
+            role: Role::Guest(host),
+        })
+    }
+
+    pub fn is_host(&self) -> bool {
+        matches!(self.role, Role::Host(..))
+    }
+
+    /// Port the race is hosted on, to tell the other players.
+    pub fn port(&self) -> Option<u16> {
+        match self.role {
+            Role::Host(port, _) => Some(port),
+            Role::Guest(_) => None,
+        }
+    }
+
+    /// Code of the room joined on a race server.
+    pub fn room(&self) -> Option<String> {
+        lock(&self.state).room.clone()
+    }
+
+    /// Seconds left before the next race starts, while a race server counts down.
+    pub fn countdown(&self) -> Option<u32> {
+        lock(&self.state).countdown
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Starts a race on `start` for everyone connected. Only the host can.
+    pub fn start(&mut self, start: Start) {
+        let Role::Host(_, guests) = &self.role else {
+            return;
+        };
+
+        let standings = {
+            let mut state = lock(&self.state);
+            state.players.iter_mut().for_each(Player::reset);
+            state.changed = true;
+            state.start = Some(start.clone());
+            Message::standings(&state.players)
+        };
+        broadcast(guests, &Message::Start(start));
+        broadcast(guests, &standings);
+    }
+
+    /// Asks a race server for the next race. Hosts pick it themselves.
+    pub fn request_start(&mut self) {
+        if let Role::Guest(host) = &self.role {
+            host.send(&Message::Ready);
+        }
+    }
+
+    /// A race the host started since the last call, to type right away.
+    pub fn take_start(&self) -> Option<Start> {
+        match self.role {
+            Role::Host(..) => None,
+            Role::Guest(_) => lock(&self.state).start.take(),
+        }
+    }
+
+    /// Tells everyone how far this player got.
+    pub fn report(&mut self, progress: f32, wpm: f32, done: bool) {
+        match &self.role {
+            Role::Host(_, guests) => {
+                let standings = {
+                    let mut state = lock(&self.state);
+                    place(&mut state.players, &self.name, progress, wpm, done);
+                    state.changed = true;
+                    Message::standings(&state.players)
+                };
+                broadcast(guests, &standings);
+            }
+            Role::Guest(host) => {
+                host.send(&Message::Progress {
+                    progress,
+                    wpm,
+                    done,
+                });
+            }
+        }
+    }
+
+    /// Players by place, then by how far they got.
+    pub fn standings(&self) -> Vec<Player> {
+        let mut players = lock(&self.state).players.clone();
+        players.sort_by(|a, b| {
+            let place = |p: &Player| p.place.unwrap_or(usize::MAX);
+            place(a)
+                .cmp(&place(b))
+                .then(b.progress.total_cmp(&a.progress))
+        });
+        players
+    }
+
+    /// Whether anyone's progress changed since the last call.
+    pub fn changed(&self) -> bool {
+        std::mem::take(&mut lock(&self.state).changed)
+    }
+}
+
+/// Follows a guest of the host until it leaves.
+fn follow_guest(stream: TcpStream, state: &Mutex<State>, guests: &Mutex<Vec<Peer>>) {
+    let Ok(writer) = stream.try_clone() else {
+        return;
+    };
+    let mut lines = BufReader::new(stream).lines().map_while(Result::ok);
+    let Some(Ok(Message::Join { name, .. })) = lines.next().map(|line| serde_json::from_str(&line))
+    else {
+        return;
+    };
+
+    let guest = Peer::spawn(writer);
+    let (name, standings) = {
+        let mut state = lock(state);
+        let name = unique_name(&state.players, &name);
+        state.players.push(Player::new(name.clone()));
+        state.changed = true;
+        if let Some(start) = &state.start {
+            guest.send(&Message::Start(start.clone()));
+        }
+        (name, Message::standings(&state.players))
+    };
+    lock(guests).push(guest);
+    broadcast(guests, &standings);
+
+    for line in lines {
+        if let Ok(Message::Progress {
+            progress,
+            wpm,
+            done,
+        }) = serde_json::from_str(&line)
+        {
+            let standings = {
+                let mut state = lock(state);
+                place(&mut state.players, &name, progress, wpm, done);
+                state.changed = true;
+                Message::standings(&state.players)
+            };
+            broadcast(guests, &standings);
+        }
+    }
+
+    let standings = {
+        let mut state = lock(state);
+        state.players.retain(|p| p.name != name);
+        state.changed = true;
+        Message::standings(&state.players)
+    };
+    broadcast(guests, &standings);
+}
+
+/// `name`, numbered when a player already goes by it, since players are told apart by name.
+fn unique_name(players: &[Player], name: &str) -> String {
+    let mut unique = name.to_string();
+    for n in 2.. {
+        if !players.iter().any(|p| p.name == unique) {
+            break;
+        }
+        unique = format!("{name} {n}");
+    }
+    unique
+}
+
+/// Moves a player along, and gives it the next place once done.
+fn place(players: &mut [Player], name: &str, progress: f32, wpm: f32, done: bool) {
+    let finished = players.iter().filter(|p| p.place.is_some()).count();
+    if let Some(player) = players.iter_mut().find(|p| p.name == name) {
+        player.progress = progress;
+        player.wpm = wpm;
+        if done && player.place.is_none() {
+            player.place = Some(finished + 1);
+        }
+    }
+}
+
+/// Sends to every guest, forgetting the ones that left.
+fn broadcast(guests: &Mutex<Vec<Peer>>, message: &Message) {
+    lock(guests).retain(|guest| guest.send(message));
+}
@@ -0,0 +1,191 @@
+use rand::{rng, seq::IndexedRandom};
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use super::{Message, Peer, Player, Start, place, unique_name};
+use crate::worker::lock;
+
+// Seconds counted down before each race
+const COUNTDOWN: u32 = 3;
+const CODE_LENGTH: usize = 4;
+// Letters room codes are made of, without the ones mistaken for digits
+const CODE_LETTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+
+type Rooms = Arc<Mutex<HashMap<String, Room>>>;
+type NextText = Arc<dyn Fn() -> Option<Start> + Send + Sync>;
+
+/// Players racing together on a server, until the last one leaves.
+#[derive(Debug, Default)]
+struct Room {
+    players: Vec<Player>,
+    guests: Vec<(String, Peer)>,
+    // text of the last race, sent to players joining while it runs
+    start: Option<Start>,
+    // counting down or racing
+    running: bool,
+}
+
+impl Room {
+    /// Queues a message for every player, the sockets are written to by their own threads
+    /// once the rooms are unlocked.
+    fn broadcast(&mut self, message: &Message) {
+        self.guests.retain(|(_, guest)| guest.send(message));
+    }
+
+    fn broadcast_standings(&mut self) {
+        self.broadcast(&Message::standings(&self.players));
+    }
+
+    /// Ends the race once everyone still in the room finished, and counts the win.
+    fn check_finished(&mut self) {
+        if !self.running || self.start.is_none() || self.players.iter().any(|p| p.place.is_none()) {
+            return;
+        }
+
+        self.running = false;
+        if let Some(winner) = self.players.iter_mut().find(|p| p.place == Some(1)) {
+            winner.wins += 1;
+        }
+    }
+}
+
+/// Runs a race server for players connecting to `listener`, without playing, until the process
+/// is stopped. Players meet in rooms by code, and any of them can ask for the next race once the
+/// last one is over. Each race is on a text from `next_text`.
+pub fn serve(
+    listener: TcpListener,
+    next_text: impl Fn() -> Option<Start> + Send + Sync + 'static,
+) -> io::Result<()> {
+    let rooms = Rooms::default();
+    let next_text: NextText = Arc::new(next_text);
+
+    for stream in listener.incoming().flatten() {
+        let (rooms, next_text) = (rooms.clone(), next_text.clone());
+        thread::spawn(move || follow_player(stream, &rooms, &next_text));
+    }
+    Ok(())
+}
+
+/// Follows a player of the server until it leaves.
+fn follow_player(stream: TcpStream, rooms: &Rooms, next_text: &NextText) {
+    let Ok(writer) = stream.try_clone() else {
+        return;
+    };
+    let mut lines = BufReader::new(stream).lines().map_while(Result::ok);
+    let Some(Ok(Message::Join { name, room })) =
+        lines.next().map(|line| serde_json::from_str(&line))
+    else {
+        return;
+    };
+
+    let player = Peer::spawn(writer);
+    let (code, name) = {
+        let mut rooms = lock(rooms);
+        let code = match room {
+            Some(code) => code.to_uppercase(),
+            None => new_code(&rooms),
+        };
+        let room = rooms.entry(code.clone()).or_default();
+        let name = unique_name(&room.players, &name);
+        room.players.push(Player::new(name.clone()));
+
+        player.send(&Message::Joined { room: code.clone() });
+        if room.running
+            && let Some(start) = &room.start
+        {
+            player.send(&Message::Start(start.clone()));
+        }
+        room.guests.push((name.clone(), player));
+        room.broadcast_standings();
+        (code, name)
+    };
+
+    for line in lines {
+        match serde_json::from_str(&line) {
+            Ok(Message::Ready) => countdown(rooms, &code, next_text),
+            Ok(Message::Progress {
+                progress,
+                wpm,
+                done,
+            }) => {
+                let mut rooms = lock(rooms);
+                let Some(room) = rooms.get_mut(&code) else {
+                    break;
+                };
+                place(&mut room.players, &name, progress, wpm, done);
+                room.check_finished();
+                room.broadcast_standings();
+            }
+            _ => {}
+        }
+    }
+
+    let mut rooms = lock(rooms);
+    let Some(room) = rooms.get_mut(&code) else {
+        return;
+    };
+    room.players.retain(|p| p.name != name);
+    room.guests.retain(|(guest, _)| *guest != name);
+    if room.players.is_empty() {
+        rooms.remove(&code);
+    } else {
+        room.check_finished();
+        room.broadcast_standings();
+    }
+}
+
+/// Counts down to the next race in the background, unless one is already on.
+fn countdown(rooms: &Rooms, code: &str, next_text: &NextText) {
+    match lock(rooms).get_mut(code) {
+        Some(room) if !room.running => {
+            room.running = true;
+            room.start = None;
+        }
+        _ => return,
+    }
+
+    let (rooms, code, next_text) = (rooms.clone(), code.to_string(), next_text.clone());
+    thread::spawn(move || {
+        for seconds in (1..=COUNTDOWN).rev() {
+            match lock(&rooms).get_mut(&code) {
+                Some(room) => room.broadcast(&Message::Countdown { seconds }),
+                None => return,
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+
+        let start = next_text();
+        let mut rooms = lock(&rooms);
+        let Some(room) = rooms.get_mut(&code) else {
+            return;
+        };
+        let Some(start) = start else {
+            room.running = false;
+            return;
+        };
+
+        room.players.iter_mut().for_each(Player::reset);
+        room.broadcast(&Message::Start(start.clone()));
+        room.start = Some(start);
+        room.broadcast_standings();
+    });
+}
+
+/// A code no open room has.
+fn new_code(rooms: &HashMap<String, Room>) -> String {
+    loop {
+        let code: String = (0..CODE_LENGTH)
+            .filter_map(|_| CODE_LETTERS.choose(&mut rng()))
+            .map(|&letter| letter as char)
+            .collect();
+        if !rooms.contains_key(&code) {
+            return code;
+        }
+    }
+}
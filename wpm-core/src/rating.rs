@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, fs, io,
+    path::PathBuf,
+};
+
+use crate::history;
+
+const RATINGS_FILE: &str = "ratings.json";
+
+/// How a quote felt to type, rated after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tag {
+    Fun,
+    Boring,
+    Easy,
+    Hard,
+}
+
+impl Tag {
+    pub const ALL: [Tag; 4] = [Tag::Fun, Tag::Boring, Tag::Easy, Tag::Hard];
+
+    /// The tag a quote can't have along with this one.
+    pub fn opposite(self) -> Tag {
+        match self {
+            Tag::Fun => Tag::Boring,
+            Tag::Boring => Tag::Fun,
+            Tag::Easy => Tag::Hard,
+            Tag::Hard => Tag::Easy,
+        }
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", format!("{self:?}").to_lowercase())
+    }
+}
+
+/// Tags given to quotes, by the name of their source and their id in it, kept between runs.
+/// Packs number their quotes on their own, so ids alone would mix them up.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Ratings {
+    sources: BTreeMap<String, BTreeMap<u32, BTreeSet<Tag>>>,
+}
+
+impl Ratings {
+    fn path() -> Option<PathBuf> {
+        history::data_dir().map(|dir| dir.join(RATINGS_FILE))
+    }
+
+    /// The ratings stored so far, none before the first one.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or(io::ErrorKind::NotFound)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    /// Tags of a quote, in the order of `Tag::ALL`, none when it was never rated.
+    pub fn tags(&self, source: &str, id: u32) -> Vec<Tag> {
+        match self.sources.get(source).and_then(|quotes| quotes.get(&id)) {
+            Some(tags) => tags.iter().copied().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn has(&self, source: &str, id: u32, tag: Tag) -> bool {
+        let tags = self.sources.get(source).and_then(|quotes| quotes.get(&id));
+        tags.is_some_and(|tags| tags.contains(&tag))
+    }
+
+    /// Gives a quote a tag in place of its opposite, or takes it away when it had it already.
+    pub fn toggle(&mut self, source: &str, id: u32, tag: Tag) {
+        let quotes = self.sources.entry(source.to_string()).or_default();
+        let tags = quotes.entry(id).or_default();
+        if !tags.remove(&tag) {
+            tags.remove(&tag.opposite());
+            tags.insert(tag);
+        }
+        if tags.is_empty() {
+            quotes.remove(&id);
+        }
+        if quotes.is_empty() {
+            self.sources.remove(source);
+        }
+    }
+
+    /// Quotes rated so far, from every source.
+    pub fn len(&self) -> usize {
+        self.sources.values().map(BTreeMap::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+}
@@ -0,0 +1,81 @@
+use jiff::{ToSpan, Zoned, civil::Time};
+use std::{path::Path, time::Duration};
+
+use crate::history::{self, Record};
+
+/// Minutes spent typing from `since` on, a timestamp, like the start of today.
+pub fn minutes_since(records: &[Record], since: u64) -> f32 {
+    history::since(records, since)
+        .iter()
+        .map(|record| record.seconds)
+        .sum::<f32>()
+        / 60.
+}
+
+/// Times of the day to be reminded at, in the local time zone.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Schedule {
+    times: Vec<Time>,
+}
+
+impl Schedule {
+    /// Reads times like `18:00`, failing on the first one that isn't one.
+    pub fn parse(times: &[String]) -> Result<Self, String> {
+        let times = times
+            .iter()
+            .map(|time| {
+                time.parse()
+                    .map_err(|_| format!("invalid reminder time: {time}"))
+            })
+            .collect::<Result<Vec<Time>, _>>()?;
+        Ok(Schedule { times })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+
+    /// The first reminder strictly after `now`, tomorrow for those already past today.
+    pub fn next(&self, now: &Zoned) -> Option<Zoned> {
+        self.times
+            .iter()
+            .filter_map(|time| {
+                let today = now
+                    .date()
+                    .to_datetime(*time)
+                    .to_zoned(now.time_zone().clone());
+                let at = today.ok()?;
+                if at > *now {
+                    Some(at)
+                } else {
+                    at.checked_add(1.day()).ok()
+                }
+            })
+            .min()
+    }
+
+    /// How long until the next reminder from now, none without any.
+    pub fn until_next(&self) -> Option<Duration> {
+        let now = Zoned::now();
+        let next = self.next(&now)?;
+        Duration::try_from(next.duration_since(&now)).ok()
+    }
+
+    /// A systemd timer going off at each reminder time, every day.
+    pub fn systemd_timer(&self) -> String {
+        let mut timer =
+            String::from("[Unit]\nDescription=Reminders to practice typing\n\n[Timer]\n");
+        for time in &self.times {
+            timer += &format!("OnCalendar=*-*-* {}\n", time.strftime("%H:%M:%S"));
+        }
+        timer + "\n[Install]\nWantedBy=timers.target\n"
+    }
+}
+
+/// A systemd service checking the daily goal once, started by the timer.
+pub fn systemd_service(exe: &Path) -> String {
+    format!(
+        "[Unit]\nDescription=Reminder to practice typing\n\n[Service]\nType=oneshot\nExecStart={} daemon --once\n",
+        exe.display()
+    )
+}
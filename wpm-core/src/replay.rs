@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{clock::ManualClock, engine::Rules, history};
+
+/// Bumped whenever the format changes in a way older readers can't handle.
+pub const VERSION: u32 = 1;
+const REPLAYS_DIR: &str = "replays";
+const SPEEDS: [f32; 4] = [0.5, 1., 2., 4.];
+
+/// Input recorded in a replay, stored as a single character to keep files small.
+pub const BACKSPACE: char = '\u{8}';
+pub const DELETE_WORD: char = '\u{17}';
+
+/// Milliseconds since the first keystroke, and the key. Serialized as `[ms, "key"]`.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct Key(pub u32, pub char);
+
+/// A test keystroke by keystroke, also the file to send someone to race against, since it
+/// holds the text it was typed on.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Replay {
+    pub version: u32,
+    pub timestamp: u64,
+    /// Who typed it, empty when not known
+    #[serde(default)]
+    pub player: String,
+    pub quote_id: u32,
+    /// The quote exactly as it was split into lines on screen
+    pub lines: Vec<String>,
+    /// Needed to play the keys back the same way
+    pub rules: Rules,
+    pub keys: Vec<Key>,
+}
+
+impl Replay {
+    pub fn now(quote_id: u32, lines: Vec<String>, rules: Rules, keys: Vec<Key>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        Replay {
+            version: VERSION,
+            timestamp,
+            player: String::new(),
+            quote_id,
+            lines,
+            rules,
+            keys,
+        }
+    }
+
+    fn file_name(&self) -> String {
+        format!("{}-{}.json", self.timestamp, self.quote_id)
+    }
+}
+
+pub fn dir() -> Option<PathBuf> {
+    history::data_dir().map(|dir| dir.join(REPLAYS_DIR))
+}
+
+/// Writes the replay to its own file in the replays directory.
+pub fn save(replay: &Replay) -> io::Result<PathBuf> {
+    let dir = dir().ok_or(io::ErrorKind::NotFound)?;
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(replay.file_name());
+    let file = fs::File::create(&path)?;
+    serde_json::to_writer(BufWriter::new(file), replay)?;
+    Ok(path)
+}
+
+/// The most recently saved replay.
+pub fn latest() -> Option<PathBuf> {
+    fs::read_dir(dir()?)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .max_by_key(|path| {
+            // named after their timestamp, which sorts numerically but not alphabetically
+            path.file_stem()
+                .and_then(|stem| stem.to_str()?.split('-').next()?.parse::<u64>().ok())
+                .unwrap_or(0)
+        })
+}
+
+/// The replay saved along with a history record, if replays were recorded then.
+pub fn find(record: &history::Record) -> Option<Replay> {
+    let path = dir()?.join(format!("{}-{}.json", record.timestamp, record.quote_id));
+    load(&path).ok()
+}
+
+/// Reads a replay, refusing versions newer than this build understands.
+pub fn load(path: &Path) -> io::Result<Replay> {
+    let replay: Replay = serde_json::from_str(&fs::read_to_string(path)?)?;
+    if replay.version > VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("replay version {} is not supported", replay.version),
+        ));
+    }
+
+    Ok(replay)
+}
+
+/// Hands out the keys of a replay as their time comes, at an adjustable speed. The test they are
+/// played on takes its time from [`Playback::clock`], so its speeds come out as they were typed
+/// whatever the speed it is watched at.
+#[derive(Debug)]
+pub struct Playback {
+    keys: Vec<Key>,
+    next: usize,
+    // how far into the replay the playback is
+    position: Duration,
+    // the time of the replay, moved key by key up to `position`
+    clock: ManualClock,
+    at: Duration,
+    last_tick: Instant,
+    speed: usize,
+    paused: bool,
+}
+
+impl Playback {
+    pub fn new(keys: Vec<Key>) -> Self {
+        Playback {
+            keys,
+            next: 0,
+            position: Duration::from_secs(0),
+            clock: ManualClock::new(),
+            at: Duration::from_secs(0),
+            last_tick: Instant::now(),
+            speed: 1,
+            paused: false,
+        }
+    }
+
+    /// Moves the playback forward by the time since the last call, at its speed.
+    pub fn advance(&mut self) {
+        let now = Instant::now();
+        if !self.paused {
+            self.position += now.duration_since(self.last_tick).mul_f32(self.speed());
+        }
+        self.last_tick = now;
+    }
+
+    /// The next key pressed before where the playback is, with the clock moved to when it was
+    /// pressed. Once there are none left, the clock catches up with the playback.
+    pub fn next_key(&mut self) -> Option<char> {
+        let due = self
+            .keys
+            .get(self.next)
+            .map(|&Key(ms, key)| (Duration::from_millis(ms as u64), key))
+            .filter(|(at, _)| *at <= self.position);
+        let (at, key) = match due {
+            Some((at, key)) => (at, Some(key)),
+            None => (self.position, None),
+        };
+        self.clock.advance(at.saturating_sub(self.at));
+        self.at = self.at.max(at);
+        self.next += key.is_some() as usize;
+        key
+    }
+
+    /// The time of the replay, for the test it is played on.
+    pub fn clock(&self) -> ManualClock {
+        self.clock.clone()
+    }
+
+    pub fn speed(&self) -> f32 {
+        SPEEDS[self.speed]
+    }
+
+    pub fn faster(&mut self) {
+        self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed = self.speed.saturating_sub(1);
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{engine::TypingTest, history};
+
+const REVIEW_FILE: &str = "review.json";
+const DAY: u64 = 24 * 60 * 60;
+// Clean sessions in a row after which a word leaves the queue
+pub const CLEAN_SESSIONS: u32 = 3;
+
+/// A word to review, and when.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Card {
+    /// Sessions in a row it was typed cleanly, since it was last missed
+    pub streak: u32,
+    pub misses: u32,
+    /// When it is up for review again, in seconds since the Unix epoch
+    pub due: u64,
+}
+
+impl Card {
+    /// Days until the next review after `streak` clean sessions, three times longer each time.
+    fn interval(streak: u32) -> u64 {
+        DAY * 3u64.pow(streak.saturating_sub(1))
+    }
+}
+
+/// The words of a finished test, those with mistakes left in them and the others.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Session {
+    pub missed: BTreeSet<String>,
+    pub clean: BTreeSet<String>,
+    pub timestamp: u64,
+}
+
+impl Session {
+    /// Sorts the words of a test by whether they kept mistakes, now.
+    pub fn of(test: &TypingTest) -> Self {
+        let timestamp = now();
+        let missed: BTreeSet<String> = test
+            .word_errors()
+            .keys()
+            .map(|word| key(word))
+            .filter(|word| !word.is_empty())
+            .collect();
+        let text: String = test.lines().iter().flatten().map(String::as_str).collect();
+        let clean = text
+            .split_whitespace()
+            .map(key)
+            .filter(|word| !word.is_empty() && !missed.contains(word))
+            .collect();
+        Session {
+            missed,
+            clean,
+            timestamp,
+        }
+    }
+}
+
+/// Seconds since the Unix epoch, what the schedule counts in.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// A word without the punctuation around it, the way it is reviewed.
+pub fn key(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_string()
+}
+
+/// Words missed in tests, brought back on a spaced repetition schedule: right away after a
+/// miss, then one and three days after each clean session, until typed cleanly
+/// `CLEAN_SESSIONS` times in a row.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Queue {
+    words: BTreeMap<String, Card>,
+}
+
+impl Queue {
+    fn path() -> Option<PathBuf> {
+        history::data_dir().map(|dir| dir.join(REVIEW_FILE))
+    }
+
+    /// The words stored so far, none before the first miss.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or(io::ErrorKind::NotFound)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    pub fn get(&self, word: &str) -> Option<&Card> {
+        self.words.get(word)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Queues the words missed in a session, and moves those due and typed cleanly along.
+    /// Clean words that are not due yet don't count, so a session only counts once.
+    pub fn add(&mut self, session: &Session) {
+        let now = session.timestamp;
+        for word in &session.missed {
+            let card = self.words.entry(word.clone()).or_default();
+            card.streak = 0;
+            card.misses += 1;
+            card.due = now;
+        }
+        for word in &session.clean {
+            let Some(card) = self.words.get_mut(word).filter(|card| card.due <= now) else {
+                continue;
+            };
+            card.streak += 1;
+            card.due = now + Card::interval(card.streak);
+            if card.streak >= CLEAN_SESSIONS {
+                self.words.remove(word);
+            }
+        }
+    }
+
+    /// Every word of the queue, the most missed first, then those typed cleanly the least.
+    pub fn worst(&self) -> Vec<&str> {
+        let mut words: Vec<(&str, &Card)> = self
+            .words
+            .iter()
+            .map(|(word, card)| (word.as_str(), card))
+            .collect();
+        words.sort_by_key(|(_, card)| (std::cmp::Reverse(card.misses), card.streak));
+        words.into_iter().map(|(word, _)| word).collect()
+    }
+
+    /// Words up for review at `now`, those waiting the longest first.
+    pub fn due(&self, now: u64) -> Vec<&str> {
+        let mut due: Vec<(&str, &Card)> = self
+            .words
+            .iter()
+            .filter(|(_, card)| card.due <= now)
+            .map(|(word, card)| (word.as_str(), card))
+            .collect();
+        due.sort_by_key(|(_, card)| card.due);
+        due.into_iter().map(|(word, _)| word).collect()
+    }
+
+    /// When the next word is up for review, none when the queue is empty.
+    pub fn next_due(&self) -> Option<u64> {
+        self.words.values().map(|card| card.due).min()
+    }
+}
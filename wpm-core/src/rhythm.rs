@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use crate::{
+    latency::HESITATION_MS,
+    replay::{BACKSPACE, DELETE_WORD, Key},
+};
+
+/// How far an interval can be from the beat and still be on it, as a share of the beat.
+pub const TOLERANCE: f32 = 0.25;
+
+/// The intervals between keys typed in a row, in milliseconds. Corrections and pauses are
+/// left out, they are no part of the rhythm.
+pub fn intervals(keys: &[Key]) -> Vec<u32> {
+    keys.windows(2)
+        .filter(|pair| {
+            !pair
+                .iter()
+                .any(|key| key.1 == BACKSPACE || key.1 == DELETE_WORD)
+        })
+        .map(|pair| pair[1].0.saturating_sub(pair[0].0))
+        .filter(|interval| *interval <= HESITATION_MS)
+        .collect()
+}
+
+/// How evenly the keys of a test came, against a steady beat.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cadence {
+    intervals: Vec<u32>,
+    beat_ms: f32,
+}
+
+impl Cadence {
+    /// The intervals of `keys` against `beat`, or against their own average without one. None
+    /// before two keys in a row.
+    pub fn of(keys: &[Key], beat: Option<Duration>) -> Option<Self> {
+        let intervals = intervals(keys);
+        if intervals.is_empty() {
+            return None;
+        }
+        let beat_ms = match beat {
+            Some(beat) => beat.as_secs_f32() * 1000.,
+            None => intervals.iter().sum::<u32>() as f32 / intervals.len() as f32,
+        };
+        Some(Cadence {
+            intervals,
+            beat_ms: beat_ms.max(1.),
+        })
+    }
+
+    /// Time between two keys on the beat, in milliseconds.
+    pub fn beat_ms(&self) -> f32 {
+        self.beat_ms
+    }
+
+    pub fn intervals(&self) -> &[u32] {
+        &self.intervals
+    }
+
+    /// How far an interval is from the beat, as a share of it.
+    pub fn offset(&self, interval: u32) -> f32 {
+        (interval as f32 - self.beat_ms) / self.beat_ms
+    }
+
+    /// How close the intervals kept to the beat on average, in percent: 100 when every one of
+    /// them was on it exactly.
+    pub fn score(&self) -> f32 {
+        let offset = self
+            .intervals
+            .iter()
+            .map(|interval| self.offset(*interval).abs())
+            .sum::<f32>()
+            / self.intervals.len() as f32;
+        (100. * (1. - offset)).max(0.)
+    }
+
+    /// Intervals further than `TOLERANCE` from the beat.
+    pub fn off_beat(&self) -> usize {
+        self.intervals
+            .iter()
+            .filter(|interval| self.offset(**interval).abs() > TOLERANCE)
+            .count()
+    }
+}
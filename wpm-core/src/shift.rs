@@ -0,0 +1,69 @@
+use crate::{
+    keyboard::Layout,
+    latency::HESITATION_MS,
+    replay::{BACKSPACE, DELETE_WORD, Key},
+};
+
+/// How much slower the keys around Shift came than the others: the key typed with it, and
+/// the one after, once it is let go.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Penalty {
+    /// Keys typed with Shift
+    pub shifted: u32,
+    // intervals into or out of a shifted key, and the others, with the time they took
+    around: u32,
+    around_ms: u64,
+    plain: u32,
+    plain_ms: u64,
+}
+
+impl Penalty {
+    /// Sorts the intervals between `keys`. A key counts as shifted if the terminal reported
+    /// Shift held on it, its index being in `held`, or if it needs Shift on `layout`, for
+    /// terminals that don't. Pauses and corrections are left out, they are not about Shift.
+    pub fn of(keys: &[Key], held: &[usize], layout: Layout) -> Self {
+        let shifted = |i: usize| held.contains(&i) || layout.needs_shift(keys[i].1);
+        let mut penalty = Penalty {
+            shifted: (0..keys.len()).filter(|i| shifted(*i)).count() as u32,
+            ..Penalty::default()
+        };
+        for i in 1..keys.len() {
+            let interval = keys[i].0.saturating_sub(keys[i - 1].0);
+            if interval > HESITATION_MS
+                || [keys[i - 1].1, keys[i].1]
+                    .iter()
+                    .any(|key| [BACKSPACE, DELETE_WORD].contains(key))
+            {
+                continue;
+            }
+            if shifted(i) || shifted(i - 1) {
+                penalty.around += 1;
+                penalty.around_ms += interval as u64;
+            } else {
+                penalty.plain += 1;
+                penalty.plain_ms += interval as u64;
+            }
+        }
+        penalty
+    }
+
+    /// How much longer an interval around Shift took than the others on average, in
+    /// milliseconds. None without both kinds to compare.
+    pub fn per_interval(&self) -> Option<f32> {
+        (self.around > 0 && self.plain > 0).then(|| {
+            self.around_ms as f32 / self.around as f32 - self.plain_ms as f32 / self.plain as f32
+        })
+    }
+
+    /// Time the intervals around Shift took past the others, in milliseconds: what typing
+    /// shifted keys like the rest would save.
+    pub fn lost_ms(&self) -> f32 {
+        self.per_interval().unwrap_or(0.).max(0.) * self.around as f32
+    }
+
+    /// Time lost for each shifted key, in milliseconds, the shift penalty of the test.
+    pub fn per_key(&self) -> Option<f32> {
+        let per_interval = self.per_interval()?;
+        (self.shifted > 0).then(|| per_interval * self.around as f32 / self.shifted as f32)
+    }
+}
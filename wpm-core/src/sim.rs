@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use crate::{
+    clock::ManualClock,
+    content,
+    engine::{Milestone, Rules, TypingTest},
+    replay::{self, Key, Replay},
+    stats::Metric,
+};
+
+// Width the app wraps quotes at
+const LINE_WIDTH: usize = 50;
+
+/// Drives a [`TypingTest`] with scripted keys on a fake clock, to check what a test scores
+/// without a terminal or waiting in real time. Keys go in as they would from a replay, so dead
+/// keys are not composed, except through [`Simulation::type_keys`].
+///
+/// ```
+/// use wpm_core::{engine::Rules, sim::Simulation};
+///
+/// let mut sim = Simulation::new("hello world", Rules::default());
+/// sim.type_text("hello world", 100);
+///
+/// assert!(sim.test().is_done());
+/// assert_eq!(sim.test().accuracy(), 100.);
+/// ```
+#[derive(Debug)]
+pub struct Simulation {
+    test: TypingTest,
+    clock: ManualClock,
+    milestones: Vec<Milestone>,
+}
+
+impl Simulation {
+    /// A test on `text`, split into lines like the app does it.
+    pub fn new(text: &str, rules: Rules) -> Self {
+        Self::with_lines(content::wrap(text, LINE_WIDTH, true), rules)
+    }
+
+    pub fn with_lines(lines: Vec<String>, rules: Rules) -> Self {
+        let clock = ManualClock::new();
+        Simulation {
+            test: TypingTest::new(lines, rules, Metric::Wpm).with_clock(clock.clone()),
+            clock,
+            milestones: Vec::new(),
+        }
+    }
+
+    /// A test played back from the keys of a replay, at the pace they were recorded.
+    pub fn from_replay(replay: &Replay) -> Self {
+        let mut sim = Self::with_lines(replay.lines.clone(), replay.rules);
+        let mut last = 0;
+        for &Key(ms, key) in &replay.keys {
+            sim.press(key, u64::from(ms.saturating_sub(last)));
+            last = ms;
+        }
+        sim
+    }
+
+    /// Lets `ms` milliseconds pass without typing.
+    pub fn wait(&mut self, ms: u64) -> &mut Self {
+        self.clock.advance(Duration::from_millis(ms));
+        self
+    }
+
+    /// Presses a key `ms` milliseconds after the previous one.
+    pub fn press(&mut self, key: char, ms: u64) -> &mut Self {
+        self.wait(ms);
+        if let Some(milestone) = self.test.play_key(key) {
+            self.milestones.push(milestone);
+        }
+        self
+    }
+
+    /// Types every character of `text`, one every `ms` milliseconds.
+    pub fn type_text(&mut self, text: &str, ms: u64) -> &mut Self {
+        for char in text.chars() {
+            self.press(char, ms);
+        }
+        self
+    }
+
+    /// Types every character of `text` as keys of the keyboard, one every `ms` milliseconds,
+    /// composing dead keys with the letter after them.
+    pub fn type_keys(&mut self, text: &str, ms: u64) -> &mut Self {
+        for char in text.chars() {
+            self.wait(ms);
+            if let Some(milestone) = self.test.type_key(char) {
+                self.milestones.push(milestone);
+            }
+        }
+        self
+    }
+
+    pub fn backspace(&mut self, ms: u64) -> &mut Self {
+        self.press(replay::BACKSPACE, ms)
+    }
+
+    pub fn delete_word(&mut self, ms: u64) -> &mut Self {
+        self.press(replay::DELETE_WORD, ms)
+    }
+
+    pub fn test(&self) -> &TypingTest {
+        &self.test
+    }
+
+    pub fn into_test(self) -> TypingTest {
+        self.test
+    }
+
+    /// Every milestone reached so far, in order.
+    pub fn milestones(&self) -> &[Milestone] {
+        &self.milestones
+    }
+
+    /// Time on the fake clock since the first key.
+    pub fn elapsed(&self) -> Duration {
+        self.test.elapsed()
+    }
+
+    /// Speed as of the fake clock.
+    pub fn speed(&self) -> f32 {
+        self.test.speed(self.elapsed())
+    }
+}
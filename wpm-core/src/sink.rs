@@ -0,0 +1,123 @@
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use crate::{
+    history::Record,
+    replay::Replay,
+    storage::{Job, Writer},
+    worker::{NETWORK_TIMEOUT, Worker},
+};
+
+/// Somewhere the results of finished tests go. Sinks must not block, the results screen waits
+/// on them. What the app keeps between runs besides results, like letter stats or ratings, is
+/// written by [`storage`](crate::storage) whatever the sinks.
+pub trait StatsSink: fmt::Debug {
+    /// Takes a valid result, and its keystrokes when replays are recorded.
+    fn record(&mut self, record: &Record, replay: Option<&Replay>);
+}
+
+/// The history file and replays directory the app reads back, written in the background.
+impl StatsSink for Writer {
+    fn record(&mut self, record: &Record, replay: Option<&Replay>) {
+        self.write(Job::Record(record.clone()));
+        if let Some(replay) = replay {
+            self.write(Job::Replay(replay.clone()));
+        }
+    }
+}
+
+/// Appends each result as a line of JSON to a file of its own.
+#[derive(Debug)]
+pub struct JsonLog {
+    path: PathBuf,
+}
+
+impl JsonLog {
+    pub fn new(path: PathBuf) -> Self {
+        JsonLog { path }
+    }
+
+    fn append(&self, record: &Record) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(record)?)
+    }
+}
+
+impl StatsSink for JsonLog {
+    // A line is small enough to write in place, failures are dropped like for the history
+    fn record(&mut self, record: &Record, _replay: Option<&Replay>) {
+        let _ = self.append(record);
+    }
+}
+
+/// Prints every result as a line of JSON once dropped, after the terminal is given back.
+#[derive(Debug, Default)]
+pub struct Stdout {
+    records: Vec<Record>,
+}
+
+impl StatsSink for Stdout {
+    fn record(&mut self, record: &Record, _replay: Option<&Replay>) {
+        self.records.push(record.clone());
+    }
+}
+
+impl Drop for Stdout {
+    fn drop(&mut self) {
+        let mut stdout = io::stdout().lock();
+        for record in &self.records {
+            if let Ok(line) = serde_json::to_string(record) {
+                let _ = writeln!(stdout, "{line}");
+            }
+        }
+    }
+}
+
+/// POSTs each result as JSON to a URL, from a [`Worker`] thread so a slow server never holds
+/// up the results screen.
+#[derive(Debug)]
+pub struct Webhook(Worker<Record>);
+
+impl Webhook {
+    /// `authorization` is sent as is in the `Authorization` header, like `Bearer <token>`.
+    pub fn spawn(url: String, authorization: Option<String>) -> Self {
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .timeout_global(Some(NETWORK_TIMEOUT))
+            .build()
+            .into();
+        Webhook(Worker::spawn("webhook", move |record| {
+            let _ = post(&agent, &url, authorization.as_deref(), &record);
+        }))
+    }
+}
+
+fn post(
+    agent: &ureq::Agent,
+    url: &str,
+    authorization: Option<&str>,
+    record: &Record,
+) -> Result<(), ureq::Error> {
+    let mut request = agent.post(url).header("Content-Type", "application/json");
+    if let Some(authorization) = authorization {
+        request = request.header("Authorization", authorization);
+    }
+    request.send(serde_json::to_string(record).map_err(io::Error::from)?)?;
+    Ok(())
+}
+
+impl StatsSink for Webhook {
+    fn record(&mut self, record: &Record, _replay: Option<&Replay>) {
+        let _ = self.0.send(record.clone());
+    }
+}
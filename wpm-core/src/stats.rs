@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// Standard word length used to turn characters into words per minute
+pub const CHARS_PER_WORD: f32 = 5.;
+
+/// What speed is counted in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    /// Words per minute, five correct characters making a word
+    #[default]
+    Wpm,
+    /// Characters per minute, for languages where words are not clear cut
+    Cpm,
+}
+
+impl Metric {
+    /// Speed of `correct` characters typed in `duration`.
+    pub fn speed(self, correct: u32, duration: Duration) -> f32 {
+        match self {
+            Metric::Wpm => wpm(correct, duration),
+            Metric::Cpm => per_minute(correct, duration),
+        }
+    }
+
+    pub fn unit(self) -> &'static str {
+        match self {
+            Metric::Wpm => "wpm",
+            Metric::Cpm => "cpm",
+        }
+    }
+
+    pub fn is_wpm(&self) -> bool {
+        *self == Metric::Wpm
+    }
+}
+
+pub fn per_minute(count: u32, duration: Duration) -> f32 {
+    let mut minutes = duration.as_secs_f32() / 60.;
+    if minutes == 0. {
+        minutes = 0.01;
+    }
+
+    count as f32 / minutes
+}
+
+/// Standard words per minute, counting every five correct characters as a word.
+pub fn wpm(correct: u32, duration: Duration) -> f32 {
+    per_minute(correct, duration) / CHARS_PER_WORD
+}
+
+pub fn accuracy(correct: u32, incorrect: u32) -> f32 {
+    if correct + incorrect > 0 {
+        (correct as f32 / (correct + incorrect) as f32) * 100.
+    } else {
+        0.
+    }
+}
+
+/// Rough 0 to 10 score of how hard a text is to type, from its word lengths and how many
+/// characters need shift or are off the letter keys.
+pub fn difficulty(text: &str) -> f32 {
+    let words = text.split_whitespace().count().max(1) as f32;
+    let chars = text.chars().filter(|c| !c.is_whitespace()).count().max(1) as f32;
+    let awkward = text
+        .chars()
+        .filter(|c| c.is_uppercase() || (!c.is_alphanumeric() && !c.is_whitespace()))
+        .count() as f32;
+    let non_ascii = text.chars().filter(|c| !c.is_ascii()).count() as f32;
+
+    let word_length = chars / words;
+    let score = (word_length - 3.) * 1.5 + awkward / chars * 25. + non_ascii / chars * 40.;
+    score.clamp(0., 10.)
+}
@@ -0,0 +1,45 @@
+use crate::{
+    history::{self, Record},
+    stats::Metric,
+};
+
+/// What `wpm status` prints without a format.
+pub const DEFAULT_FORMAT: &str = "{avg_wpm_today} wpm";
+// Shown for values there is no result for yet
+const MISSING: &str = "-";
+
+/// A one-line summary of the history for status bars and prompts, each `{placeholder}` of
+/// `format` replaced by its value. Results from `today` on, a timestamp, count as today's.
+///
+/// Placeholders are `avg_wpm`, `best_wpm`, `best_burst`, `last_wpm`, `accuracy` and `tests`
+/// over the whole history, and the same with a `_today` suffix. Unknown ones are left as is.
+///
+/// ```
+/// use wpm_core::{history::Record, status};
+///
+/// let records = [Record::now(1, 0, 72.4, 97., 12, 10.)];
+/// assert_eq!(status::summary("{best_wpm} wpm in {tests} tests", &records, 0), "72 wpm in 1 tests");
+/// ```
+pub fn summary(format: &str, records: &[Record], today: u64) -> String {
+    let mut line = format.to_string();
+    for (suffix, records) in [("", records), ("_today", history::since(records, today))] {
+        let count = records.len() as f32;
+        let average = |value: fn(&Record) -> f32| {
+            (!records.is_empty()).then(|| records.iter().map(value).sum::<f32>() / count)
+        };
+
+        let values = [
+            ("avg_wpm", average(|r| r.wpm)),
+            ("best_wpm", history::personal_best(records)),
+            ("best_burst", history::peak_burst_in(records, Metric::Wpm)),
+            ("last_wpm", records.last().map(|r| r.wpm)),
+            ("accuracy", average(|r| r.accuracy)),
+            ("tests", Some(count)),
+        ];
+        for (name, value) in values {
+            let value = value.map_or(MISSING.to_string(), |value| format!("{value:.0}"));
+            line = line.replace(&format!("{{{name}{suffix}}}"), &value);
+        }
+    }
+    line
+}
@@ -0,0 +1,72 @@
+use crate::{
+    achievements::Unlocked, course::Progress, endurance, history, latency::Histogram,
+    letters::Letters, rating::Ratings, replay, review, worker::Worker,
+};
+
+/// Something to write to disk.
+#[derive(Debug)]
+pub enum Job {
+    Record(history::Record),
+    Replay(replay::Replay),
+    /// Letter stats of a test, added to the stored ones
+    Letters(Letters),
+    /// Progress through the course, in place of the stored one
+    Course(Progress),
+    /// Intervals between the keys of a test, added to the stored ones
+    Latency(Histogram),
+    /// Words of a test, added to the review queue
+    Review(review::Session),
+    /// An endurance run, kept apart from the history
+    Endurance(endurance::Run),
+    /// Achievements unlocked so far, in place of the stored ones
+    Achievements(Unlocked),
+    /// Tags given to quotes so far, in place of the stored ones
+    Ratings(Ratings),
+}
+
+impl Job {
+    // Failed writes are dropped, like they were when done in place
+    fn run(self) {
+        let _ = match self {
+            Job::Record(record) => history::append(&record),
+            Job::Replay(replay) => replay::save(&replay).map(|_| ()),
+            Job::Letters(letters) => {
+                let mut stored = Letters::load();
+                stored.merge(&letters);
+                stored.save()
+            }
+            Job::Latency(histogram) => {
+                let mut stored = Histogram::load();
+                stored.merge(&histogram);
+                stored.save()
+            }
+            Job::Course(progress) => progress.save(),
+            Job::Endurance(run) => endurance::append(&run),
+            Job::Achievements(unlocked) => unlocked.save(),
+            Job::Ratings(ratings) => ratings.save(),
+            Job::Review(session) => {
+                let mut queue = review::Queue::load();
+                queue.add(&session);
+                queue.save()
+            }
+        };
+    }
+}
+
+/// Writes history records and replays on a [`Worker`] thread, so finishing a test never waits
+/// on the disk.
+#[derive(Debug)]
+pub struct Writer(Worker<Job>);
+
+impl Writer {
+    pub fn spawn() -> Self {
+        Writer(Worker::spawn("storage", Job::run))
+    }
+
+    /// Queues a write, or does it right away if the thread could not be started.
+    pub fn write(&self, job: Job) {
+        if let Err(job) = self.0.send(job) {
+            job.run();
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    io,
+    net::{TcpListener, TcpStream},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, Sender},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use tungstenite::{Message, WebSocket};
+
+use crate::{engine::TypingTest, worker::lock};
+
+// Frames go out at least this often while the test moves, speed changes even without typing
+const INTERVAL: Duration = Duration::from_secs(1);
+// A client this slow to take a frame is dropped rather than let its queue grow
+const WRITE_TIMEOUT: Duration = Duration::from_millis(100);
+// A client that connects and says nothing for this long is let go
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where the test stands, sent to overlays as a JSON object.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Frame {
+    pub quote_id: u32,
+    /// Seconds since the first key
+    pub elapsed: f32,
+    pub wpm: f32,
+    pub accuracy: f32,
+    /// Share of the text typed, from 0 to 1
+    pub progress: f32,
+    /// Line of the caret and graphemes typed on it, from 0
+    pub line: usize,
+    pub column: usize,
+    pub done: bool,
+}
+
+impl Frame {
+    pub fn of(test: &TypingTest, quote_id: u32) -> Self {
+        let elapsed = test.elapsed();
+        Frame {
+            quote_id,
+            elapsed: elapsed.as_secs_f32(),
+            wpm: test.speed(elapsed),
+            accuracy: test.accuracy(),
+            progress: test.progress(),
+            line: test.current_line(),
+            column: test.typing().len(),
+            done: test.is_done(),
+        }
+    }
+
+    fn same_place(&self, other: &Frame) -> bool {
+        (self.quote_id, self.line, self.column, self.done)
+            == (other.quote_id, other.line, other.column, other.done)
+    }
+}
+
+/// The writing end of an overlay's socket, on a thread of its own: sending only queues the
+/// frame, so an overlay slow to read never holds up the game.
+#[derive(Debug)]
+struct Overlay(Sender<String>);
+
+impl Overlay {
+    fn spawn(mut socket: WebSocket<TcpStream>) -> Self {
+        let _ = socket.get_ref().set_write_timeout(Some(WRITE_TIMEOUT));
+        let (frames, queue) = mpsc::channel::<String>();
+        thread::spawn(move || {
+            for frame in queue {
+                // Gone for good after a failed write, sends fail from then on
+                if socket.send(Message::text(frame)).is_err() {
+                    return;
+                }
+            }
+        });
+        Overlay(frames)
+    }
+
+    /// Queues a frame, false once the overlay is gone.
+    fn send(&self, frame: String) -> bool {
+        self.0.send(frame).is_ok()
+    }
+}
+
+/// Streams the test live to browser overlays, over WebSocket on the local machine only.
+#[derive(Debug)]
+pub struct Telemetry {
+    port: u16,
+    clients: Arc<Mutex<Vec<Overlay>>>,
+    last: Option<(Frame, Instant)>,
+}
+
+impl Telemetry {
+    /// Waits for overlays on the given port in the background, any free one for 0.
+    pub fn serve(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        let port = listener.local_addr()?.port();
+        let clients = Arc::new(Mutex::new(Vec::new()));
+
+        let accept_clients = clients.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                // Each handshake on a thread of its own, so a silent client holds up no other
+                let clients = accept_clients.clone();
+                thread::spawn(move || {
+                    let _ = stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT));
+                    let Ok(socket) = tungstenite::accept(stream) else {
+                        return;
+                    };
+                    lock(&clients).push(Overlay::spawn(socket));
+                });
+            }
+        });
+
+        Ok(Telemetry {
+            port,
+            clients,
+            last: None,
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Overlays connected right now.
+    pub fn clients(&self) -> usize {
+        lock(&self.clients).len()
+    }
+
+    /// Sends the frame when the caret moved, or a second after the last one if anything else
+    /// changed.
+    pub fn update(&mut self, frame: Frame) {
+        if let Some((last, sent)) = &self.last
+            && (*last == frame || (last.same_place(&frame) && sent.elapsed() < INTERVAL))
+        {
+            return;
+        }
+
+        if let Ok(json) = serde_json::to_string(&frame) {
+            lock(&self.clients).retain(|client| client.send(json.clone()));
+        }
+        self.last = Some((frame, Instant::now()));
+    }
+}
@@ -0,0 +1,69 @@
+use std::{
+    sync::{
+        Mutex, MutexGuard,
+        mpsc::{self, Sender},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// How long a request to a server may take: long enough for one that is slow or waking up,
+/// short enough that quitting, which waits for the requests queued, or a command someone waits
+/// on never hangs.
+pub const NETWORK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A thread working through the jobs sent to it in order, so whoever sends them never waits on
+/// the disk or the network. Dropping it waits for the jobs queued so far, so none are lost on
+/// exit.
+#[derive(Debug)]
+pub struct Worker<T> {
+    jobs: Option<Sender<T>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl<T: Send + 'static> Worker<T> {
+    /// Does `work` on each job on a thread called `name`. Failed jobs are up to `work`, they
+    /// are dropped as a rule since nobody waits for them.
+    pub fn spawn(name: &str, mut work: impl FnMut(T) + Send + 'static) -> Self {
+        let (jobs, queue) = mpsc::channel::<T>();
+        let thread = thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || {
+                for job in queue {
+                    work(job);
+                }
+            })
+            .ok();
+
+        Worker {
+            jobs: thread.is_some().then_some(jobs),
+            thread,
+        }
+    }
+
+    /// Queues a job, or hands it back when the thread could not be started.
+    pub fn send(&self, job: T) -> Result<(), T> {
+        match &self.jobs {
+            Some(jobs) => jobs.send(job).map_err(|mpsc::SendError(job)| job),
+            None => Err(job),
+        }
+    }
+}
+
+impl<T> Drop for Worker<T> {
+    fn drop(&mut self) {
+        // Closing the channel ends the thread once the queue is empty
+        self.jobs.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Locks data shared with other threads. One panicking while holding the lock leaves the data
+/// usable, so it is taken anyway.
+pub fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,51 @@
+use crate::history::Record;
+
+/// Points for each word of an accurate text of no difficulty.
+pub const POINTS_PER_WORD: f32 = 10.;
+/// Points it takes to go from the first level to the second, each level after that taking
+/// this much more than the one before.
+pub const LEVEL_XP: u64 = 500;
+
+/// Points a result is worth: more for longer texts and harder ones, far less for sloppy
+/// typing. 95% accuracy keeps about four fifths of the points, 80% less than half, and a text
+/// of difficulty 10 is worth twice one of 0.
+pub fn points(record: &Record) -> u64 {
+    let accuracy = (record.accuracy / 100.).clamp(0., 1.).powi(4);
+    let difficulty = 1. + record.difficulty.clamp(0., 10.) / 10.;
+    (record.words as f32 * POINTS_PER_WORD * accuracy * difficulty).round() as u64
+}
+
+/// Points of every result of the history.
+pub fn total<'a>(records: impl IntoIterator<Item = &'a Record>) -> u64 {
+    records.into_iter().map(points).sum()
+}
+
+/// Where some points put a typist, from level 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Level {
+    pub level: u32,
+    /// Points earned since reaching the level
+    pub xp: u64,
+    /// Points the level takes to reach the next one
+    pub needed: u64,
+}
+
+impl Level {
+    pub fn of(mut xp: u64) -> Self {
+        let mut level = 1;
+        while xp >= LEVEL_XP * level as u64 {
+            xp -= LEVEL_XP * level as u64;
+            level += 1;
+        }
+        Level {
+            level,
+            xp,
+            needed: LEVEL_XP * level as u64,
+        }
+    }
+
+    /// How far along the level is, in percent.
+    pub fn progress(&self) -> f32 {
+        self.xp as f32 / self.needed as f32 * 100.
+    }
+}
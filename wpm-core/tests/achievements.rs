@@ -0,0 +1,55 @@
+mod common;
+
+use common::{DAY, record};
+use wpm_core::{
+    achievements::{self, Achievement, Unlocked},
+    history::Record,
+};
+
+// Noon, far from midnight in any time zone change
+const START: u64 = 1000 * DAY + DAY / 2;
+
+fn on(day: u64) -> u64 {
+    START + day * DAY
+}
+
+#[test]
+fn days_in_a_row_make_a_streak() {
+    let records: Vec<Record> = [0, 1, 1, 2, 4, 5, 6, 7, 8, 9]
+        .map(|day| record(on(day), 50., 95.))
+        .to_vec();
+    assert_eq!(achievements::longest_day_streak(&records), 6);
+    assert!(!Achievement::Streak.earned(&records));
+
+    let mut records = records;
+    records.push(record(on(10), 50., 95.));
+    assert!(Achievement::Streak.earned(&records));
+}
+
+#[test]
+fn accuracy_only_counts_on_long_texts() {
+    assert!(!Achievement::Accurate.earned(&[record(on(0), 50., 100.)]));
+    assert!(Achievement::Accurate.earned(&[Record {
+        words: 50,
+        ..record(on(0), 50., 99.)
+    }]));
+    assert!(!Achievement::Fast.earned(&[record(on(0), 99.9, 100.)]));
+    assert!(Achievement::Fast.earned(&[record(on(0), 100., 100.)]));
+}
+
+#[test]
+fn achievements_unlock_once() {
+    let mut unlocked = Unlocked::default();
+    let mut records = vec![record(on(0), 104., 97.)];
+    assert_eq!(unlocked.update(&records, 5), [Achievement::Fast]);
+    assert_eq!(unlocked.get(Achievement::Fast), Some(5));
+
+    records.extend((1..100).map(|day| record(on(day), 60., 90.)));
+    assert_eq!(
+        unlocked.update(&records, 9),
+        [Achievement::Streak, Achievement::Tests]
+    );
+    assert_eq!(unlocked.get(Achievement::Fast), Some(5));
+    assert!(unlocked.update(&records, 10).is_empty());
+    assert_eq!(unlocked.len(), 3);
+}
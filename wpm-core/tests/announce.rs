@@ -0,0 +1,134 @@
+mod common;
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread,
+};
+
+use common::record;
+use wpm_core::{
+    announce::{Announcer, Room},
+    history::Record,
+    sink::StatsSink,
+    stats::Metric,
+};
+
+#[test]
+fn irc_gets_new_personal_bests_only() {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let server = listener.local_addr().unwrap().to_string();
+    // A connection for each message
+    let irc = thread::spawn(move || {
+        let mut received = Vec::new();
+        for _ in 0..2 {
+            let (mut stream, _) = listener.accept().unwrap();
+            for line in BufReader::new(stream.try_clone().unwrap()).lines() {
+                let line = line.unwrap();
+                if line.starts_with("USER") {
+                    write!(stream, ":irc.test 001 wpm-ann :Welcome\r\n").unwrap();
+                }
+                if line == "QUIT" {
+                    break;
+                }
+                received.push(line);
+            }
+        }
+        received
+    });
+
+    let mut announcer = Announcer::spawn(
+        Room::Irc {
+            server,
+            channel: "#typing".to_string(),
+            nick: "wpm-ann".to_string(),
+        },
+        "ann".to_string(),
+        vec![record(0, 80., 98.)],
+    );
+    announcer.record(&record(0, 70., 99.), None);
+    announcer.record(&record(0, 90., 97.5), None);
+    // Characters per minute are only compared to each other
+    let cpm = |wpm| Record {
+        metric: Metric::Cpm,
+        ..record(0, wpm, 96.)
+    };
+    announcer.record(&cpm(85.), None);
+    announcer.record(&cpm(84.), None);
+    drop(announcer);
+
+    let received = irc.join().unwrap();
+    assert_eq!(
+        received,
+        [
+            "NICK wpm-ann",
+            "USER wpm-ann 0 * :wpm",
+            "JOIN #typing",
+            "PRIVMSG #typing :ann just hit 90 WPM (97.5% acc) on wpm-rust",
+            "NICK wpm-ann",
+            "USER wpm-ann 0 * :wpm",
+            "JOIN #typing",
+            "PRIVMSG #typing :ann just hit 85 CPM (96.0% acc) on wpm-rust",
+        ]
+    );
+}
+
+#[test]
+fn matrix_rooms_get_a_text_message() {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let homeserver = format!("http://{}", listener.local_addr().unwrap());
+    let matrix = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut head = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                break;
+            }
+            head.push(line.trim().to_lowercase());
+        }
+        let length: usize = head
+            .iter()
+            .find_map(|line| line.strip_prefix("content-length: ")?.parse().ok())
+            .unwrap();
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+
+        let answer = r#"{"event_id": "$1"}"#;
+        write!(
+            &stream,
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{answer}",
+            answer.len()
+        )
+        .unwrap();
+        (head, String::from_utf8(body).unwrap())
+    });
+
+    let mut announcer = Announcer::spawn(
+        Room::Matrix {
+            homeserver,
+            room: "!club:example.org".to_string(),
+            token: "secret".to_string(),
+        },
+        "ann".to_string(),
+        Vec::new(),
+    );
+    announcer.record(&record(0, 104., 98.2), None);
+    drop(announcer);
+
+    let (head, body) = matrix.join().unwrap();
+    assert!(
+        head[0]
+            .starts_with("put /_matrix/client/v3/rooms/%21club%3aexample.org/send/m.room.message/"),
+        "{}",
+        head[0]
+    );
+    assert!(head.contains(&"authorization: bearer secret".to_string()));
+    assert!(body.contains(r#""msgtype":"m.text""#), "{body}");
+    assert!(
+        body.contains("ann just hit 104 WPM (98.2% acc) on wpm-rust"),
+        "{body}"
+    );
+}
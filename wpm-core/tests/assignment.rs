@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+
+use wpm_core::{
+    assignment::{self, Assignment, Attempt, StudentKey, Submission},
+    engine::Rules,
+    sim::Simulation,
+};
+
+/// An assignment with `key` as ann's.
+fn assignment(key: &StudentKey) -> Assignment {
+    Assignment {
+        title: "Week 1".to_string(),
+        texts: vec!["the cat sat".to_string(), "a dog ran off".to_string()],
+        rules: Rules::default(),
+        min_accuracy: 95.,
+        attempts: 2,
+        students: BTreeMap::from([("ann".to_string(), key.public())]),
+    }
+}
+
+/// An attempt typing `typed` on the text, a key every 100 ms.
+fn attempt(assignment: &Assignment, text: usize, typed: &str) -> Attempt {
+    let mut sim = Simulation::new(&assignment.texts[text], assignment.rules);
+    sim.type_text(typed, 100);
+    Attempt {
+        text,
+        invalid: None,
+        replay: sim.test().replay(text as u32),
+    }
+}
+
+#[test]
+fn grades_come_from_the_keystrokes() {
+    let key = StudentKey::generate().unwrap();
+    let assignment = assignment(&key);
+    let mut submission = Submission::new("ann".to_string(), &assignment);
+    submission.attempts = vec![
+        attempt(&assignment, 0, "the cat sat"),
+        attempt(&assignment, 1, "a dxg rxn off"),
+        attempt(&assignment, 1, "a dog rxn off"),
+    ];
+    submission.sign(&key);
+    assert!(submission.verify(&key.public()));
+
+    let grade = assignment::grade(&assignment, &submission);
+    assert!(grade.problems.is_empty(), "{:?}", grade.problems);
+    assert_eq!(grade.attempts, [1, 2]);
+    assert_eq!(grade.passed(), 1);
+    let first = grade.scores[0].unwrap();
+    assert!(first.passed && first.accuracy == 100.);
+    // The better of the two, still not accurate enough
+    let second = grade.scores[1].unwrap();
+    assert!(!second.passed && second.accuracy > 90.);
+}
+
+#[test]
+fn submissions_need_the_signature_of_their_student() {
+    let key = StudentKey::generate().unwrap();
+    let assignment = assignment(&key);
+    let mut submission = Submission::new("ann".to_string(), &assignment);
+    submission.attempts = vec![attempt(&assignment, 0, "the cat sat")];
+    submission.sign(&key);
+
+    let mut edited = submission.clone();
+    edited
+        .attempts
+        .push(attempt(&assignment, 1, "a dog ran off"));
+    assert!(!edited.verify(&key.public()));
+
+    // Signed by someone else in ann's name
+    let mut forged = submission.clone();
+    forged.sign(&StudentKey::generate().unwrap());
+    assert!(!forged.verify(&key.public()));
+    assert_eq!(assignment::grade(&assignment, &forged).problems.len(), 1);
+
+    let mut unknown = submission.clone();
+    unknown.student = "bob".to_string();
+    unknown.sign(&key);
+    let grade = assignment::grade(&assignment, &unknown);
+    assert_eq!(grade.problems, ["the student has no key in the assignment"]);
+}
+
+#[test]
+fn doubtful_submissions_are_flagged() {
+    let key = StudentKey::generate().unwrap();
+    let assignment = assignment(&key);
+    let mut submission = Submission::new("ann".to_string(), &assignment);
+    submission.attempts = vec![attempt(&assignment, 0, "the cat sat")];
+    submission.sign(&key);
+
+    let mut other = assignment.clone();
+    other.title = "Week 2".to_string();
+    let grade = assignment::grade(&other, &submission);
+    assert_eq!(grade.problems, [r#"the results are for "Week 1""#]);
+
+    // Attempts on other rules or past the limit don't count
+    let mut extra = submission.clone();
+    extra.attempts[0].replay.rules.stop_on_error = true;
+    extra.attempts.push(attempt(&assignment, 0, "the cat sat"));
+    extra.attempts.push(attempt(&assignment, 0, "the cat sat"));
+    extra.attempts.push(attempt(&assignment, 0, "the cat sat"));
+    extra.sign(&key);
+    let grade = assignment::grade(&assignment, &extra);
+    assert_eq!(grade.attempts, [2, 0]);
+    assert_eq!(grade.problems.len(), 2, "{:?}", grade.problems);
+}
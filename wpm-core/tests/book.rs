@@ -0,0 +1,65 @@
+use wpm_core::content::{Book, ContentProvider};
+
+const TEXT: &str = "It was the best of times,\nit was the worst of times,\n\n\
+                    it was the age of wisdom,   it was the age of foolishness.\n";
+
+fn book(width: usize, page_lines: usize) -> Book<&'static [u8]> {
+    Book::new("Two Cities".to_string(), TEXT.as_bytes(), width, page_lines)
+}
+
+#[test]
+fn pages_follow_the_text() {
+    let mut book = book(30, 2);
+
+    assert_eq!(
+        book.next_page().unwrap(),
+        [
+            "It was the best of times, it",
+            "was the worst of times, it was"
+        ]
+    );
+    assert_eq!(book.page(), 1);
+    assert_eq!(
+        book.next_page().unwrap(),
+        ["the age of wisdom, it was the", "age of foolishness."]
+    );
+    assert!(book.next_page().unwrap().is_empty());
+    assert_eq!(book.page(), 2);
+}
+
+#[test]
+fn pages_join_into_the_whole_text() {
+    let mut book = book(12, 3);
+    let mut pages = Vec::new();
+    while let Some(quote) = book.next_text(0) {
+        // Each page is a quote of its own
+        assert_eq!(quote.id as usize, pages.len() + 1);
+        pages.push(quote.text);
+    }
+
+    let words: Vec<&str> = TEXT.split_whitespace().collect();
+    assert_eq!(pages.join(" "), words.join(" "));
+    assert_eq!(book.page(), pages.len());
+}
+
+#[test]
+fn lines_fit_the_width() {
+    let mut book = book(12, 3);
+    loop {
+        let page = book.next_page().unwrap();
+        if page.is_empty() {
+            break;
+        }
+        assert!(
+            page.iter().all(|line| line.chars().count() <= 12),
+            "{page:?}"
+        );
+    }
+}
+
+#[test]
+fn files_that_are_not_text_are_reported() {
+    let bytes: &[u8] = b"caf\xe9 au lait\n";
+    let error = Book::open("latin1.txt".to_string(), bytes, 30, 2).unwrap_err();
+    assert_eq!(error.to_string(), "latin1.txt is not UTF-8 text");
+}
@@ -0,0 +1,57 @@
+use wpm_core::{
+    engine::Rules,
+    history::{self, Record},
+    sim::Simulation,
+    stats::Metric,
+};
+
+#[test]
+fn bursts_keep_the_fastest_stretch() {
+    let text = "aaaa ".repeat(40);
+    let mut sim = Simulation::new(text.trim_end(), Rules::default());
+    // 12 keys a second, then twice as fast for the last 6 seconds
+    sim.type_text(&text[..60], 83)
+        .type_text(text[60..].trim_end(), 42);
+
+    let test = sim.test();
+    assert!(test.is_done());
+    // 5 seconds at 42 ms a key hold 120 keys, or 24 words
+    assert!((test.burst() - 288.).abs() < 0.01, "{}", test.burst());
+    assert!(test.speed(test.elapsed()) < test.burst() - 50.);
+    // 5 keys in 210 ms
+    assert!((test.word_burst() - 285.7).abs() < 0.1);
+
+    let record = test.record(1, 0);
+    assert_eq!(record.burst, test.burst());
+    let older = Record {
+        burst: 300.,
+        ..Record::now(2, 0, 100., 100., 10, 10.)
+    };
+    assert_eq!(
+        history::peak_burst_in(&[record, older], Metric::Wpm),
+        Some(300.)
+    );
+}
+
+#[test]
+fn text_committed_at_once_is_no_burst() {
+    let mut sim = Simulation::new("abcdefghij", Rules::default());
+    // Like an input method committing the whole text in one go
+    sim.type_text("abcdefghij", 0);
+
+    // Over a second at least
+    assert!(sim.test().burst() <= 120., "{}", sim.test().burst());
+}
+
+#[test]
+fn words_with_mistakes_are_no_bursts() {
+    let mut sim = Simulation::new("ab cd ef", Rules::default());
+    sim.type_text("ab ", 200)
+        .type_text("cx", 50)
+        .backspace(50)
+        .type_text("d ", 50)
+        .type_text("ef", 200);
+
+    // The fast word was corrected, the others take 2 keys in 400 ms
+    assert!((sim.test().word_burst() - 60.).abs() < 0.01);
+}
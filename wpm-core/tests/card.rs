@@ -0,0 +1,73 @@
+use wpm_core::{
+    card,
+    engine::Rules,
+    history::Record,
+    replay::{Key, Replay},
+    sim::Simulation,
+};
+
+#[test]
+fn cards_show_the_result_and_its_graph() {
+    let record = Record::now(3, 0, 87.4, 96.25, 12, 41.3);
+    let svg = card::svg(
+        &record,
+        "Tom & Jerry",
+        &[(1., 60.), (2., 80.)],
+        "last 2 results",
+    );
+
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains(">87<"));
+    assert!(svg.contains("96.2% accuracy"));
+    assert!(svg.contains("Tom &amp; Jerry"));
+    assert!(svg.contains("<polyline"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+
+    // A single point makes no graph
+    assert!(!card::svg(&record, "", &[(1., 60.)], "").contains("<polyline"));
+}
+
+#[test]
+fn text_cards_hold_the_challenge_as_a_qr_code() {
+    let record = Record::now(3, 0, 87.4, 96.25, 12, 41.3);
+    let card = card::text(&record, "Tom & Jerry", Some("0123456789ABCDEFGHJKM"));
+    let lines: Vec<&str> = card.lines().collect();
+
+    assert!(lines[0].starts_with("╭─") && lines[lines.len() - 1].starts_with("╰─"));
+    assert!(lines[2].contains("87 wpm · 96.2% accuracy · 41.3s"));
+    assert!(lines[3].contains("Tom & Jerry"));
+    assert!(lines[lines.len() - 2].contains("wpm challenge 0123456789ABCDEFGHJKM"));
+    let width = lines[0].chars().count();
+    assert!(lines.iter().all(|line| line.chars().count() == width));
+
+    let plain = card::text(&record, "Tom & Jerry", None);
+    assert_eq!(plain.lines().count(), 5);
+}
+
+#[test]
+fn qr_codes_take_two_rows_of_modules_a_line() {
+    // 21 modules of a version 1 code and a quiet zone of 2 on each side
+    let qr = card::qr("0123456789ABCDEFGHJKM").unwrap();
+    assert_eq!(qr.len(), 13);
+    assert!(qr.iter().all(|line| line.chars().count() == 25));
+
+    // The quiet zone is light, the corner of the top left finder pattern dark
+    assert!(qr[0].chars().all(|c| c == '█'));
+    assert_eq!(qr[1].chars().nth(2), Some(' '));
+    assert!(card::qr(&"x".repeat(5000)).is_none());
+}
+
+#[test]
+fn replays_play_back_at_their_pace() {
+    let keys = "hi you"
+        .chars()
+        .enumerate()
+        .map(|(i, key)| Key(i as u32 * 200, key))
+        .collect();
+    let replay = Replay::now(3, vec!["hi you".to_string()], Rules::default(), keys);
+
+    let sim = Simulation::from_replay(&replay);
+    assert!(sim.test().is_done());
+    assert_eq!(sim.elapsed().as_millis(), 1000);
+    assert_eq!(sim.test().samples().len(), 2);
+}
@@ -0,0 +1,27 @@
+use wpm_core::content::{Casing, recase};
+
+const TEXT: &str = "Élan of the Quick fox, isn't it?";
+
+#[test]
+fn texts_can_be_typed_in_one_case() {
+    assert_eq!(recase(TEXT, Casing::Exact), TEXT);
+    assert_eq!(
+        recase(TEXT, Casing::Lower),
+        "élan of the quick fox, isn't it?"
+    );
+    assert_eq!(
+        recase(TEXT, Casing::Upper),
+        "ÉLAN OF THE QUICK FOX, ISN'T IT?"
+    );
+}
+
+#[test]
+fn random_casing_only_capitalizes_word_starts() {
+    for _ in 0..20 {
+        let recased = recase(TEXT, Casing::Random);
+        assert_eq!(recased.to_lowercase(), TEXT.to_lowercase());
+        for word in recased.split_whitespace() {
+            assert!(!word.chars().skip(1).any(char::is_uppercase), "{recased}");
+        }
+    }
+}
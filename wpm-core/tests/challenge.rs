@@ -0,0 +1,41 @@
+use wpm_core::{challenge::Challenge, engine::Rules, punctuation::Punctuation};
+
+fn challenge() -> Challenge {
+    Challenge {
+        quote_id: 4242,
+        rules: Rules {
+            stop_on_error: true,
+            punctuation: Punctuation::Accept,
+            ..Rules::default()
+        },
+        trailing_space: false,
+        wpm: 87.3,
+        accuracy: 96.5,
+        seconds: 41.2,
+    }
+}
+
+#[test]
+fn codes_round_trip() {
+    let code = challenge().encode();
+    assert_eq!(code.len(), 21);
+    assert_eq!(Challenge::decode(&code), Some(challenge()));
+
+    // Read back from a chat message, as typed by hand
+    let spaced = code.to_lowercase().replace('0', "o").replace('1', "l");
+    assert_eq!(Challenge::decode(&format!(" {spaced} ")), Some(challenge()));
+}
+
+#[test]
+fn typos_are_caught() {
+    let code = challenge().encode();
+    let mut typo: Vec<char> = code.chars().collect();
+    typo[8] = if typo[8] == 'A' { 'B' } else { 'A' };
+
+    assert_eq!(
+        Challenge::decode(&typo.into_iter().collect::<String>()),
+        None
+    );
+    assert_eq!(Challenge::decode(&code[..10]), None);
+    assert_eq!(Challenge::decode("not a code!"), None);
+}
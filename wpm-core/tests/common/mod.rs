@@ -0,0 +1,14 @@
+//! Fixtures shared by the tests, each of them using only some.
+#![allow(dead_code)]
+
+use wpm_core::history::Record;
+
+pub const DAY: u64 = 24 * 60 * 60;
+
+/// A valid result of 20 words in 30 seconds, finished at `timestamp`.
+pub fn record(timestamp: u64, wpm: f32, accuracy: f32) -> Record {
+    Record {
+        timestamp,
+        ..Record::now(1, 0, wpm, accuracy, 20, 30.)
+    }
+}
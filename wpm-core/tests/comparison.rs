@@ -0,0 +1,73 @@
+mod common;
+
+use common::{DAY, record};
+use wpm_core::{
+    history::{self, Record},
+    stats::Metric,
+};
+
+const NOW: u64 = 100 * DAY;
+
+fn ago(days: f32) -> u64 {
+    NOW - (days * DAY as f32) as u64
+}
+
+#[test]
+fn weeks_compare_to_the_one_before() {
+    let records = [
+        record(ago(20.), 40., 90.),
+        record(ago(10.), 60., 94.),
+        record(ago(8.), 62., 96.),
+        record(ago(3.), 70., 97.),
+        record(ago(0.5), 72., 99.),
+        record(ago(0.), 68., 98.),
+    ];
+    let week = history::compare(&records, NOW, 7);
+    assert_eq!(week.current.tests, 3);
+    assert_eq!(week.previous.tests, 2);
+    assert_eq!(week.current.wpm, Some(70.));
+    assert_eq!(week.previous.wpm, Some(61.));
+    assert_eq!(week.wpm(), Some(9.));
+    assert_eq!(week.accuracy(), Some(3.));
+    assert_eq!(week.tests(), 1);
+    assert_eq!(week.minutes(), 0.5);
+
+    let month = history::compare(&records, NOW, 30);
+    assert_eq!(month.current.tests, 6);
+    assert_eq!(month.previous, history::Period::default());
+    assert_eq!(month.wpm(), None);
+    assert_eq!(month.tests(), 6);
+}
+
+#[test]
+fn speed_averages_leave_out_other_units() {
+    let records = [
+        record(ago(1.), 60., 95.),
+        Record {
+            metric: Metric::Cpm,
+            ..record(ago(0.), 300., 85.)
+        },
+    ];
+    let period = history::compare(&records, NOW, 7).current;
+    assert_eq!(period.tests, 2);
+    assert_eq!(period.wpm, Some(60.));
+    assert_eq!(period.accuracy, Some(90.));
+}
+
+#[test]
+fn categories_add_up_their_results() {
+    let records =
+        [("code", 40.), ("english", 80.), ("code", 50.), ("", 100.)].map(|(category, wpm)| {
+            Record {
+                category: category.to_string(),
+                ..record(ago(1.), wpm, 95.)
+            }
+        });
+    let categories = history::categories(&records);
+    assert_eq!(categories.len(), 2);
+    assert_eq!(categories[0].0, "code");
+    assert_eq!(categories[0].1.tests, 2);
+    assert_eq!(categories[0].1.wpm, Some(45.));
+    assert_eq!(categories[1].0, "english");
+    assert_eq!(history::Period::of(&records).wpm, Some(67.5));
+}
@@ -0,0 +1,37 @@
+use wpm_core::{
+    content::ContentProvider,
+    course::{self, LESSONS, Progress},
+    keyboard::Layout,
+};
+
+#[test]
+fn lessons_unlock_in_order() {
+    let mut progress = Progress::default();
+    assert_eq!(progress.next(), Some(0));
+    assert!(progress.unlocked(0) && !progress.unlocked(1));
+
+    // Fast enough but too many mistakes
+    assert!(!progress.attempt(0, 30., 90.));
+    assert!(!progress.unlocked(1));
+    assert!(progress.attempt(0, 12., 96.));
+    assert!(progress.unlocked(1) && !progress.unlocked(2));
+    assert_eq!(progress.next(), Some(1));
+
+    let first = progress.get(0);
+    assert_eq!((first.attempts, first.passed), (2, true));
+    assert_eq!((first.best_wpm, first.best_accuracy), (30., 96.));
+    assert!(!progress.unlocked(LESSONS.len()));
+}
+
+#[test]
+fn lessons_only_use_the_keys_learned_so_far() {
+    let mut drill = course::drill(1, Layout::Qwerty, ["a dark fjord faded"]);
+    let text = drill.next_text(0).unwrap().text;
+    assert!(text.chars().all(|c| "fjdk ".contains(c)), "{text}");
+    // Every other word is made of the new keys
+    let second = text.split(' ').nth(1).unwrap();
+    assert!(second.chars().all(|c| "dk".contains(c)), "{text}");
+
+    // Where f and j are on QWERTY
+    assert_eq!(LESSONS[0].new_keys(Layout::Dvorak), ['u', 'h']);
+}
@@ -0,0 +1,71 @@
+use std::collections::BTreeSet;
+use wpm_core::{
+    content::{ContentProvider, Custom},
+    custom::Drills,
+    letters::Letters,
+    review::{Queue, Session},
+};
+
+const TEXTS: [&str; 2] = [
+    "The zebra grazed by the maze, then the lazy dog was dozing in the haze.",
+    "Then the brown fox ran over the hill and into the old barn at night.",
+];
+
+fn missed(words: &[&str], timestamp: u64) -> Session {
+    Session {
+        missed: words.iter().map(|word| word.to_string()).collect(),
+        clean: BTreeSet::new(),
+        timestamp,
+    }
+}
+
+fn slow_on(bigram: (char, char)) -> Letters {
+    let mut letters = Letters::default();
+    letters.add_bigram('t', 'h', 50, 0, 100.);
+    letters.add_bigram(bigram.0, bigram.1, 10, 10, 900.);
+    letters
+}
+
+#[test]
+fn drills_take_the_worst_words_and_pairs() {
+    let mut queue = Queue::default();
+    queue.add(&missed(&["barn", "hill"], 0));
+    queue.add(&missed(&["hill"], 1));
+
+    let mut drills = Drills::default();
+    let version = drills.build("nemesis", &queue, &slow_on(('z', 'e')), 35., TEXTS, 2);
+    assert_eq!(version.number, 1);
+    assert_eq!(version.words, ["hill", "barn"]);
+    assert_eq!(version.bigrams[0], "ze");
+    assert_eq!(version.bigram_words[..2], ["haze", "maze"]);
+
+    let mut custom = Custom::new("nemesis".to_string(), version.clone());
+    let quote = custom.next_text(0).unwrap();
+    assert_eq!(quote.source, "Drill: nemesis v1");
+    assert!(
+        quote
+            .text
+            .split(' ')
+            .all(|word| version.texts().any(|w| w == word))
+    );
+}
+
+#[test]
+fn drills_get_a_version_when_weaknesses_change() {
+    let mut queue = Queue::default();
+    queue.add(&missed(&["barn"], 0));
+    let mut drills = Drills::default();
+    drills.build("nemesis", &queue, &slow_on(('z', 'e')), 35., TEXTS, 1);
+    // Nothing changed, nothing new
+    drills.build("nemesis", &queue, &slow_on(('z', 'e')), 35., TEXTS, 2);
+    assert_eq!(drills.get("nemesis", None).unwrap().number, 1);
+
+    queue.add(&missed(&["night"], 3));
+    let latest = drills.build("nemesis", &queue, &slow_on(('z', 'e')), 35., TEXTS, 4);
+    assert_eq!(latest.number, 2);
+    assert_eq!(latest.created, 4);
+
+    assert_eq!(drills.get("nemesis", Some(1)).unwrap().words, ["barn"]);
+    assert_eq!(drills.get("nemesis", Some(3)), None);
+    assert_eq!(drills.iter().count(), 1);
+}
@@ -0,0 +1,60 @@
+use wpm_core::{
+    diff::{self, Cell, Mark},
+    engine::Rules,
+    replay::{self, Key, Replay},
+};
+
+fn marks(line: &[Cell]) -> Vec<(&str, Mark)> {
+    line.iter()
+        .map(|cell| (cell.expected.as_str(), cell.mark.clone()))
+        .collect()
+}
+
+#[test]
+fn replays_show_mistakes_left_and_fixed() {
+    let rules = Rules {
+        overtype: true,
+        strict_space: false,
+        ..Rules::default()
+    };
+    // A mistake fixed on the second try, a word cut short, an extra letter, and a test left unfinished
+    let keys = format!("tg{0}j{0}he ct satt h", replay::BACKSPACE)
+        .chars()
+        .enumerate()
+        .map(|(i, key)| Key(i as u32 * 100, key))
+        .collect();
+    let replay = Replay::now(0, vec!["the cat sat here".to_string()], rules, keys);
+
+    let lines = diff::of(&replay);
+    assert_eq!(lines.len(), 1);
+    let fixed = Mark::Fixed(vec!["g".to_string(), "j".to_string()]);
+    let extra = Mark::Extra("t".to_string());
+    let wrong = Mark::Wrong("t".to_string());
+    #[rustfmt::skip]
+    assert_eq!(
+        marks(&lines[0]),
+        [
+            ("t", Mark::Right), ("h", fixed), ("e", Mark::Right), (" ", Mark::Right),
+            ("c", Mark::Right), ("a", wrong), ("t", Mark::Skipped), (" ", Mark::Right),
+            ("s", Mark::Right), ("a", Mark::Right), ("t", Mark::Right), ("", extra),
+            (" ", Mark::Right), ("h", Mark::Right), ("e", Mark::Untyped), ("r", Mark::Untyped),
+            ("e", Mark::Untyped),
+        ]
+    );
+}
+
+#[test]
+fn lines_not_reached_are_untyped() {
+    let keys = "hi"
+        .chars()
+        .enumerate()
+        .map(|(i, key)| Key(i as u32 * 100, key))
+        .collect();
+    let lines = vec!["hi ".to_string(), "you".to_string()];
+    let replay = Replay::now(0, lines, Rules::default(), keys);
+
+    let lines = diff::of(&replay);
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0][..2].iter().all(|cell| cell.mark == Mark::Right));
+    assert!(lines[1].iter().all(|cell| cell.mark == Mark::Untyped));
+}
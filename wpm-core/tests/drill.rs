@@ -0,0 +1,75 @@
+use wpm_core::{
+    content::{ContentProvider, Drill},
+    keyboard::{Fingering, Hand, KeyFilter, Layout, Row},
+};
+
+#[test]
+fn filters_keep_to_a_hand_and_rows() {
+    let left = KeyFilter {
+        hand: Some(Hand::Left),
+        ..KeyFilter::default()
+    };
+    assert_eq!(
+        String::from_iter(left.letters(Layout::Qwerty, &Fingering::new())),
+        "qwertasdfgzxcvb"
+    );
+
+    let mut filter = KeyFilter::default();
+    for word in ["right", "home", "top", "index"] {
+        assert!(filter.add(word));
+    }
+    assert!(!filter.add("thumb"));
+    assert_eq!(
+        String::from_iter(filter.letters(Layout::Qwerty, &Fingering::new())),
+        "yuhj"
+    );
+    assert_eq!(
+        String::from_iter(filter.letters(Layout::Dvorak, &Fingering::new())),
+        "fgdh"
+    );
+    assert_eq!(
+        filter.to_string(),
+        "right hand, home and top rows, index finger"
+    );
+}
+
+#[test]
+fn drills_only_use_the_allowed_keys() {
+    let home = KeyFilter {
+        rows: vec![Row::Home],
+        ..KeyFilter::default()
+    };
+    let letters = home.letters(Layout::Qwerty, &Fingering::new());
+
+    // Too few words fit, they are made up
+    let mut drill = Drill::new(home.to_string(), letters.clone(), ["A lad had a flask."]);
+    let quote = drill.next_text(0).unwrap();
+    assert_eq!(quote.source, "Drill: home row");
+    assert!(quote.text.chars().all(|c| c == ' ' || letters.contains(&c)));
+
+    let words: Vec<String> = (0..40).map(|i| format!("{}as", "d".repeat(i))).collect();
+    let mut drill = Drill::new(home.to_string(), letters, words.iter().map(String::as_str));
+    let quote = drill.next_text(0).unwrap();
+    assert!(
+        quote
+            .text
+            .split(' ')
+            .all(|word| words.contains(&word.to_string()))
+    );
+    assert_eq!(quote.text.split(' ').count(), 25);
+}
+
+#[test]
+fn keys_type_the_emulated_layout() {
+    let typed: String = "Hello, world; [x]"
+        .chars()
+        .map(|c| Layout::Qwerty.emulate(Layout::Colemak, c))
+        .collect();
+    assert_eq!(typed, "Hfiiy, wypiso [x]");
+
+    assert_eq!(Layout::Qwerty.emulate(Layout::Dvorak, 'q'), '\'');
+    assert_eq!(Layout::Qwerty.emulate(Layout::Dvorak, 'Z'), ':');
+    assert_eq!(Layout::Qwerty.emulate(Layout::Workman, 'e'), 'r');
+    // Off the letter rows
+    assert_eq!(Layout::Qwerty.emulate(Layout::Dvorak, '1'), '1');
+}
@@ -0,0 +1,59 @@
+use wpm_core::{
+    content::{ContentProvider, Endurance},
+    endurance::{self, Run},
+    engine::Rules,
+    sim::Simulation,
+};
+
+#[test]
+fn speed_is_taken_minute_by_minute() {
+    let text = "abcd ".repeat(400);
+    let mut sim = Simulation::new(text.trim_end(), Rules::default());
+    // 300 keys in the first minute, then 200 in the second, and half a minute more
+    sim.type_text(&text[..300], 200)
+        .type_text(&text[300..500], 300)
+        .type_text(&text[500..600], 300);
+
+    let speeds = sim.test().minute_speeds();
+    assert_eq!(speeds.len(), 2);
+    assert!((speeds[0] - 60.).abs() < 0.5, "{speeds:?}");
+    assert!((speeds[1] - 40.).abs() < 0.5, "{speeds:?}");
+}
+
+#[test]
+fn fatigue_compares_the_first_third_to_the_last() {
+    let run = Run {
+        per_minute: vec![60., 58., 55., 50., 48., 45.],
+        ..Run::default()
+    };
+    // 59 wpm down to 46.5
+    assert!((run.fatigue().unwrap() + 21.19).abs() < 0.01);
+    let short = Run {
+        per_minute: vec![60., 50.],
+        ..Run::default()
+    };
+    assert_eq!(short.fatigue(), None);
+
+    let runs = [
+        Run {
+            minutes: 10,
+            wpm: 50.,
+            ..Run::default()
+        },
+        Run {
+            minutes: 20,
+            wpm: 70.,
+            ..Run::default()
+        },
+    ];
+    assert_eq!(endurance::best(&runs, 10), Some(50.));
+    assert_eq!(endurance::best(&runs, 30), None);
+}
+
+#[test]
+fn texts_last_the_whole_run() {
+    let mut texts = Endurance::new(["one two three four", "five six", " "], 10);
+    let quote = texts.next_text(0).unwrap();
+    assert!(quote.text.split_whitespace().count() >= 2000);
+    assert_eq!(quote.source, "Endurance: 10 minutes");
+}
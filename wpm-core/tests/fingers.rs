@@ -0,0 +1,57 @@
+use wpm_core::{
+    engine::Rules,
+    keyboard::{self, Finger, Fingering, Hand, Layout},
+    letters::{Letter, Letters},
+    sim::Simulation,
+};
+
+#[test]
+fn tests_time_each_letter() {
+    let mut sim = Simulation::new("hello world", Rules::default());
+    sim.type_text("hellp", 100)
+        .backspace(100)
+        .type_text("o world", 100);
+
+    let letters = sim.test().letters();
+    // The first key has nothing to be timed from
+    assert_eq!(letters.get('h'), None);
+    assert_eq!(
+        letters.get('o'),
+        Some(&Letter {
+            hits: 2,
+            misses: 1,
+            time_ms: 100.
+        })
+    );
+    assert_eq!(letters.get('l').unwrap().hits, 3);
+}
+
+#[test]
+fn fingers_add_up_their_letters() {
+    let mut letters = Letters::default();
+    letters.add('a', 3, 1, 200.);
+    letters.add('f', 4, 0, 100.);
+    letters.add('g', 2, 0, 160.);
+
+    let stats = keyboard::finger_stats(&letters, Layout::Qwerty, &Fingering::new());
+    assert_eq!(stats.len(), 8);
+    let (pinky, index) = (stats[0], stats[3]);
+    assert_eq!(pinky.to_string(), "left pinky");
+    assert_eq!((pinky.keystrokes, pinky.error_rate()), (4, 25.));
+    assert_eq!(index.to_string(), "left index");
+    assert_eq!(index.keystrokes, 6);
+    assert!((index.load - 60.).abs() < 0.01);
+    assert!((index.time_ms - 120.).abs() < 0.01);
+}
+
+#[test]
+fn fingering_moves_letters_to_other_fingers() {
+    let mut letters = Letters::default();
+    letters.add('g', 2, 0, 160.);
+    let fingering = Fingering::from([('g', (Hand::Right, Finger::Index))]);
+
+    let stats = keyboard::finger_stats(&letters, Layout::Qwerty, &fingering);
+    assert_eq!(stats[3].keystrokes, 0);
+    assert_eq!(stats[4].to_string(), "right index");
+    assert_eq!(stats[4].keystrokes, 2);
+}
@@ -0,0 +1,69 @@
+use wpm_core::{
+    content::{
+        ContentProvider, Drill, Flow,
+        flow::{MAX_LEVEL, RAISE_AT},
+    },
+    keyboard::{Fingering, KeyFilter, Layout, Row},
+};
+
+#[test]
+fn accuracy_kept_high_raises_the_level() {
+    let mut flow = Flow::default();
+    flow.record(99.);
+    flow.record(98.);
+    assert_eq!(flow.level(), 0);
+    flow.record(RAISE_AT);
+    assert_eq!(flow.level(), 1);
+    // It takes a whole new run of good tests for the next one
+    flow.record(100.);
+    assert_eq!(flow.level(), 1);
+
+    // A bad test brings it back down at once
+    flow.record(80.);
+    assert_eq!(flow.level(), 0);
+    assert_eq!(Flow::replay([99.; 100]).level(), MAX_LEVEL);
+}
+
+#[test]
+fn higher_levels_favor_long_words_with_rare_letters() {
+    let easy = Flow::default();
+    let hard = Flow::replay([100.; 15]);
+    assert!(easy.weight("the") > easy.weight("quizzically"));
+    assert!(hard.weight("quizzically") > hard.weight("the"));
+    assert!(hard.weight("jazz") > hard.weight("tent"));
+}
+
+#[test]
+fn punctuation_keeps_to_the_keys_allowed() {
+    let words: Vec<String> = (0..200).map(|_| "word".to_string()).collect();
+    assert_eq!(Flow::default().punctuate(&words, |_| true), words.join(" "));
+
+    let hard = Flow::replay([100.; 15]);
+    let text = hard.punctuate(&words, |c| c == ',');
+    assert!(text.contains(','));
+    assert!(!text.contains(['.', ';', '?', '!']));
+    assert!(text.ends_with("word"));
+}
+
+#[test]
+fn adaptive_drills_name_their_level() {
+    let home = KeyFilter {
+        rows: vec![Row::Home],
+        ..KeyFilter::default()
+    };
+    let letters = home.letters(Layout::Qwerty, &Fingering::new());
+    let mut drill = Drill::new(home.to_string(), letters.clone(), ["A lad had a flask."])
+        .adaptive(Flow::default());
+    assert_eq!(
+        drill.next_text(0).unwrap().source,
+        "Drill: home row, level 1"
+    );
+
+    for _ in 0..3 {
+        drill.adapt(100.);
+    }
+    let quote = drill.next_text(0).unwrap();
+    assert_eq!(quote.source, "Drill: home row, level 2");
+    // The home row has ; but no comma or period
+    assert!(quote.text.chars().all(|c| c == ' ' || letters.contains(&c)));
+}
@@ -0,0 +1,71 @@
+use wpm_core::{
+    engine::Rules,
+    latency::{BUCKETS, Histogram},
+    sim::Simulation,
+};
+
+#[test]
+fn intervals_fall_in_their_bars() {
+    let mut histogram = Histogram::default();
+    for interval in [0, 49, 50, 120, 999, 1000, 5000] {
+        histogram.add(interval);
+    }
+    let counts = histogram.counts();
+    assert_eq!(counts[0], 2);
+    assert_eq!(counts[1], 1);
+    assert_eq!(counts[2], 1);
+    assert_eq!(counts[19], 1);
+    // A second and more share the last bar
+    assert_eq!(counts[BUCKETS - 1], 2);
+    assert_eq!(histogram.total(), 7);
+    assert_eq!(histogram.hesitations(), 3);
+    // 499 + 500 + 4500 past the half second
+    assert_eq!(histogram.lost_ms(), 5499);
+}
+
+#[test]
+fn a_test_gives_its_median_and_pauses() {
+    let mut sim = Simulation::new("abcdef ghijkl", Rules::default());
+    sim.type_text("abcdef ", 100)
+        .wait(1500)
+        .type_text("ghijkl", 100);
+
+    let histogram = Histogram::of(sim.test().keys());
+    assert_eq!(histogram.total(), 12);
+    assert_eq!(histogram.median(), 125);
+    assert_eq!(histogram.hesitations(), 1);
+    assert_eq!(histogram.lost_ms(), 1100);
+    assert!((histogram.lost_share() - 1100. / 2700. * 100.).abs() < 0.01);
+}
+
+#[test]
+fn histograms_add_up() {
+    let mut all = Histogram::default();
+    assert_eq!(all.median(), 0);
+    assert_eq!(all.shares(), [0.; BUCKETS]);
+
+    let mut test = Histogram::default();
+    test.add(80);
+    test.add(700);
+    all.merge(&test);
+    all.merge(&test);
+    assert_eq!(all.total(), 4);
+    assert_eq!(all.counts()[1], 2);
+    assert_eq!(all.lost_ms(), 400);
+    assert_eq!(all.shares()[14], 50.);
+}
+
+#[test]
+fn pauses_are_found_in_the_text() {
+    let mut sim = Simulation::new("ab cde", Rules::default());
+    sim.type_text("ab ", 100)
+        .wait(600)
+        .type_text("cx", 100)
+        .wait(900)
+        .backspace(100)
+        .wait(700)
+        .type_text("de", 100);
+
+    // Only keys of the text count, not the backspace fixing the mistake
+    assert_eq!(sim.test().pauses(), [(0, 3), (0, 4)]);
+}
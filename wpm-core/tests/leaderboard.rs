@@ -0,0 +1,83 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread,
+};
+
+use wpm_core::{
+    history::Record,
+    leaderboard::{Cache, Client},
+};
+
+/// Answers `count` requests with `response`, then returns their first line and body.
+fn server(count: usize, response: &'static str) -> (String, thread::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let url = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+    let handle = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for stream in listener.incoming().take(count) {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim().to_lowercase();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(value) = line.strip_prefix("content-length: ") {
+                    length = value.parse().unwrap();
+                }
+                if request.is_empty() {
+                    request = line;
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            requests.push(format!("{request} {}", String::from_utf8(body).unwrap()));
+
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                response.len()
+            )
+            .unwrap();
+        }
+        requests
+    });
+    (url, handle)
+}
+
+#[test]
+fn syncs_only_new_results() {
+    let standings = r#"[
+        {"name": "linus", "best_wpm": 87, "average_wpm": 80, "tests": 3},
+        {"name": "ada", "best_wpm": 112, "average_wpm": 98, "tests": 40}
+    ]"#;
+    let (url, handle) = server(2, standings);
+    let client = Client::new(&url, "secret".to_string());
+    let records = vec![
+        Record::now(1, 0, 60., 95., 10, 10.),
+        Record::now(2, 0, 70., 97., 12, 10.),
+    ];
+    let mut cache = Cache {
+        pushed: 1,
+        ..Cache::default()
+    };
+
+    assert_eq!(client.push_new("ada", &records, &mut cache).unwrap(), 1);
+    assert_eq!(cache.pushed, 2);
+    let standings = client.pull().unwrap();
+    let names: Vec<&str> = standings.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["ada", "linus"]);
+
+    let requests = handle.join().unwrap();
+    assert!(requests[0].starts_with("post /results http/1.1 "));
+    assert!(requests[0].contains(r#""name":"ada""#));
+    assert!(requests[0].contains(r#""quote_id":2"#));
+    assert!(!requests[0].contains(r#""quote_id":1"#));
+    assert!(requests[1].starts_with("get /leaderboard http/1.1"));
+}
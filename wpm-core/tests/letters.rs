@@ -0,0 +1,38 @@
+use wpm_core::letters::Letters;
+
+// Two results as keybr.com exports them, trimmed to a few keys
+const EXPORT: &str = r#"[
+    {
+        "layout": "en-us", "textType": "generator", "timeStamp": "2024-03-01T10:00:00.000Z",
+        "length": 120, "time": 30000, "errors": 2, "speed": 240,
+        "histogram": [
+            {"codePoint": 97, "hitCount": 10, "missCount": 0, "timeToType": 200},
+            {"codePoint": 113, "hitCount": 2, "missCount": 1, "timeToType": 900},
+            {"codePoint": 32, "hitCount": 20, "missCount": 0, "timeToType": 100}
+        ]
+    },
+    {
+        "layout": "en-us", "textType": "generator", "timeStamp": "2024-03-02T10:00:00.000Z",
+        "length": 100, "time": 25000, "errors": 0, "speed": 240,
+        "histogram": [
+            {"codePoint": 65, "hitCount": 10, "missCount": 1, "timeToType": 400}
+        ]
+    }
+]"#;
+
+#[test]
+fn keybr_exports_give_letter_confidence() {
+    let mut letters = Letters::default();
+    assert_eq!(letters.import_keybr(EXPORT).unwrap(), 2);
+
+    let a = letters.get('a').unwrap();
+    assert_eq!((a.hits, a.misses, a.time_ms), (20, 1, 300.));
+    // Spaces are not letters
+    assert!(letters.get(' ').is_none());
+
+    // 35 WPM is about 343 ms a letter
+    assert_eq!(letters.confidence('a', 35.), Some(1.));
+    assert!(letters.confidence('q', 35.).unwrap() < 0.4);
+    assert_eq!(letters.confidence('z', 35.), None);
+    assert_eq!(letters.weakest(35.), ['q', 'a']);
+}
@@ -0,0 +1,47 @@
+use wpm_core::{
+    content::{self, ContentProvider, Library},
+    history::{self, Record},
+    stats::Metric,
+};
+
+fn library(json_metric: &str) -> Library {
+    Library::from_json(&format!(
+        r#"{{"language": "english"{json_metric}, "groups": [[0, 100]], "quotes": []}}"#
+    ))
+    .unwrap()
+}
+
+#[test]
+fn quote_packs_pick_their_metric() {
+    assert_eq!(library("").metric(), Metric::Wpm);
+    assert_eq!(library(r#", "metric": "cpm""#).metric(), Metric::Cpm);
+
+    let library: &'static Library = Box::leak(Box::new(library(r#", "metric": "cpm""#)));
+    assert_eq!(ContentProvider::metric(&library), Metric::Cpm);
+}
+
+#[test]
+fn languages_without_spaces_count_characters() {
+    assert_eq!(content::metric("japanese_1k"), Metric::Cpm);
+    assert_eq!(content::metric("thai"), Metric::Cpm);
+    assert_eq!(content::metric("english"), Metric::Wpm);
+}
+
+#[test]
+fn records_keep_their_metric() {
+    let words = Record::now(1, 0, 80., 97., 10, 10.);
+    let characters = Record {
+        metric: Metric::Cpm,
+        ..Record::now(2, 0, 300., 97., 10, 10.)
+    };
+
+    // Records in words are saved as before
+    assert!(!serde_json::to_string(&words).unwrap().contains("metric"));
+    let json = serde_json::to_string(&characters).unwrap();
+    let read: Record = serde_json::from_str(&json).unwrap();
+    assert_eq!(read.unit(), "cpm");
+
+    let records = [words, characters];
+    assert_eq!(history::personal_best(&records), Some(80.));
+    assert_eq!(history::personal_best_in(&records, Metric::Cpm), Some(300.));
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    time::{Duration, Instant},
+};
+
+use common::record;
+use wpm_core::metrics;
+
+fn get(addr: std::net::SocketAddr, path: &str) -> String {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[test]
+fn scrapes_get_the_history() {
+    let addr = metrics::serve(0, || vec![record(1000, 60., 96.), record(2000, 80., 98.5)]).unwrap();
+
+    let response = get(addr, "/metrics");
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+    for line in [
+        "# TYPE wpm_tests_total counter",
+        "wpm_tests_total 2",
+        "wpm_average_wpm 70",
+        "wpm_best_wpm 80",
+        "wpm_last_accuracy_percent 98.5",
+        "wpm_last_test_timestamp_seconds 2000",
+    ] {
+        assert!(
+            response.lines().any(|l| l == line),
+            "no {line} in {response}"
+        );
+    }
+
+    assert!(get(addr, "/").starts_with("HTTP/1.1 404"));
+}
+
+#[test]
+fn a_silent_client_holds_up_no_scrape() {
+    let addr = metrics::serve(0, Vec::new).unwrap();
+    let _silent = TcpStream::connect(addr).unwrap();
+
+    let start = Instant::now();
+    assert!(get(addr, "/metrics").starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn leaves_out_what_no_test_measured_yet() {
+    let text = metrics::render(&[]);
+    assert!(text.lines().any(|l| l == "wpm_tests_total 0"));
+    assert!(!text.lines().any(|l| l.starts_with("wpm_last_wpm")));
+}
@@ -0,0 +1,63 @@
+use wpm_core::{
+    engine::Rules,
+    keyboard::Layout,
+    mistakes::{self, Kind},
+    sim::Simulation,
+};
+
+fn align(expected: &str, typed: &str) -> Vec<Kind> {
+    let expected: Vec<String> = expected.chars().map(String::from).collect();
+    let typed: Vec<String> = typed.chars().map(String::from).collect();
+    mistakes::align(
+        &expected.iter().map(String::as_str).collect::<Vec<_>>(),
+        &typed.iter().map(String::as_str).collect::<Vec<_>>(),
+        |a, b| a == b,
+        Layout::Qwerty,
+    )
+}
+
+#[test]
+fn words_are_lined_up_with_the_ones_expected() {
+    assert_eq!(align("the", "the"), []);
+    assert_eq!(align("the", "teh"), [Kind::Transposition]);
+    assert_eq!(align("the", "thw"), [Kind::Adjacent]);
+    assert_eq!(align("the", "thp"), [Kind::Other]);
+    assert_eq!(align("their", "ther"), [Kind::Omission]);
+    assert_eq!(align("the", "thee"), [Kind::Insertion]);
+}
+
+#[test]
+fn keys_touch_on_staggered_rows() {
+    assert!(Layout::Qwerty.adjacent('s', 'e'));
+    assert!(Layout::Qwerty.adjacent('s', 'x'));
+    assert!(Layout::Qwerty.adjacent('S', 'a'));
+    assert!(!Layout::Qwerty.adjacent('s', 'r'));
+    assert!(Layout::Qwerty.adjacent('a', 'q'));
+    assert!(!Layout::Qwerty.adjacent('s', 's'));
+    assert!(Layout::Dvorak.adjacent('a', 'o'));
+}
+
+#[test]
+fn tests_sort_the_mistakes_left() {
+    let rules = Rules {
+        overtype: true,
+        strict_space: false,
+        ..Rules::default()
+    };
+    let mut sim = Simulation::new("the cat sat here", rules);
+    // A skipped letter, then an extra one at the end of a word
+    sim.type_text("teh ct satt hwre", 100);
+
+    let mistakes = mistakes::classify(sim.test(), Layout::Qwerty);
+    assert_eq!(
+        (
+            mistakes.transposition,
+            mistakes.omission,
+            mistakes.insertion,
+            mistakes.adjacent
+        ),
+        (1, 1, 1, 1)
+    );
+    assert_eq!(mistakes.total(), 4);
+    assert_eq!(mistakes.share(Kind::Adjacent), 25.);
+}
@@ -0,0 +1,62 @@
+use wpm_core::{
+    content::{ContentProvider, Practice},
+    engine::Rules,
+    letters::Letters,
+    sim::Simulation,
+};
+
+const TEXTS: [&str; 2] = [
+    "The lazy dog was dozing in the haze, and a zebra grazed by the maze.",
+    "Then the brown fox ran over the hill and into the old barn at night.",
+];
+
+#[test]
+fn tests_time_pairs_of_letters() {
+    let mut sim = Simulation::new("hello", Rules::default());
+    sim.type_text("hello", 100);
+
+    let letters = sim.test().letters();
+    assert_eq!(letters.bigram('l', 'l').unwrap().hits, 1);
+    assert_eq!(letters.bigram('e', 'l').unwrap().time_ms, 100.);
+    // Timed on their second letter, which the first one always has
+    assert_eq!(letters.bigram('h', 'e').unwrap().hits, 1);
+}
+
+fn share_with_z(practice: &mut Practice) -> f32 {
+    let words: Vec<String> = (0..8)
+        .flat_map(|_| {
+            practice
+                .next_text(0)
+                .unwrap()
+                .text
+                .split(' ')
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    words.iter().filter(|word| word.contains('z')).count() as f32 / words.len() as f32
+}
+
+#[test]
+fn practice_goes_for_the_weakest_letters() {
+    let mut letters = Letters::default();
+    for c in "abcdefghijklmnopqrstuvwxy".chars() {
+        letters.add(c, 50, 0, 100.);
+    }
+    letters.add('z', 5, 5, 900.);
+
+    let mut practice = Practice::new(letters, 35., TEXTS);
+    assert!(
+        practice
+            .next_text(0)
+            .unwrap()
+            .source
+            .starts_with("Practice: z")
+    );
+    assert!(share_with_z(&mut practice) > 0.5);
+
+    let mut fast_z = Letters::default();
+    fast_z.add('z', 1000, 0, 100.);
+    practice.learn(&fast_z);
+    assert!(share_with_z(&mut practice) < 0.5);
+}
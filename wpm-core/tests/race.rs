@@ -0,0 +1,203 @@
+use std::{
+    io::Write,
+    net::{TcpListener, TcpStream},
+    thread,
+    time::{Duration, Instant},
+};
+
+use wpm_core::{
+    engine::Rules,
+    race::{self, Bot, Race, Start},
+    replay::{BACKSPACE, Key, Replay},
+};
+
+/// Polls until `done` holds, the other end runs on its own threads.
+fn eventually(mut done: impl FnMut() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !done() {
+        assert!(Instant::now() < deadline, "timed out");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn guests_get_the_text_and_standings() {
+    let mut host = Race::host("host".to_string(), 0).unwrap();
+    let port = host.port().unwrap();
+    let mut guest = Race::join("guest".to_string(), ("127.0.0.1", port), None).unwrap();
+    eventually(|| host.standings().len() == 2);
+
+    host.start(Start {
+        quote_id: 7,
+        source: "test".to_string(),
+        lines: vec!["hello ".to_string(), "world".to_string()],
+        rules: Rules::default(),
+    });
+    let mut start = None;
+    eventually(|| {
+        start = guest.take_start();
+        start.is_some()
+    });
+    let start = start.unwrap();
+    assert_eq!(start.quote_id, 7);
+    assert_eq!(start.lines, ["hello ", "world"]);
+
+    guest.report(1., 80., true);
+    eventually(|| host.standings()[0].place.is_some());
+    host.report(1., 60., true);
+    eventually(|| guest.standings().iter().all(|p| p.place.is_some()));
+
+    let standings = guest.standings();
+    assert_eq!(standings[0].name, "guest");
+    assert_eq!(standings[0].place, Some(1));
+    assert_eq!(standings[1].name, "host");
+    assert_eq!(standings[1].place, Some(2));
+}
+
+#[test]
+fn guests_that_stop_reading_hold_nobody_up() {
+    let mut host = Race::host("host".to_string(), 0).unwrap();
+    let port = host.port().unwrap();
+    let mut stalled = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    writeln!(stalled, r#"{{"type": "join", "name": "stalled"}}"#).unwrap();
+    let guest = Race::join("guest".to_string(), ("127.0.0.1", port), None).unwrap();
+    eventually(|| host.standings().len() == 3);
+
+    // Far more than the socket buffers of the stalled guest hold
+    let started = Instant::now();
+    for i in 0..30_000 {
+        host.report(i as f32 / 30_000., 60., false);
+    }
+    assert!(started.elapsed() < Duration::from_secs(5));
+    eventually(|| {
+        guest
+            .standings()
+            .iter()
+            .any(|p| p.name == "host" && p.progress > 0.99)
+    });
+}
+
+#[test]
+fn players_with_the_same_name_are_told_apart() {
+    let host = Race::host("sam".to_string(), 0).unwrap();
+    let _guest = Race::join("sam".to_string(), ("127.0.0.1", host.port().unwrap()), None).unwrap();
+    eventually(|| host.standings().len() == 2);
+
+    let mut names: Vec<String> = host.standings().into_iter().map(|p| p.name).collect();
+    names.sort();
+    assert_eq!(names, ["sam", "sam 2"]);
+}
+
+#[test]
+fn server_players_that_stop_reading_hold_no_room_up() {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || race::serve(listener, || None));
+
+    let mut ann = Race::join("ann".to_string(), addr, None).unwrap();
+    eventually(|| ann.room().is_some());
+    let mut stalled = TcpStream::connect(addr).unwrap();
+    let join = format!(
+        r#"{{"type": "join", "name": "stalled", "room": "{}"}}"#,
+        ann.room().unwrap()
+    );
+    writeln!(stalled, "{join}").unwrap();
+    eventually(|| ann.standings().len() == 2);
+
+    for i in 0..30_000 {
+        ann.report(i as f32 / 30_000., 60., false);
+    }
+    eventually(|| ann.standings().iter().any(|p| p.progress > 0.99));
+    // Nor any other room
+    let carl = Race::join("carl".to_string(), addr, None).unwrap();
+    eventually(|| carl.room().is_some());
+}
+
+#[test]
+fn server_rooms_count_down_and_count_wins() {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        race::serve(listener, || {
+            Some(Start {
+                quote_id: 1,
+                source: "test".to_string(),
+                lines: vec!["go".to_string()],
+                rules: Rules::default(),
+            })
+        })
+    });
+
+    let mut ann = Race::join("ann".to_string(), addr, None).unwrap();
+    eventually(|| ann.room().is_some());
+    let room = ann.room().unwrap();
+    let mut bob = Race::join("bob".to_string(), addr, Some(room.to_lowercase())).unwrap();
+    eventually(|| bob.room().is_some() && ann.standings().len() == 2);
+    assert_eq!(bob.room(), Some(room));
+    // Someone racing alone in another room is not in the standings
+    let _carl = Race::join("carl".to_string(), addr, None).unwrap();
+
+    bob.request_start();
+    eventually(|| ann.countdown().is_some());
+    eventually(|| ann.take_start().is_some());
+    eventually(|| bob.take_start().is_some());
+    assert_eq!(ann.countdown(), None);
+
+    bob.report(1., 90., true);
+    // Each player has a connection of its own, so reports can arrive in any order
+    eventually(|| ann.standings().iter().any(|p| p.place.is_some()));
+    ann.report(1., 70., true);
+    eventually(|| ann.standings().iter().all(|p| p.place.is_some()));
+
+    let standings = ann.standings();
+    assert_eq!(standings.len(), 2);
+    assert_eq!(standings[0].name, "bob");
+    assert_eq!(standings[0].wins, 1);
+    assert_eq!(standings[1].wins, 0);
+}
+
+#[test]
+fn bots_type_at_about_their_speed() {
+    let mut bot = Bot::new("bot".to_string(), 60.);
+    bot.start(300);
+
+    // 300 characters at 60 WPM take a minute, give or take the drift and stumbles
+    let finish = bot.finish_time().as_secs_f32();
+    assert!((50. ..85.).contains(&finish), "finished in {finish}s");
+
+    let mut last = 0.;
+    for second in 0..=90 {
+        let player = bot.player(Duration::from_secs(second));
+        assert!(player.progress >= last);
+        last = player.progress;
+    }
+    assert_eq!(last, 1.);
+    let wpm = bot.player(Duration::from_secs(90)).wpm;
+    assert!((40. ..72.).contains(&wpm), "typed at {wpm} WPM");
+}
+
+#[test]
+fn ghosts_follow_their_replay() {
+    // A key every quarter of a second, with a mistake fixed on the way
+    let keys = format!("hellx{BACKSPACE}o world")
+        .chars()
+        .enumerate()
+        .map(|(i, key)| Key(i as u32 * 250, key))
+        .collect();
+    let replay = Replay::now(0, vec!["hello world".to_string()], Rules::default(), keys);
+    let mut ghost = Bot::from_replay("ghost".to_string(), &replay).unwrap();
+    assert!(ghost.is_ghost());
+    ghost.start(11);
+
+    // The last of 13 keys at 3 seconds
+    let finish = ghost.finish_time().as_secs_f32();
+    assert!((finish - 3.).abs() < 0.01, "finished in {finish}s");
+    // Five keys by the end of the first second, the mistake still there
+    let progress = ghost.player(Duration::from_secs(1)).progress;
+    assert!((progress - 5. / 11.).abs() < 0.01, "typed {progress} at 1s");
+    assert_eq!(ghost.player(Duration::from_secs(3)).progress, 1.);
+
+    let mut unfinished = replay.clone();
+    unfinished.keys.truncate(5);
+    assert!(Bot::from_replay("ghost".to_string(), &unfinished).is_none());
+}
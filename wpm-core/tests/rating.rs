@@ -0,0 +1,60 @@
+use wpm_core::{
+    content::{ContentProvider, Library},
+    rating::{Ratings, Tag},
+};
+
+#[test]
+fn tags_replace_their_opposite_and_toggle_off() {
+    let mut ratings = Ratings::default();
+    ratings.toggle("quotes", 7, Tag::Fun);
+    ratings.toggle("quotes", 7, Tag::Easy);
+    assert_eq!(ratings.tags("quotes", 7), [Tag::Fun, Tag::Easy]);
+
+    ratings.toggle("quotes", 7, Tag::Boring);
+    assert_eq!(ratings.tags("quotes", 7), [Tag::Boring, Tag::Easy]);
+    assert!(!ratings.has("quotes", 7, Tag::Fun));
+
+    ratings.toggle("quotes", 7, Tag::Boring);
+    ratings.toggle("quotes", 7, Tag::Easy);
+    assert!(ratings.tags("quotes", 7).is_empty());
+    assert!(ratings.is_empty());
+}
+
+#[test]
+fn quotes_of_different_sources_are_rated_apart() {
+    let mut ratings = Ratings::default();
+    ratings.toggle("quotes", 7, Tag::Fun);
+    ratings.toggle("french", 7, Tag::Hard);
+    assert_eq!(ratings.tags("quotes", 7), [Tag::Fun]);
+    assert_eq!(ratings.tags("french", 7), [Tag::Hard]);
+    assert!(!ratings.has("french", 7, Tag::Fun));
+    assert_eq!(ratings.len(), 2);
+}
+
+#[test]
+fn ratings_round_trip_with_tag_names() {
+    let mut ratings = Ratings::default();
+    ratings.toggle("quotes", 3, Tag::Hard);
+    let json = serde_json::to_string(&ratings).unwrap();
+    assert_eq!(json, r#"{"sources":{"quotes":{"3":["hard"]}}}"#);
+    assert_eq!(serde_json::from_str::<Ratings>(&json).unwrap(), ratings);
+}
+
+#[test]
+fn quotes_are_picked_among_those_kept() {
+    let library = Library::from_json(
+        r#"{"language": "english", "groups": [[0, 100]], "quotes": [
+            {"text": "one", "source": "", "length": 3, "id": 1},
+            {"text": "two", "source": "", "length": 3, "id": 2},
+            {"text": "three", "source": "", "length": 5, "id": 3}
+        ]}"#,
+    )
+    .unwrap();
+    let mut library: &'static Library = Box::leak(Box::new(library));
+    for _ in 0..5 {
+        let quote = library.next_text_where(0, &|quote| quote.id == 2).unwrap();
+        assert_eq!(quote.text, "two");
+    }
+    assert!(library.next_text_where(0, &|_| false).is_none());
+    assert!(library.next_text_where(1, &|_| true).is_none());
+}
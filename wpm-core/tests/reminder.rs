@@ -0,0 +1,64 @@
+use jiff::{Zoned, civil::date, tz::TimeZone};
+use std::path::Path;
+use wpm_core::{
+    history::Record,
+    reminder::{self, Schedule},
+};
+
+fn at(hour: i8, minute: i8) -> Zoned {
+    date(2024, 5, 17)
+        .at(hour, minute, 0, 0)
+        .to_zoned(TimeZone::UTC)
+        .unwrap()
+}
+
+fn schedule(times: &[&str]) -> Schedule {
+    Schedule::parse(
+        &times
+            .iter()
+            .map(|time| time.to_string())
+            .collect::<Vec<_>>(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn reminders_come_at_the_next_time() {
+    let schedule = schedule(&["18:00", "09:30"]);
+    assert_eq!(schedule.next(&at(8, 0)), Some(at(9, 30)));
+    assert_eq!(schedule.next(&at(9, 30)), Some(at(18, 0)));
+
+    let tomorrow = date(2024, 5, 18)
+        .at(9, 30, 0, 0)
+        .to_zoned(TimeZone::UTC)
+        .unwrap();
+    assert_eq!(schedule.next(&at(20, 0)), Some(tomorrow));
+    assert_eq!(Schedule::default().next(&at(8, 0)), None);
+}
+
+#[test]
+fn invalid_times_are_refused() {
+    let error = Schedule::parse(&["18:00".to_string(), "25:00".to_string()]).unwrap_err();
+    assert_eq!(error, "invalid reminder time: 25:00");
+}
+
+#[test]
+fn practice_adds_up_from_the_start_of_the_day() {
+    let record = |timestamp, seconds| Record {
+        timestamp,
+        ..Record::now(1, 0, 60., 100., 20, seconds)
+    };
+    let records = [record(100, 600.), record(200, 90.), record(300, 30.)];
+    assert_eq!(reminder::minutes_since(&records, 150), 2.);
+    assert_eq!(reminder::minutes_since(&records, 400), 0.);
+}
+
+#[test]
+fn systemd_units_run_the_check_at_each_time() {
+    let timer = schedule(&["09:30", "18:00"]).systemd_timer();
+    assert!(timer.contains("OnCalendar=*-*-* 09:30:00\nOnCalendar=*-*-* 18:00:00\n"));
+    assert!(timer.contains("WantedBy=timers.target"));
+
+    let service = reminder::systemd_service(Path::new("/usr/bin/wpm"));
+    assert!(service.contains("ExecStart=/usr/bin/wpm daemon --once"));
+}
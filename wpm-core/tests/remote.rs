@@ -0,0 +1,79 @@
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    thread,
+    time::Duration,
+};
+
+use wpm_core::content::remote;
+
+const PACK: &str = r#"{"language": "test", "groups": [[0, 100]], "quotes": []}"#;
+
+/// Answers one request with `status` and `headers`, then returns the headers of the request.
+fn answer_once(listener: &TcpListener, status: &'static str, headers: &'static str) -> Vec<String> {
+    let (mut stream, _) = listener.accept().unwrap();
+    let mut request = Vec::new();
+    for line in BufReader::new(stream.try_clone().unwrap()).lines() {
+        let line = line.unwrap();
+        if line.is_empty() {
+            break;
+        }
+        request.push(line.to_lowercase());
+    }
+
+    let body = if status.starts_with("200") { PACK } else { "" };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+    request
+}
+
+#[test]
+fn packs_are_cached_and_revalidated() {
+    let dir = env::temp_dir().join(format!("wpm-remote-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let url = format!("http://{}/pack.json", listener.local_addr().unwrap());
+
+    // Nothing on disk yet
+    assert!(remote::fetch(&dir, "pack", &url, true, Duration::MAX).is_err());
+
+    let server = thread::spawn(move || {
+        let first = answer_once(&listener, "200 OK", "ETag: \"v1\"\r\n");
+        let second = answer_once(&listener, "304 Not Modified", "");
+        (first, second)
+    });
+    assert_eq!(
+        remote::fetch(&dir, "pack", &url, false, Duration::MAX).unwrap(),
+        PACK
+    );
+    // Fresh enough to skip the server
+    assert_eq!(
+        remote::fetch(&dir, "pack", &url, false, Duration::MAX).unwrap(),
+        PACK
+    );
+    // Stale, so asked whether it changed
+    assert_eq!(
+        remote::fetch(&dir, "pack", &url, false, Duration::ZERO).unwrap(),
+        PACK
+    );
+
+    let (first, second) = server.join().unwrap();
+    assert!(!first.iter().any(|line| line.starts_with("if-none-match")));
+    assert!(second.contains(&"if-none-match: \"v1\"".to_string()));
+
+    // The server is gone, the copy on disk still works offline or not
+    assert_eq!(
+        remote::fetch(&dir, "pack", &url, true, Duration::ZERO).unwrap(),
+        PACK
+    );
+    assert_eq!(
+        remote::fetch(&dir, "pack", &url, false, Duration::ZERO).unwrap(),
+        PACK
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,71 @@
+use wpm_core::{
+    content::{ContentProvider, Review},
+    engine::Rules,
+    review::{CLEAN_SESSIONS, Queue, Session},
+    sim::Simulation,
+};
+
+const DAY: u64 = 24 * 60 * 60;
+
+fn played(missed: &[&str], clean: &[&str], timestamp: u64) -> Session {
+    Session {
+        missed: missed.iter().map(|word| word.to_string()).collect(),
+        clean: clean.iter().map(|word| word.to_string()).collect(),
+        timestamp,
+    }
+}
+
+#[test]
+fn sessions_sort_words_by_mistakes_left() {
+    let mut sim = Simulation::new("the cat, sat", Rules::default());
+    sim.type_text("the cst, sat", 100);
+
+    let session = Session::of(sim.test());
+    assert_eq!(
+        session,
+        Session {
+            timestamp: session.timestamp,
+            ..played(&["cat"], &["the", "sat"], 0)
+        }
+    );
+}
+
+#[test]
+fn words_leave_after_clean_sessions_in_a_row() {
+    let mut queue = Queue::default();
+    queue.add(&played(&["cat"], &["the"], 0));
+    assert_eq!(queue.due(0), ["cat"]);
+    assert_eq!(queue.len(), 1);
+
+    queue.add(&played(&[], &["cat"], 10));
+    assert_eq!(queue.get("cat").unwrap().streak, 1);
+    // Typed again before it is due, it doesn't count
+    queue.add(&played(&[], &["cat"], 20));
+    assert_eq!(queue.get("cat").unwrap().streak, 1);
+    assert!(queue.due(20).is_empty());
+    assert_eq!(queue.next_due(), Some(10 + DAY));
+
+    // A miss starts it over
+    queue.add(&played(&["cat"], &[], DAY));
+    assert_eq!(queue.get("cat").unwrap().streak, 0);
+
+    let mut now = DAY;
+    for _ in 0..CLEAN_SESSIONS {
+        assert_eq!(queue.due(now), ["cat"]);
+        queue.add(&played(&[], &["cat"], now));
+        now += 3 * DAY;
+    }
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn reviews_type_the_words_due() {
+    let mut queue = Queue::default();
+    queue.add(&played(&["cat", "dog"], &[], 0));
+    let mut review = Review::new(queue);
+
+    let text = review.next_text(0).unwrap().text;
+    let words: Vec<&str> = text.split(' ').collect();
+    assert_eq!(words.len(), 10);
+    assert!(words.iter().all(|word| ["cat", "dog"].contains(word)));
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+use wpm_core::{
+    replay::{BACKSPACE, Key},
+    rhythm::{self, Cadence},
+};
+
+fn keys(intervals: &[u32]) -> Vec<Key> {
+    let mut ms = 0;
+    let mut keys = vec![Key(0, 'a')];
+    for interval in intervals {
+        ms += interval;
+        keys.push(Key(ms, 'a'));
+    }
+    keys
+}
+
+#[test]
+fn corrections_and_pauses_are_no_part_of_the_rhythm() {
+    let mut keys = keys(&[100, 200, 2000, 100]);
+    keys.push(Key(2500, BACKSPACE));
+    keys.push(Key(2600, 'a'));
+    assert_eq!(rhythm::intervals(&keys), [100, 200, 100]);
+}
+
+#[test]
+fn even_keys_keep_to_their_own_beat() {
+    let cadence = Cadence::of(&keys(&[120; 10]), None).unwrap();
+    assert_eq!(cadence.beat_ms(), 120.);
+    assert_eq!(cadence.score(), 100.);
+    assert_eq!(cadence.off_beat(), 0);
+    assert!(Cadence::of(&keys(&[]), None).is_none());
+}
+
+#[test]
+fn keys_off_the_metronome_lower_the_score() {
+    let cadence = Cadence::of(
+        &keys(&[100, 100, 150, 50]),
+        Some(Duration::from_millis(100)),
+    )
+    .unwrap();
+    assert_eq!(cadence.beat_ms(), 100.);
+    // Half a beat off, twice out of four
+    assert_eq!(cadence.score(), 75.);
+    assert_eq!(cadence.off_beat(), 2);
+}
@@ -0,0 +1,66 @@
+use wpm_core::{
+    engine::{Rules, TypingTest},
+    keyboard::Layout,
+    replay::{BACKSPACE, Key},
+    shift::Penalty,
+    stats::Metric,
+};
+
+fn keys(keys: &[(u32, char)]) -> Vec<Key> {
+    keys.iter().map(|(ms, key)| Key(*ms, *key)).collect()
+}
+
+#[test]
+fn capitals_and_symbols_need_shift() {
+    assert!(Layout::Qwerty.needs_shift('A'));
+    assert!(Layout::Qwerty.needs_shift('?'));
+    assert!(Layout::Qwerty.needs_shift('!'));
+    assert!(!Layout::Qwerty.needs_shift(','));
+    assert!(!Layout::Qwerty.needs_shift('1'));
+    // Digits are shifted on AZERTY
+    assert!(Layout::Azerty.needs_shift('1'));
+    assert!(Layout::Azerty.needs_shift('.'));
+}
+
+#[test]
+fn intervals_around_shift_cost_more() {
+    let keys = keys(&[
+        (0, 'a'),
+        (100, 'b'),
+        (300, 'C'),
+        (500, 'd'),
+        (600, 'e'),
+        (700, 'f'),
+        // A pause and a correction don't count
+        (2000, 'g'),
+        (2050, BACKSPACE),
+    ]);
+    let penalty = Penalty::of(&keys, &[], Layout::Qwerty);
+    assert_eq!(penalty.shifted, 1);
+    assert_eq!(penalty.per_interval(), Some(100.));
+    assert_eq!(penalty.lost_ms(), 200.);
+    assert_eq!(penalty.per_key(), Some(200.));
+}
+
+#[test]
+fn shift_reported_by_the_terminal_counts() {
+    let keys = keys(&[(0, 'a'), (100, 'b'), (400, 'c'), (500, 'd')]);
+    assert_eq!(Penalty::of(&keys, &[], Layout::Qwerty).per_key(), None);
+    assert_eq!(
+        Penalty::of(&keys, &[2], Layout::Qwerty).per_key(),
+        Some(200.)
+    );
+}
+
+#[test]
+fn the_engine_remembers_keys_typed_with_shift() {
+    let mut test = TypingTest::new(vec!["ab Cd".to_string()], Rules::default(), Metric::Wpm);
+    test.type_key('a');
+    test.type_key('b');
+    test.type_key(' ');
+    test.hold_shift(true);
+    test.type_key('C');
+    test.hold_shift(false);
+    test.type_key('d');
+    assert_eq!(test.shifted(), &[3]);
+}
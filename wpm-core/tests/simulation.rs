@@ -0,0 +1,217 @@
+use std::{thread, time::Duration};
+
+use wpm_core::{
+    engine::{Milestone, Rules, SKIPPED, TypingTest},
+    replay::Playback,
+    sim::Simulation,
+    stats::Metric,
+};
+
+const TEXT: &str = "hello world";
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 0.01
+}
+
+#[test]
+fn perfect_run() {
+    let mut sim = Simulation::new(TEXT, Rules::default());
+    sim.type_text(TEXT, 100);
+
+    let test = sim.test();
+    assert!(test.is_done());
+    assert_eq!(sim.milestones(), [Milestone::Finished]);
+    // 11 keys a tenth of a second apart, the timer starts on the first one
+    assert_eq!(sim.elapsed(), Duration::from_secs(1));
+    assert!(close(sim.speed(), 11. / 5. * 60.));
+    assert_eq!(test.accuracy(), 100.);
+    assert_eq!(test.words(), 2);
+}
+
+#[test]
+fn waiting_before_the_first_key_is_free() {
+    let mut sim = Simulation::new(TEXT, Rules::default());
+    sim.wait(5000).type_text(TEXT, 100);
+
+    assert_eq!(sim.elapsed(), Duration::from_secs(1));
+}
+
+#[test]
+fn a_stray_space_before_the_first_key_is_free() {
+    let mut sim = Simulation::new(TEXT, Rules::default());
+    sim.press(' ', 0).wait(5000).type_text(TEXT, 100);
+
+    assert_eq!(sim.elapsed(), Duration::from_secs(1));
+}
+
+#[test]
+fn time_stops_once_done() {
+    let mut sim = Simulation::new(TEXT, Rules::default());
+    sim.type_text(TEXT, 100).wait(5000);
+
+    assert_eq!(sim.elapsed(), Duration::from_secs(1));
+}
+
+#[test]
+fn fixed_mistakes_lower_accuracy() {
+    let mut sim = Simulation::new(TEXT, Rules::default());
+    sim.type_text("hellp", 100)
+        .backspace(100)
+        .type_text("o world", 100);
+
+    let test = sim.test();
+    assert!(test.is_done());
+    assert_eq!(test.live_counts(), (11, 0));
+    assert_eq!(test.corrected(), 1);
+    assert!(close(test.accuracy(), 11. / 12. * 100.));
+    assert!(test.word_errors().is_empty());
+}
+
+#[test]
+fn mistakes_are_counted_per_word() {
+    let mut sim = Simulation::new(TEXT, Rules::default());
+    sim.type_text("hellp world", 100);
+
+    let test = sim.test();
+    assert_eq!(test.incorrect(), 1);
+    assert_eq!(test.word_errors().get("hello"), Some(&1));
+}
+
+#[test]
+fn extra_characters_are_errors() {
+    let mut sim = Simulation::new(TEXT, Rules::default());
+    sim.type_text("hellooo world", 100);
+
+    let test = sim.test();
+    assert!(test.is_done());
+    assert_eq!((test.correct(), test.incorrect()), (11, 2));
+}
+
+#[test]
+fn overtype_off_ignores_extra_characters() {
+    let rules = Rules {
+        overtype: false,
+        ..Rules::default()
+    };
+    let mut sim = Simulation::new(TEXT, rules);
+    sim.type_text("hellooo world", 100);
+
+    assert_eq!(sim.test().incorrect(), 0);
+}
+
+#[test]
+fn stop_on_error_holds_the_caret() {
+    let rules = Rules {
+        stop_on_error: true,
+        ..Rules::default()
+    };
+    let mut sim = Simulation::new(TEXT, rules);
+    sim.type_text("hellpo world", 100);
+
+    let test = sim.test();
+    assert!(test.is_done());
+    assert_eq!(test.incorrect(), 0);
+    assert_eq!(test.corrected(), 1);
+}
+
+#[test]
+fn loose_space_skips_the_word() {
+    let rules = Rules {
+        strict_space: false,
+        ..Rules::default()
+    };
+    let mut sim = Simulation::new(TEXT, rules);
+    sim.type_text("hel world", 100);
+
+    let test = sim.test();
+    assert!(test.is_done());
+    assert_eq!(test.typed()[0][3], SKIPPED);
+    assert_eq!(test.incorrect(), 2);
+}
+
+#[test]
+fn strict_space_ignores_early_space() {
+    let mut sim = Simulation::new(TEXT, Rules::default());
+    sim.type_text("hel ", 100);
+
+    assert_eq!(sim.test().typing().concat(), "hel");
+}
+
+#[test]
+fn delete_word_goes_back_to_the_word_start() {
+    let mut sim = Simulation::new(TEXT, Rules::default());
+    sim.type_text("hello wp", 100).delete_word(100);
+
+    assert_eq!(sim.test().typing().concat(), "hello ");
+    assert_eq!(sim.test().corrected(), 1);
+}
+
+#[test]
+fn spacing_accents_compose_and_ascii_ones_type_themselves() {
+    let mut sim = Simulation::new("été à côté naïve x", Rules::default());
+    sim.type_keys("´et´e ˋa cˆot´e na¨ive", 100);
+    assert_eq!(sim.test().incorrect(), 0);
+    assert_eq!(sim.test().typing().concat(), "été à côté naïve");
+
+    let mut sim = Simulation::new("'e`a^o~n\"i x", Rules::default());
+    sim.type_keys("'e`a^o~n\"i", 100);
+    assert_eq!(sim.test().typing().concat(), "'e`a^o~n\"i");
+}
+
+#[test]
+fn lines_complete_one_by_one() {
+    let text = "the quick brown fox jumps over the lazy dog and keeps on running far away";
+    let mut sim = Simulation::new(text, Rules::default());
+    sim.type_text(text, 100);
+
+    let test = sim.test();
+    assert_eq!(test.lines().len(), 2);
+    assert_eq!(
+        sim.milestones(),
+        [Milestone::LineCompleted, Milestone::Finished]
+    );
+    assert_eq!(test.splits().len(), 2);
+    assert_eq!(test.splits().iter().sum::<Duration>(), sim.elapsed());
+}
+
+#[test]
+fn replay_keeps_the_timing() {
+    let mut sim = Simulation::new(TEXT, Rules::default());
+    sim.type_text("hellp", 100)
+        .backspace(250)
+        .type_text("o world", 100);
+
+    let replay = sim.test().replay(1);
+    let times: Vec<u32> = replay.keys.iter().map(|key| key.0).collect();
+    assert_eq!(times[..7], [0, 100, 200, 300, 400, 650, 750]);
+
+    let mut again = Simulation::with_lines(replay.lines.clone(), replay.rules);
+    let mut last = 0;
+    for key in &replay.keys {
+        again.press(key.1, (key.0 - last) as u64);
+        last = key.0;
+    }
+    assert_eq!(again.elapsed(), sim.elapsed());
+    assert_eq!(again.test().accuracy(), sim.test().accuracy());
+}
+
+#[test]
+fn playback_keeps_the_timing_at_any_speed() {
+    let mut sim = Simulation::new(TEXT, Rules::default());
+    sim.type_text(TEXT, 100);
+    let replay = sim.test().replay(1);
+
+    let mut playback = Playback::new(replay.keys);
+    playback.faster();
+    playback.faster();
+    let mut test = TypingTest::new(replay.lines, replay.rules, Metric::Wpm);
+    test.set_clock(playback.clock());
+    // A second of the replay at 4x
+    thread::sleep(Duration::from_millis(260));
+    playback.advance();
+    while let Some(key) = playback.next_key() {
+        test.play_key(key);
+    }
+    assert!(test.is_done());
+    assert_eq!(test.elapsed(), sim.elapsed());
+}
@@ -0,0 +1,69 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread,
+};
+
+use wpm_core::{
+    history::Record,
+    sink::{StatsSink, Webhook},
+};
+
+#[test]
+fn webhooks_post_results_as_json() {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        let mut head = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                break;
+            }
+            head.push(line.trim().to_string());
+        }
+        let length: usize = head
+            .iter()
+            .find_map(|line| {
+                line.to_lowercase()
+                    .strip_prefix("content-length: ")?
+                    .parse()
+                    .ok()
+            })
+            .unwrap();
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+
+        let mut stream = stream;
+        write!(
+            stream,
+            "HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n"
+        )
+        .unwrap();
+        (head, String::from_utf8(body).unwrap())
+    });
+
+    let mut webhook = Webhook::spawn(
+        format!("http://127.0.0.1:{port}/results"),
+        Some("Bearer secret".to_string()),
+    );
+    let record = Record::now(42, 1, 87.5, 96., 12, 8.2);
+    webhook.record(&record, None);
+    // Waits for the request to go out
+    drop(webhook);
+
+    let (head, body) = server.join().unwrap();
+    assert_eq!(head[0], "POST /results HTTP/1.1");
+    assert!(
+        head.iter()
+            .any(|line| line.eq_ignore_ascii_case("authorization: bearer secret"))
+    );
+
+    let sent: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(sent["quote_id"], 42);
+    assert_eq!(sent["wpm"], 87.5);
+}
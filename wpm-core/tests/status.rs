@@ -0,0 +1,30 @@
+mod common;
+
+use common::record;
+use wpm_core::status;
+
+#[test]
+fn sums_up_today_and_all_time() {
+    let records = [
+        record(100, 120., 90.),
+        record(1000, 60., 96.),
+        record(2000, 80., 98.),
+    ];
+    let format = "{avg_wpm_today}/{best_wpm_today} wpm, {accuracy_today}% in {tests_today} tests \
+                  (best {best_wpm}, last {last_wpm}, {tests} tests)";
+
+    assert_eq!(
+        status::summary(format, &records, 1000),
+        "70/80 wpm, 97% in 2 tests (best 120, last 80, 3 tests)"
+    );
+}
+
+#[test]
+fn marks_missing_values() {
+    let records = [record(100, 50., 90.)];
+
+    assert_eq!(
+        status::summary("{avg_wpm_today} wpm {tests_today} {streak}", &records, 1000),
+        "- wpm 0 {streak}"
+    );
+}
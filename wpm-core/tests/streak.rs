@@ -0,0 +1,28 @@
+use wpm_core::{
+    engine::Rules,
+    history::{self, Record},
+    sim::Simulation,
+};
+
+#[test]
+fn streaks_count_right_keys_in_a_row() {
+    let mut sim = Simulation::new("the cat sat on the mat", Rules::default());
+    sim.type_text("the cx", 100)
+        .backspace(100)
+        .type_text("at sat on the mat", 100);
+
+    let test = sim.test();
+    // Fixing the mistake doesn't break the streak after it
+    assert_eq!(test.clean_streak(), 17);
+    assert_eq!(test.record(1, 0).clean_streak, 17);
+}
+
+#[test]
+fn longest_streak_of_all_results() {
+    let records = [40, 120, 0].map(|clean_streak| Record {
+        clean_streak,
+        ..Record::now(1, 0, 60., 95., 10, 10.)
+    });
+    assert_eq!(history::longest_clean_streak(&records), 120);
+    assert_eq!(history::longest_clean_streak(&[]), 0);
+}
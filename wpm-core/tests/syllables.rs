@@ -0,0 +1,12 @@
+use wpm_core::content::syllables;
+
+#[test]
+fn words_split_in_syllables() {
+    assert_eq!(syllables("dyslexia"), ["dys", "le", "xia"]);
+    assert_eq!(syllables("typing"), ["ty", "ping"]);
+    assert_eq!(syllables("mother,"), ["mo", "ther,"]);
+    // A final e is silent
+    assert_eq!(syllables("make"), ["make"]);
+    assert_eq!(syllables("the"), ["the"]);
+    assert_eq!(syllables(""), [""]);
+}
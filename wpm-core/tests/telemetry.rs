@@ -0,0 +1,45 @@
+use std::{
+    net::TcpStream,
+    thread,
+    time::{Duration, Instant},
+};
+
+use wpm_core::{
+    engine::{Rules, TypingTest},
+    stats::Metric,
+    telemetry::{Frame, Telemetry},
+};
+
+#[test]
+fn overlays_get_the_caret_as_it_moves() {
+    let mut telemetry = Telemetry::serve(0).unwrap();
+    // Connects and never says a word, the overlay gets in anyway
+    let _silent = TcpStream::connect(("127.0.0.1", telemetry.port())).unwrap();
+    let (mut overlay, _) =
+        tungstenite::connect(format!("ws://127.0.0.1:{}", telemetry.port())).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while telemetry.clients() == 0 {
+        assert!(Instant::now() < deadline, "timed out");
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let mut test = TypingTest::new(
+        vec!["hello world".to_string()],
+        Rules::default(),
+        Metric::Wpm,
+    );
+    telemetry.update(Frame::of(&test, 7));
+    // Nothing moved, nothing sent
+    telemetry.update(Frame::of(&test, 7));
+    test.type_text("hel");
+    telemetry.update(Frame::of(&test, 7));
+
+    let mut read = || -> Frame {
+        let message = overlay.read().unwrap();
+        serde_json::from_str(message.to_text().unwrap()).unwrap()
+    };
+    let first = read();
+    assert_eq!((first.quote_id, first.column), (7, 0));
+    let second = read();
+    assert_eq!((second.line, second.column, second.done), (0, 3, false));
+}
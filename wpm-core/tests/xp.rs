@@ -0,0 +1,45 @@
+mod common;
+
+use common::record;
+use wpm_core::{
+    history::Record,
+    xp::{self, Level},
+};
+
+fn scored(words: u32, accuracy: f32, difficulty: f32) -> Record {
+    Record {
+        words,
+        difficulty,
+        ..record(0, 60., accuracy)
+    }
+}
+
+#[test]
+fn points_follow_length_accuracy_and_difficulty() {
+    assert_eq!(xp::points(&scored(20, 100., 0.)), 200);
+    assert_eq!(xp::points(&scored(40, 100., 0.)), 400);
+    assert_eq!(xp::points(&scored(20, 100., 10.)), 400);
+    assert_eq!(xp::points(&scored(20, 95., 0.)), 163);
+    assert!(xp::points(&scored(20, 80., 0.)) < 100);
+    assert_eq!(xp::points(&scored(0, 100., 5.)), 0);
+}
+
+#[test]
+fn each_level_takes_longer() {
+    let first = Level::of(0);
+    assert_eq!((first.level, first.xp, first.needed), (1, 0, 500));
+    assert_eq!(Level::of(499).level, 1);
+
+    let second = Level::of(500);
+    assert_eq!((second.level, second.xp, second.needed), (2, 0, 1000));
+    let third = Level::of(1750);
+    assert_eq!((third.level, third.xp, third.needed), (3, 250, 1500));
+    assert_eq!(third.progress(), 250. / 1500. * 100.);
+}
+
+#[test]
+fn the_history_adds_up() {
+    let records = [scored(20, 100., 0.), scored(40, 100., 10.)];
+    assert_eq!(xp::total(&records), 1000);
+    assert_eq!(Level::of(xp::total(&records)).level, 2);
+}